@@ -0,0 +1,109 @@
+//! Binary data for the `Bytes` custom scalar.
+//!
+//! With the `bytes` feature enabled, the generated code uses [`Base64Bytes`] for a custom scalar named `Bytes`
+//! instead of expecting a type in the parent module. The value is transmitted as a base64 string (standard alphabet, with padding).
+//!
+//! The mapping can be overridden with the `scalars` option of the `graphql` attribute, for example
+//! `scalars = "Bytes = crate::MyBytes"`.
+
+use base64;
+use bytes_crate::Bytes;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+
+/// Binary data, serialized as a base64 string.
+///
+/// ```
+/// # extern crate graphql_client;
+/// # extern crate serde_json;
+/// use graphql_client::bytes::Base64Bytes;
+///
+/// let value = Base64Bytes::from(b"hello".to_vec());
+/// assert_eq!(serde_json::to_string(&value).unwrap(), "\"aGVsbG8=\"");
+///
+/// let parsed: Base64Bytes = serde_json::from_str("\"aGVsbG8=\"").unwrap();
+/// assert_eq!(&parsed[..], b"hello");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Base64Bytes(pub Bytes);
+
+impl Base64Bytes {
+    /// The underlying bytes.
+    pub fn into_inner(self) -> Bytes {
+        self.0
+    }
+}
+
+impl Deref for Base64Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Base64Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Bytes> for Base64Bytes {
+    fn from(bytes: Bytes) -> Self {
+        Base64Bytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for Base64Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Base64Bytes(bytes.into())
+    }
+}
+
+impl From<Base64Bytes> for Bytes {
+    fn from(bytes: Base64Bytes) -> Self {
+        bytes.0
+    }
+}
+
+impl Serialize for Base64Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::encode(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Base64Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Base64Visitor;
+
+        impl<'de> Visitor<'de> for Base64Visitor {
+            type Value = Base64Bytes;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a base64 string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Base64Bytes, E> {
+                base64::decode(value)
+                    .map(Base64Bytes::from)
+                    .map_err(|err| E::custom(format!("invalid base64: {}", err)))
+            }
+        }
+
+        deserializer.deserialize_str(Base64Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn invalid_base64_is_rejected() {
+        let err = serde_json::from_str::<Base64Bytes>("\"not base64!\"").unwrap_err();
+        assert!(err.to_string().starts_with("invalid base64"));
+    }
+}
@@ -2,26 +2,67 @@
 //!
 //! The main interface to this library is the custom derive that generates modules from a GraphQL query and schema. See the docs for the [`GraphQLQuery`] trait for a full example.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(warnings)]
 #![deny(missing_docs)]
 
+// Re-exported for the code generated with the `no_std` option.
+#[doc(hidden)]
+#[cfg_attr(not(feature = "std"), macro_use)]
+pub extern crate alloc;
+#[cfg(feature = "std")]
+extern crate core;
 extern crate itertools;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 pub extern crate graphql_query_derive;
 
+#[doc(hidden)]
 #[cfg_attr(test, macro_use)]
-extern crate serde_json;
+pub extern crate serde_json;
+#[cfg(feature = "well_known_scalars")]
+extern crate bigdecimal;
+#[cfg(feature = "bytes")]
+extern crate base64;
+#[cfg(feature = "bytes")]
+extern crate bytes as bytes_crate;
+#[cfg(feature = "reqwest")]
+extern crate reqwest;
+#[cfg(feature = "validation")]
+extern crate regex;
 
 #[doc(hidden)]
 pub use graphql_query_derive::*;
 
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::fmt::{self, Display};
+
+// `alloc` has no hash map: without the `std` feature, the maps in errors and dynamic queries are `BTreeMap`s.
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 use itertools::Itertools;
 
+#[cfg(feature = "well_known_scalars")]
+pub mod well_known_scalars;
+
+#[cfg(feature = "bytes")]
+pub mod bytes;
+
+pub mod validation;
+
+/// The types used with most queries, for `use graphql_client::prelude::*;`.
+pub mod prelude {
+    pub use super::{Error, GraphQLQuery, OperationType, PathFragment, QueryBody, Response};
+}
+
 /// A convenience trait that can be used to build a GraphQL request body.
 ///
 /// This will be implemented for you by codegen in the normal case. It is implemented on the struct you place the derive on.
@@ -71,21 +112,75 @@ use itertools::Itertools;
 /// ```
 pub trait GraphQLQuery {
     /// The shape of the variables expected by the query. This should be a generated struct most of the time.
-    type Variables: serde::Serialize;
+    type Variables: serde::Serialize + QueryVariables;
     /// The top-level shape of the response data (the `data` field in the GraphQL response). In practice this should be generated, since it is hard to write by hand without error.
     type ResponseData: for<'de> serde::Deserialize<'de>;
 
     /// Produce a GraphQL query struct that can be JSON serialized and sent to a GraphQL API.
     fn build_query(variables: Self::Variables) -> QueryBody<Self::Variables>;
+
+    /// The hex-encoded SHA-256 hash of the query document, computed at compile time. This is the key for persisted queries (APQ), which lets clients avoid hashing the document at runtime.
+    ///
+    /// There is no default, since the document is only known through [`build_query`](GraphQLQuery::build_query). Manual implementations return the hash of their document, as a string literal.
+    fn document_hash() -> &'static str;
+
+    /// The JSON representation of the variables, as sent in the request body. This is convenient for middleware and logging that do not know the concrete `Variables` type.
+    fn variables_as_json(variables: &Self::Variables) -> serde_json::Value {
+        serde_json::to_value(variables).expect("variables serialization")
+    }
+
+    /// Whether the operation is a query, a mutation or a subscription, for middleware that treats them differently (e.g. sending only queries with GET). The default is `Query`: derived implementations override it with the type of the selected operation, and manual implementations of mutations and subscriptions should too.
+    fn operation_type() -> OperationType {
+        OperationType::Query
+    }
+}
+
+/// The variables of an operation, as sent in a [`QueryBody`]. The derive implements it for the generated `Variables` structs.
+pub trait QueryVariables {
+    /// Whether there is nothing to send, in which case the `variables` key is left out of the request body.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl QueryVariables for () {
+    fn is_empty(&self) -> bool {
+        true
+    }
+}
+
+impl QueryVariables for serde_json::Value {
+    fn is_empty(&self) -> bool {
+        match self {
+            serde_json::Value::Null => true,
+            serde_json::Value::Object(map) => map.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+/// The type of a GraphQL operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationType {
+    /// A read-only fetch.
+    Query,
+    /// A write followed by a fetch.
+    Mutation,
+    /// A long-lived request that fetches data in response to source events.
+    Subscription,
 }
 
 /// The form in which queries are sent over HTTP in most implementations. This will be built using the [`GraphQLQuery`] trait normally.
 #[derive(Debug, Serialize, Deserialize)]
+#[must_use = "the query body does nothing unless it is sent"]
 pub struct QueryBody<Variables>
 where
-    Variables: serde::Serialize,
+    Variables: serde::Serialize + QueryVariables,
 {
     /// The values for the variables. They must match those declared in the queries. This should be the `Variables` struct from the generated module corresponding to the query.
+    ///
+    /// The `variables` key is omitted when the variables are empty (see [`QueryVariables::is_empty`]), as for operations without variables.
+    #[serde(skip_serializing_if = "variables_are_empty")]
     pub variables: Variables,
     /// The GraphQL query, as a string.
     pub query: &'static str,
@@ -94,8 +189,173 @@ where
     pub operation_name: &'static str,
 }
 
+impl<Variables: serde::Serialize + QueryVariables> QueryBody<Variables> {
+    /// The request body as minified JSON, as it is sent. `Display` pretty-prints it instead.
+    pub fn display_compact(&self) -> String {
+        serde_json::to_string(self).expect("query body serialization")
+    }
+}
+
+/// Pretty-prints the request body as JSON, for debugging.
+impl<Variables: serde::Serialize + QueryVariables> Display for QueryBody<Variables> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let json = serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+/// The request body as JSON, for sending queries with your own `reqwest` client. Requires the `reqwest` feature.
+///
+/// ```rust,ignore
+/// let response = reqwest::Client::new()
+///     .post("https://api.example.com/graphql")
+///     .header(reqwest::header::CONTENT_TYPE, "application/json")
+///     .body(MyQuery::build_query(variables))
+///     .send()?;
+/// ```
+///
+/// # Panics
+///
+/// When the variables fail to serialize, for example with a map that has non-string keys.
+#[cfg(feature = "reqwest")]
+impl<Variables: serde::Serialize + QueryVariables> From<QueryBody<Variables>> for reqwest::Body {
+    fn from(body: QueryBody<Variables>) -> Self {
+        serde_json::to_vec(&body)
+            .expect("query body serialization")
+            .into()
+    }
+}
+
+fn variables_are_empty<Variables: QueryVariables>(variables: &Variables) -> bool {
+    variables.is_empty()
+}
+
+/// Percent-encodes the JSON representation of the variables, for the `variables` parameter of GET requests (`?variables=...`).
+///
+/// ```
+/// # extern crate graphql_client;
+/// # #[macro_use]
+/// # extern crate serde_json;
+/// use graphql_client::{query_as_url_query, variables_as_url_query};
+///
+/// # fn main() -> Result<(), serde_json::Error> {
+/// let url = format!(
+///     "https://api.example.com/graphql?query={}&variables={}",
+///     query_as_url_query("query User($id: ID!) { user(id: $id) { name } }"),
+///     variables_as_url_query(&json!({ "id": "1234" }))?,
+/// );
+///
+/// assert_eq!(
+///     url,
+///     "https://api.example.com/graphql?query=query%20User%28%24id%3A%20ID%21%29%20%7B%20user%28id%3A%20%24id%29%20%7B%20name%20%7D%20%7D&variables=%7B%22id%22%3A%221234%22%7D"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn variables_as_url_query<V: serde::Serialize>(
+    variables: &V,
+) -> Result<String, serde_json::Error> {
+    let json = serde_json::to_string(variables)?;
+    Ok(percent_encode(&json))
+}
+
+/// Percent-encodes a query document, for the `query` parameter of GET requests (`?query=...`). See [`variables_as_url_query`].
+pub fn query_as_url_query(query: &str) -> String {
+    percent_encode(query)
+}
+
+/// Encodes every byte except the unreserved characters of RFC 3986, so the value is safe anywhere in a query string.
+fn percent_encode(value: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let mut encoded = String::with_capacity(value.len());
+    for &byte in value.as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => {
+                encoded.push('%');
+                encoded.push(HEX[usize::from(byte >> 4)] as char);
+                encoded.push(HEX[usize::from(byte & 0xF)] as char);
+            }
+        }
+    }
+    encoded
+}
+
+/// A query whose document and variables are only known at runtime, for query builders and proxies.
+///
+/// It serializes to the same request body as [`QueryBody`], so it can be sent the same way. The response data can be deserialized as a `Response<serde_json::Value>`.
+///
+/// Since [`GraphQLQuery::build_query`] has no access to runtime values and returns a `&'static str` query, this does not implement [`GraphQLQuery`].
+///
+/// ```
+/// # extern crate graphql_client;
+/// # #[macro_use]
+/// # extern crate serde_json;
+/// # use graphql_client::DynamicQuery;
+/// # fn main() -> Result<(), serde_json::Error> {
+/// let query = DynamicQuery::new("query User($id: ID!) { user(id: $id) { name } }")
+///     .operation_name("User")
+///     .variable("id", "1234")?;
+///
+/// assert_eq!(
+///     serde_json::to_value(&query)?,
+///     json!({
+///         "query": "query User($id: ID!) { user(id: $id) { name } }",
+///         "operationName": "User",
+///         "variables": { "id": "1234" },
+///     })
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DynamicQuery {
+    /// The values for the variables, by name.
+    pub variables: HashMap<String, serde_json::Value>,
+    /// The GraphQL query, as a string.
+    pub query: String,
+    /// The name of the operation to run, required when the document contains several operations.
+    #[serde(
+        rename = "operationName",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub operation_name: Option<String>,
+}
+
+impl DynamicQuery {
+    /// A query without variables or operation name.
+    pub fn new<Q: Into<String>>(query: Q) -> DynamicQuery {
+        DynamicQuery {
+            variables: HashMap::new(),
+            query: query.into(),
+            operation_name: None,
+        }
+    }
+
+    /// Selects the operation to run in the query document.
+    pub fn operation_name<N: Into<String>>(mut self, operation_name: N) -> DynamicQuery {
+        self.operation_name = Some(operation_name.into());
+        self
+    }
+
+    /// Sets the value of a variable. This fails if the value cannot be serialized to JSON.
+    pub fn variable<N, V>(mut self, name: N, value: V) -> Result<DynamicQuery, serde_json::Error>
+    where
+        N: Into<String>,
+        V: serde::Serialize,
+    {
+        self.variables
+            .insert(name.into(), serde_json::to_value(value)?);
+        Ok(self)
+    }
+}
+
 /// Represents a location inside a query string. Used in errors. See [`Error`].
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Location {
     /// The line number in the query string where the error originated (starting from 1).
     pub line: i32,
@@ -104,7 +364,7 @@ pub struct Location {
 }
 
 /// Part of a path in a query. It can be an object key or an array index. See [`Error`].
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(untagged)]
 pub enum PathFragment {
     /// A key inside an object
@@ -190,7 +450,7 @@ impl Display for PathFragment {
 /// #     Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Error {
     /// The human-readable error message. This is the only required field.
     pub message: String,
@@ -202,6 +462,49 @@ pub struct Error {
     pub extensions: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// Errors are sorted by `path` (errors without a path first, then lexicographically, keys before indices), then by `message`.
+///
+/// The `locations` and `extensions` only break ties, so that the order is consistent with `PartialEq`.
+impl Ord for Error {
+    fn cmp(&self, other: &Error) -> Ordering {
+        self.path
+            .cmp(&other.path)
+            .then_with(|| self.message.cmp(&other.message))
+            .then_with(|| self.locations.cmp(&other.locations))
+            .then_with(|| sorted_extensions(self).cmp(&sorted_extensions(other)))
+    }
+}
+
+impl PartialOrd for Error {
+    fn partial_cmp(&self, other: &Error) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `serde_json::Value` is not `Ord`, so extensions are compared by key, then by the JSON of the value.
+fn sorted_extensions(error: &Error) -> Option<Vec<(&String, String)>> {
+    error.extensions.as_ref().map(|extensions| {
+        let mut extensions: Vec<(&String, String)> = extensions
+            .iter()
+            .map(|(key, value)| (key, value.to_string()))
+            .collect();
+        extensions.sort();
+        extensions
+    })
+}
+
+impl Error {
+    /// Whether the error is about a specific field, as indicated by its `path`: the other fields may have been resolved, so the `data` of the response can be partially usable.
+    pub fn is_partial_success(&self) -> bool {
+        self.path.as_ref().is_some_and(|path| !path.is_empty())
+    }
+
+    /// Whether the error is about the request as a whole (parsing, validation...), as indicated by the absence of a `path`. There is no `data` in this case.
+    pub fn is_request_error(&self) -> bool {
+        !self.is_partial_success()
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Use `/` as a separator like JSON Pointer.
@@ -278,6 +581,7 @@ impl Display for Error {
 /// # }
 /// ```
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[must_use = "the response may contain errors that should be handled"]
 pub struct Response<Data> {
     /// The absent, partial or complete response data.
     pub data: Option<Data>,
@@ -285,9 +589,348 @@ pub struct Response<Data> {
     pub errors: Option<Vec<Error>>,
 }
 
+/// Splits a response into its data, or its errors. The result is an `Err` when there are errors, in which case partial data is dropped, and when there is no data: the spec requires errors then, but the list is empty if the server left them out. `Ok` always holds `Some` data.
+///
+/// ```
+/// # extern crate graphql_client;
+/// # #[macro_use]
+/// # extern crate serde_json;
+/// use graphql_client::{Error, Response};
+///
+/// # fn main() {
+/// let response: Response<Vec<String>> = serde_json::from_value(json!({
+///     "data": null,
+///     "errors": [{ "message": "Unauthorized" }],
+/// })).unwrap();
+///
+/// let data: Result<_, Vec<Error>> = response.into();
+/// assert_eq!(data.unwrap_err()[0].message, "Unauthorized");
+/// # }
+/// ```
+impl<Data> From<Response<Data>> for Result<Option<Data>, Vec<Error>> {
+    fn from(response: Response<Data>) -> Self {
+        match (response.data, response.errors) {
+            (_, Some(errors)) if !errors.is_empty() => Err(errors),
+            (None, errors) => Err(errors.unwrap_or_default()),
+            (data, _) => Ok(data),
+        }
+    }
+}
+
+/// The value of a nullable list variable. GraphQL coerces a single item to a list containing only that item, so the variable can be sent either way.
+///
+/// ```
+/// # extern crate graphql_client;
+/// # #[macro_use]
+/// # extern crate serde_json;
+/// use graphql_client::CoercedList;
+///
+/// # fn main() {
+/// let single: CoercedList<&str> = "ada".into();
+/// let list: CoercedList<&str> = vec!["ada", "grace"].into();
+///
+/// assert_eq!(serde_json::to_value(&single).unwrap(), json!("ada"));
+/// assert_eq!(serde_json::to_value(&list).unwrap(), json!(["ada", "grace"]));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CoercedList<T> {
+    /// A list, serialized as an array.
+    List(Vec<T>),
+    /// A single item, serialized as is.
+    Single(T),
+}
+
+impl<T> CoercedList<T> {
+    /// The items of the list, as the server sees them after coercion.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            CoercedList::List(items) => items,
+            CoercedList::Single(item) => vec![item],
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for CoercedList<T> {
+    fn from(items: Vec<T>) -> Self {
+        CoercedList::List(items)
+    }
+}
+
+impl<T> From<T> for CoercedList<T> {
+    fn from(item: T) -> Self {
+        CoercedList::Single(item)
+    }
+}
+
+/// The value of a list field with a `@stream` directive in the query.
+///
+/// The initial response only holds the first items of the list, possibly none. The others arrive in later incremental payloads, and are appended with [`Extend`]. It (de)serializes as a plain list.
+///
+/// ```
+/// # extern crate graphql_client;
+/// # extern crate serde_json;
+/// use graphql_client::StreamedField;
+///
+/// let mut friends: StreamedField<String> = serde_json::from_str(r#"["ada"]"#).unwrap();
+/// // The `items` of an incremental payload for the field.
+/// friends.extend(vec!["grace".to_string()]);
+///
+/// assert_eq!(friends.len(), 2);
+/// assert_eq!(friends.into_vec(), vec!["ada", "grace"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StreamedField<T> {
+    items: Vec<T>,
+}
+
+impl<T> StreamedField<T> {
+    /// The items received so far.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// The items received so far, as a `Vec`.
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> ::core::ops::Deref for StreamedField<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T> Extend<T> for StreamedField<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.items.extend(items)
+    }
+}
+
+impl<T> From<Vec<T>> for StreamedField<T> {
+    fn from(items: Vec<T>) -> Self {
+        StreamedField { items }
+    }
+}
+
+impl<T> IntoIterator for StreamedField<T> {
+    type Item = T;
+    type IntoIter = ::alloc::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+/// The value of a nullable field marked `@semanticNonNull` in the schema.
+///
+/// Such a field is only ever null because of an error, so `null` deserializes to `SemanticResult::Error` rather than to an intentional absence of value. The error itself is in the top-level `errors` of the response, with the path of the field: see [`SemanticResult::error_in`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SemanticResult<T> {
+    /// The value of the field.
+    Value(T),
+    /// The field could not be resolved.
+    Error,
+}
+
+impl<T> SemanticResult<T> {
+    /// The value of the field, if there was no error.
+    pub fn ok(self) -> Option<T> {
+        match self {
+            SemanticResult::Value(value) => Some(value),
+            SemanticResult::Error => None,
+        }
+    }
+
+    /// Borrows the value of the field.
+    pub fn as_ref(&self) -> SemanticResult<&T> {
+        match self {
+            SemanticResult::Value(value) => SemanticResult::Value(value),
+            SemanticResult::Error => SemanticResult::Error,
+        }
+    }
+
+    /// Whether the field could not be resolved.
+    pub fn is_error(&self) -> bool {
+        match self {
+            SemanticResult::Value(_) => false,
+            SemanticResult::Error => true,
+        }
+    }
+
+    /// The error for the field at `path` among the `errors` of the response, if the field could not be resolved.
+    pub fn error_in<'a>(&self, errors: &'a [Error], path: &[PathFragment]) -> Option<&'a Error> {
+        if !self.is_error() {
+            return None;
+        }
+
+        errors
+            .iter()
+            .find(|error| error.path.as_deref() == Some(path))
+    }
+}
+
+impl<T: serde::Serialize> serde::Serialize for SemanticResult<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            SemanticResult::Value(value) => serializer.serialize_some(value),
+            SemanticResult::Error => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for SemanticResult<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value: Option<T> = serde::Deserialize::deserialize(deserializer)?;
+        Ok(value.map_or(SemanticResult::Error, SemanticResult::Value))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::collections::BTreeSet;
+    #[cfg(not(feature = "std"))]
+    use alloc::borrow::ToOwned;
+
+    #[test]
+    fn semantic_results_are_errors_when_null() {
+        let values: Vec<SemanticResult<i32>> = serde_json::from_value(json!([3, null])).unwrap();
+
+        assert_eq!(values, vec![SemanticResult::Value(3), SemanticResult::Error]);
+        assert_eq!(serde_json::to_value(&values).unwrap(), json!([3, null]));
+
+        let errors = vec![Error {
+            message: "could not fetch".to_string(),
+            locations: None,
+            path: Some(vec![PathFragment::Key("rating".to_owned())]),
+            extensions: None,
+        }];
+        let path = [PathFragment::Key("rating".to_owned())];
+        assert_eq!(values[0].error_in(&errors, &path), None);
+        assert_eq!(values[1].error_in(&errors, &path), Some(&errors[0]));
+        assert_eq!(values[1].error_in(&errors, &[]), None);
+    }
+
+    #[test]
+    fn query_bodies_display_as_json() {
+        let body = QueryBody {
+            variables: json!({ "id": 1 }),
+            query: "query User($id: ID!) { user(id: $id) { name } }",
+            operation_name: "User",
+        };
+
+        assert_eq!(
+            body.to_string(),
+            "{\n  \"variables\": {\n    \"id\": 1\n  },\n  \"query\": \"query User($id: ID!) { user(id: $id) { name } }\",\n  \"operationName\": \"User\"\n}"
+        );
+        assert_eq!(
+            body.display_compact(),
+            r#"{"variables":{"id":1},"query":"query User($id: ID!) { user(id: $id) { name } }","operationName":"User"}"#
+        );
+    }
+
+    #[test]
+    fn empty_variables_are_left_out_of_query_bodies() {
+        let body = |variables| QueryBody {
+            variables,
+            query: "{ version }",
+            operation_name: "Version",
+        };
+
+        assert_eq!(
+            body(json!({})).display_compact(),
+            r#"{"query":"{ version }","operationName":"Version"}"#
+        );
+        assert_eq!(
+            body(json!({ "id": 1 })).display_compact(),
+            r#"{"variables":{"id":1},"query":"{ version }","operationName":"Version"}"#
+        );
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[test]
+    fn query_bodies_convert_to_reqwest_bodies() {
+        let body = QueryBody {
+            variables: json!({ "id": 1 }),
+            query: "query User($id: ID!) { user(id: $id) { name } }",
+            operation_name: "User",
+        };
+
+        let reqwest_body = reqwest::Body::from(body);
+        assert!(format!("{:?}", reqwest_body).contains(r#"\"operationName\":\"User\""#));
+    }
+
+    #[test]
+    fn url_queries_are_percent_encoded() {
+        assert_eq!(
+            variables_as_url_query(&json!({ "name": "Zoë & co", "tags": ["a+b"] })).unwrap(),
+            "%7B%22name%22%3A%22Zo%C3%AB%20%26%20co%22%2C%22tags%22%3A%5B%22a%2Bb%22%5D%7D"
+        );
+        assert_eq!(
+            query_as_url_query("{ user(id: \"1\") { name_2 } }"),
+            "%7B%20user%28id%3A%20%221%22%29%20%7B%20name_2%20%7D%20%7D"
+        );
+    }
+
+    #[test]
+    fn dynamic_query_without_operation_name() {
+        let query = DynamicQuery::new("{ version }");
+
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            json!({
+                "query": "{ version }",
+                "variables": {},
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<DynamicQuery>(json!({
+                "query": "{ version }",
+                "variables": {},
+            })).unwrap(),
+            query
+        );
+    }
+
+    #[test]
+    fn responses_convert_to_results() {
+        let error = Error {
+            message: "could not fetch".to_string(),
+            locations: None,
+            path: Some(vec![PathFragment::Key("rating".to_owned())]),
+            extensions: None,
+        };
+
+        let complete = Response {
+            data: Some(3),
+            errors: Some(Vec::new()),
+        };
+        assert_eq!(Result::from(complete), Ok(Some(3)));
+
+        let partial = Response {
+            data: Some(3),
+            errors: Some(vec![error.clone()]),
+        };
+        assert_eq!(Result::from(partial), Err(vec![error.clone()]));
+
+        let failed: Response<i32> = Response {
+            data: None,
+            errors: Some(vec![error.clone()]),
+        };
+        assert_eq!(Result::from(failed), Err(vec![error]));
+
+        let empty: Response<i32> = Response {
+            data: None,
+            errors: None,
+        };
+        assert_eq!(Result::from(empty), Err(Vec::new()));
+    }
 
     #[test]
     fn graphql_error_works_with_just_message() {
@@ -308,6 +951,43 @@ mod tests {
         )
     }
 
+    #[test]
+    fn errors_with_a_path_are_partial_successes() {
+        let field_error: Error = serde_json::from_value(json!({
+            "message": "could not resolve the rating",
+            "path": ["home", "alone", 3, "rating"]
+        })).unwrap();
+        let request_error: Error = serde_json::from_value(json!({
+            "message": "syntax error"
+        })).unwrap();
+
+        assert!(field_error.is_partial_success());
+        assert!(!field_error.is_request_error());
+        assert!(!request_error.is_partial_success());
+        assert!(request_error.is_request_error());
+    }
+
+    #[test]
+    fn errors_are_sorted_by_path_then_message() {
+        let error = |message: &str, path: serde_json::Value| -> Error {
+            serde_json::from_value(json!({ "message": message, "path": path })).unwrap()
+        };
+        let mut errors = vec![
+            error("b", json!(["users", 1, "email"])),
+            error("a", json!(["users", 1, "email"])),
+            error("c", json!(["users", 0])),
+            error("d", json!(["posts"])),
+            error("syntax error", json!(null)),
+        ];
+        errors.sort();
+
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["syntax error", "d", "c", "a", "b"]);
+
+        let set: BTreeSet<Error> = errors.into_iter().collect();
+        assert_eq!(set.len(), 5);
+    }
+
     #[test]
     fn full_graphql_error_deserialization() {
         let err = json!({
@@ -0,0 +1,209 @@
+//! Runtime checks for the `@validate` directive on input object fields.
+//!
+//! When an input object in the schema has fields with `@validate(regex: "...", min: ..., max: ...)`, the generated struct
+//! has a `validate()` method checking these constraints, so invalid input can be rejected before it is sent:
+//!
+//! - `min` and `max` bound the value of `Int` and `Float` fields, and the length of `String` and `ID` fields and lists.
+//! - `regex` must match the whole value of `String` and `ID` fields. It requires the `validation` feature.
+//!
+//! Null values are always valid.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+#[cfg(feature = "validation")]
+use regex::Regex;
+#[cfg(feature = "validation")]
+use std::sync::OnceLock;
+
+/// A constraint from a `@validate` directive that a value does not satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The field with the invalid value, as `InputType.fieldName`.
+    pub field: &'static str,
+    /// A description of the constraint.
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ValidationError {}
+
+/// The quantity bounded by `min` and `max`: the value of numbers, the length of strings and lists.
+#[doc(hidden)]
+pub trait Measure {
+    fn measure(&self) -> f64;
+    fn measured(&self) -> &'static str;
+}
+
+impl Measure for i64 {
+    fn measure(&self) -> f64 {
+        *self as f64
+    }
+
+    fn measured(&self) -> &'static str {
+        "value"
+    }
+}
+
+impl Measure for f64 {
+    fn measure(&self) -> f64 {
+        *self
+    }
+
+    fn measured(&self) -> &'static str {
+        "value"
+    }
+}
+
+impl Measure for f32 {
+    fn measure(&self) -> f64 {
+        f64::from(*self)
+    }
+
+    fn measured(&self) -> &'static str {
+        "value"
+    }
+}
+
+impl Measure for String {
+    fn measure(&self) -> f64 {
+        self.chars().count() as f64
+    }
+
+    fn measured(&self) -> &'static str {
+        "length"
+    }
+}
+
+impl<T> Measure for Vec<T> {
+    fn measure(&self) -> f64 {
+        self.len() as f64
+    }
+
+    fn measured(&self) -> &'static str {
+        "length"
+    }
+}
+
+#[doc(hidden)]
+pub fn check_min<T: Measure>(
+    value: &T,
+    min: f64,
+    field: &'static str,
+) -> Result<(), ValidationError> {
+    if value.measure() < min {
+        return Err(ValidationError {
+            field,
+            message: format!("the {} must be at least {}", value.measured(), min),
+        });
+    }
+    Ok(())
+}
+
+#[doc(hidden)]
+pub fn check_max<T: Measure>(
+    value: &T,
+    max: f64,
+    field: &'static str,
+) -> Result<(), ValidationError> {
+    if value.measure() > max {
+        return Err(ValidationError {
+            field,
+            message: format!("the {} must be at most {}", value.measured(), max),
+        });
+    }
+    Ok(())
+}
+
+/// A `regex` constraint, compiled the first time it is checked. The generated code has a static `Pattern` for each of them.
+#[cfg(feature = "validation")]
+#[doc(hidden)]
+pub struct Pattern {
+    pattern: &'static str,
+    regex: OnceLock<Result<Regex, regex::Error>>,
+}
+
+#[cfg(feature = "validation")]
+impl Pattern {
+    pub const fn new(pattern: &'static str) -> Pattern {
+        Pattern {
+            pattern,
+            regex: OnceLock::new(),
+        }
+    }
+}
+
+#[cfg(feature = "validation")]
+#[doc(hidden)]
+pub fn check_regex(
+    value: &str,
+    pattern: &'static Pattern,
+    field: &'static str,
+) -> Result<(), ValidationError> {
+    let regex = pattern
+        .regex
+        .get_or_init(|| Regex::new(&format!("^(?:{})$", pattern.pattern)))
+        .as_ref()
+        .map_err(|err| ValidationError {
+            field,
+            message: format!("invalid pattern {}: {}", pattern.pattern, err),
+        })?;
+    if !regex.is_match(value) {
+        return Err(ValidationError {
+            field,
+            message: format!("the value must match {}", pattern.pattern),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn bounds_apply_to_values_and_lengths() {
+        assert!(check_min(&3i64, 3.0, "Input.count").is_ok());
+        assert_eq!(
+            check_max(&"tiramisù".to_string(), 7.0, "Input.name"),
+            Err(ValidationError {
+                field: "Input.name",
+                message: "the length must be at most 7".to_string(),
+            })
+        );
+        assert_eq!(
+            check_min(&Vec::<i64>::new(), 1.0, "Input.ids")
+                .unwrap_err()
+                .to_string(),
+            "Input.ids: the length must be at least 1"
+        );
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn regexes_match_the_whole_value() {
+        static LETTERS: Pattern = Pattern::new("[a-z]+");
+        static INVALID: Pattern = Pattern::new("(");
+
+        assert!(check_regex("abc", &LETTERS, "Input.code").is_ok());
+        assert!(check_regex("abc1", &LETTERS, "Input.code").is_err());
+        assert!(LETTERS.regex.get().is_some());
+        assert!(check_regex("abc", &INVALID, "Input.code")
+            .unwrap_err()
+            .message
+            .starts_with("invalid pattern ("));
+    }
+}
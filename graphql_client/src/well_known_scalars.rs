@@ -0,0 +1,30 @@
+//! Default Rust types for custom scalars that are common across GraphQL schemas.
+//!
+//! With the `well_known_scalars` feature enabled, the generated code uses these types for the custom scalars
+//! of the same name instead of expecting a type in the parent module:
+//!
+//! | GraphQL scalar | Rust type                 |
+//! |----------------|---------------------------|
+//! | `Long`         | `i64`                     |
+//! | `Short`        | `i16`                     |
+//! | `Byte`         | `i8`                      |
+//! | `BigDecimal`   | `bigdecimal::BigDecimal`  |
+//! | `JSON`         | `serde_json::Value`       |
+//!
+//! Individual mappings can be overridden with the `scalars` option of the `graphql` attribute, for example
+//! `scalars = "JSON = crate::MyJsonType"`.
+
+/// A 64-bit signed integer.
+pub type Long = i64;
+
+/// A 16-bit signed integer.
+pub type Short = i16;
+
+/// An 8-bit signed integer.
+pub type Byte = i8;
+
+/// An arbitrary-precision decimal number.
+pub type BigDecimal = ::bigdecimal::BigDecimal;
+
+/// Arbitrary JSON.
+pub type JSON = ::serde_json::Value;
@@ -32,3 +32,15 @@ fn alias() {
         "inner value"
     );
 }
+
+#[test]
+fn field_names_are_the_response_keys() {
+    assert_eq!(
+        alias_query::ResponseData::field_names(),
+        &["alias", "outer_alias"]
+    );
+    assert_eq!(
+        alias_query::RustAliasQueryOuterAlias::field_names(),
+        &["inner_alias"]
+    );
+}
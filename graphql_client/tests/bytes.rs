@@ -0,0 +1,31 @@
+#![cfg(feature = "bytes")]
+
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/bytes/query.graphql",
+    schema_path = "tests/bytes/schema.graphql",
+    response_derives = "Debug, PartialEq, Serialize"
+)]
+pub struct BytesQuery;
+
+#[test]
+fn bytes_are_base64_decoded() {
+    let response = json!({
+        "avatar": "AAEC/w==",
+        "thumbnail": null,
+    });
+
+    let data: bytes_query::ResponseData = serde_json::from_value(response.clone()).unwrap();
+
+    assert_eq!(&data.avatar[..], &[0, 1, 2, 255]);
+    assert_eq!(data.thumbnail, None);
+    assert_eq!(serde_json::to_value(&data).unwrap(), response);
+}
@@ -0,0 +1,29 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/cache_control/query.graphql",
+    schema_path = "tests/cache_control/schema.graphql"
+)]
+pub struct PostQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/cache_control/query.graphql",
+    schema_path = "tests/cache_control/schema.graphql"
+)]
+pub struct UncachedQuery;
+
+#[test]
+fn cache_max_age_is_the_smallest_hint_in_the_selection() {
+    assert_eq!(post_query::CACHE_MAX_AGE, Some(300));
+}
+
+#[test]
+fn cache_max_age_is_none_without_hints() {
+    assert_eq!(uncached_query::CACHE_MAX_AGE, None);
+}
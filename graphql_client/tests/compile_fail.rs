@@ -0,0 +1,21 @@
+extern crate trybuild;
+
+/// Every file in `tests/fail` is a query that the derive (or the code it
+/// generates) must reject. The expected compiler output lives next to it in a
+/// `.stderr` file; run with `TRYBUILD=overwrite` to update them.
+///
+/// trybuild compiles the cases from `target/tests/trybuild/graphql_client`,
+/// which is what the paths in the `graphql` attributes are relative to.
+///
+/// The cases in `tests/warnings` deny the warnings of the generated code, so
+/// the expected warnings show up as errors in their `.stderr` files.
+#[test]
+fn compile_fail() {
+    // Panics from the derive would otherwise include a backtrace in the output.
+    ::std::env::remove_var("RUST_BACKTRACE");
+
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/fail/*.rs");
+    t.compile_fail("tests/warnings/*.rs");
+}
+
@@ -0,0 +1,46 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/connections/query.graphql",
+    schema_path = "tests/connections/schema.graphql",
+    response_derives = "Debug"
+)]
+pub struct StargazersQuery;
+
+#[test]
+fn connections_iterate_over_their_nodes() {
+    let response = json!({
+        "repository": {
+            "stargazers": {
+                "totalCount": 3,
+                "pageInfo": { "hasNextPage": false, "endCursor": "c3" },
+                "edges": [
+                    { "cursor": "c1", "node": { "login": "ferris" } },
+                    null,
+                    { "cursor": "c3", "node": { "login": "corro" } },
+                ],
+            },
+        },
+    });
+    let data: stargazers_query::ResponseData = serde_json::from_value(response).unwrap();
+    let stargazers = data.repository.unwrap().stargazers;
+
+    let logins: Vec<&str> = stargazers
+        .into_iter()
+        .map(|user| user.login.as_str())
+        .collect();
+    assert_eq!(logins, vec!["ferris", "corro"]);
+
+    let mut count = 0;
+    for _user in &stargazers {
+        count += 1;
+    }
+    assert_eq!(count, 2);
+}
@@ -40,3 +40,73 @@ fn custom_scalars() {
         serde_json::from_value::<custom_scalars_query::ResponseData>(invalid_response).is_err()
     );
 }
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/custom_scalars/query.graphql",
+    schema_path = "tests/custom_scalars/schema.graphql",
+    scalars = "NetworkAddress = ::std::net::Ipv6Addr"
+)]
+pub struct MappedScalarsQuery;
+
+#[test]
+fn custom_scalars_can_be_mapped_in_the_attribute() {
+    let response = json!({
+        "address": "::1",
+    });
+
+    let data = serde_json::from_value::<mapped_scalars_query::ResponseData>(response).unwrap();
+
+    assert_eq!(data.address, Some(::std::net::Ipv6Addr::LOCALHOST));
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/custom_scalars/query.graphql",
+    schema_path = "tests/custom_scalars/schema.graphql",
+    newtype_scalars = "NetworkAddress = ::std::net::Ipv4Addr",
+    response_derives = "Debug, PartialEq"
+)]
+pub struct NewtypeScalarsQuery;
+
+#[test]
+fn newtype_scalars_dereference_to_the_wrapped_type() {
+    let response = json!({
+        "address": "127.0.0.1",
+    });
+
+    let data = serde_json::from_value::<newtype_scalars_query::ResponseData>(response).unwrap();
+    let mut address = data.address.unwrap();
+
+    assert!(address.is_loopback());
+    assert_eq!(
+        address,
+        newtype_scalars_query::NetworkAddress::from(Ipv4Addr::LOCALHOST)
+    );
+
+    *address = Ipv4Addr::new(10, 0, 0, 1);
+    assert!(address.is_private());
+    assert_eq!(serde_json::to_value(&address).unwrap(), json!("10.0.0.1"));
+}
+
+#[test]
+fn variables_iter_is_empty_without_variables() {
+    assert_eq!(custom_scalars_query::Variables.variables_iter().count(), 0);
+}
+
+#[test]
+fn variables_are_omitted_without_variables() {
+    assert!(custom_scalars_query::Variables.is_empty());
+
+    let body = <CustomScalarsQuery as graphql_client::GraphQLQuery>::build_query(
+        custom_scalars_query::Variables,
+    );
+
+    assert_eq!(
+        serde_json::to_value(&body).unwrap(),
+        json!({
+            "query": body.query,
+            "operationName": "CustomScalarQuery",
+        })
+    );
+}
@@ -3,6 +3,7 @@ extern crate graphql_client;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate serde_json;
 
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -28,6 +29,32 @@ pub struct DenyDeprecation;
 )]
 pub struct WarnDeprecation;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/deprecation/schema.graphql",
+    query_path = "tests/deprecation/query.graphql",
+    deprecated = "deny",
+    allow_deprecated_fields = "TestUser.deprecatedWithReason",
+)]
+pub struct AllowDeprecatedFields;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/deprecation/schema.graphql",
+    query_path = "tests/deprecation/deprecated_argument.graphql",
+    deprecated = "warn",
+)]
+pub struct DeprecatedArgument;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/deprecation/schema.graphql",
+    query_path = "tests/deprecation/deprecated_argument.graphql",
+    deprecated = "deny",
+    allow_deprecated_fields = "TestQuery.user",
+)]
+pub struct AllowDeprecatedArgument;
+
 #[test]
 fn deprecation_allow() {
     // Make any deprecations be a compile error.
@@ -71,3 +98,39 @@ fn deprecation_warn() {
         }),
     };
 }
+
+#[test]
+fn allow_deprecated_fields() {
+    // The allowed field is generated without a deprecation attribute, the other one is still denied.
+    #![deny(deprecated)]
+    let _ = allow_deprecated_fields::ResponseData {
+        current_user: Some(allow_deprecated_fields::RustTestCurrentUser {
+            id: Some("abcd".to_owned()),
+            name: Some("Angela Merkel".to_owned()),
+            deprecated_with_reason: Some("foo".to_owned()),
+        }),
+    };
+}
+
+#[test]
+fn deprecated_argument_variables_are_deprecated() {
+    #![allow(deprecated)]
+    let variables = deprecated_argument::Variables {
+        id: None,
+        login: Some("angela".to_owned()),
+    };
+    assert_eq!(
+        serde_json::to_string(&variables).unwrap(),
+        r#"{"id":null,"login":"angela"}"#
+    );
+}
+
+#[test]
+fn allowed_deprecated_argument_variables() {
+    // Under `deny`, variables passed to deprecated arguments are an error unless the field is allowed.
+    #![deny(deprecated)]
+    let _ = allow_deprecated_argument::Variables {
+        id: None,
+        login: Some("angela".to_owned()),
+    };
+}
@@ -0,0 +1,57 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+
+use serde::Deserialize;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/more_derives/schema.graphql",
+    query_path = "tests/more_derives/query.graphql",
+    response_derives = "Debug, Serialize",
+    deserializer_impl = "true"
+)]
+pub struct Test;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Profile {
+    current_user: User,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    name: String,
+}
+
+#[test]
+fn response_data_drives_deserialization() {
+    let response_data = test::ResponseData {
+        current_user: Some(test::RustTestCurrentUser {
+            id: Some("abcd".to_owned()),
+            name: Some("Angela Merkel".to_owned()),
+        }),
+    };
+
+    let profile = Profile::deserialize(response_data).unwrap();
+
+    assert_eq!(
+        profile,
+        Profile {
+            current_user: User {
+                name: "Angela Merkel".to_owned(),
+            },
+        }
+    );
+}
+
+#[test]
+fn deserialization_errors_are_reported() {
+    let response_data = test::ResponseData { current_user: None };
+
+    let err = Profile::deserialize(response_data).unwrap_err();
+
+    assert_eq!(err.to_string(), "invalid type: null, expected struct User");
+}
@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/more_derives/schema.graphql",
+    query_path = "tests/more_derives/query.graphql",
+)]
+pub struct DocumentHash;
+
+#[test]
+fn document_hash_is_the_sha256_of_the_query() {
+    let expected = "e2d4bd1cb287dcf3938588af3cf571e5836392fdbb6ee316b8fc21ee06c88f6c";
+
+    assert_eq!(document_hash::DOCUMENT_HASH, expected);
+    assert_eq!(
+        <DocumentHash as graphql_client::GraphQLQuery>::document_hash(),
+        expected
+    );
+}
+
+#[test]
+fn query_bytes_match_the_query() {
+    assert_eq!(document_hash::QUERY_BYTES, document_hash::QUERY.as_bytes());
+}
@@ -0,0 +1,35 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/unions/empty_union_query.graphql",
+    schema_path = "tests/unions/empty_union_schema.graphql",
+    response_derives = "Debug, PartialEq, Serialize, Display"
+)]
+pub struct SearchQuery;
+
+#[test]
+fn empty_unions_keep_the_typename() {
+    use search_query::RustSearchQuerySearch;
+
+    let response: search_query::ResponseData = serde_json::from_value(json!({
+        "search": [{ "__typename": "Article", "title": "ignored" }],
+    }))
+    .unwrap();
+
+    assert_eq!(
+        response.search,
+        vec![RustSearchQuerySearch::Other("Article".to_string())]
+    );
+    assert_eq!(response.search[0].to_string(), "Article");
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        json!({ "search": [{ "__typename": "Article" }] })
+    );
+}
@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/deprecation/schema.graphql",
+    query_path = "../../../../graphql_client/tests/deprecation/deprecated_argument.graphql",
+    deprecated = "deny",
+)]
+pub struct DeprecatedArgument;
+
+fn main() {}
@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/fail/schema.graphql",
+    query_path = "../../../../graphql_client/tests/fail/duplicate_operation_name.graphql",
+)]
+pub struct DuplicateOperationName;
+
+fn main() {}
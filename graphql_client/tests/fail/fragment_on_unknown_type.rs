@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/fail/schema.graphql",
+    query_path = "../../../../graphql_client/tests/fail/fragment_on_unknown_type.graphql",
+)]
+pub struct FragmentOnUnknownType;
+
+fn main() {}
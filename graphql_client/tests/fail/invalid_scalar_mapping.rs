@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/fail/schema.graphql",
+    query_path = "../../../../graphql_client/tests/fail/invalid_scalar_mapping.graphql",
+    scalars = "ID = not a path",
+)]
+pub struct InvalidScalarMapping;
+
+fn main() {}
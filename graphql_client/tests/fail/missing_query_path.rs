@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(schema_path = "../../../../graphql_client/tests/fail/schema.graphql")]
+pub struct MissingQueryPath;
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/fail/schema.graphql",
+    query_path = "../../../../graphql_client/tests/fail/missing_required_variable.graphql",
+)]
+pub struct MissingRequiredVariable;
+
+fn main() {
+    let _ = missing_required_variable::Variables {};
+}
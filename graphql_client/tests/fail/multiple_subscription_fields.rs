@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/subscription/subscription_schema.graphql",
+    query_path = "../../../../graphql_client/tests/subscription/subscription_invalid_query.graphql",
+)]
+pub struct InvalidSubscription;
+
+fn main() {}
@@ -0,0 +1,17 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+pub struct PersonId(pub u64);
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/fail/schema.graphql",
+    query_path = "../../../../graphql_client/tests/fail/unserializable_scalar.graphql",
+    scalars = "ID = crate::PersonId",
+)]
+pub struct UnserializableScalar;
+
+fn main() {}
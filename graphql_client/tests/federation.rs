@@ -0,0 +1,34 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/federation/subgraph_query.graphql",
+    schema_path = "tests/federation/schema.graphql",
+    federation_mode = "true",
+    response_derives = "Debug, PartialEq",
+)]
+pub struct ProductsQuery;
+
+#[test]
+fn external_fields_are_omitted_in_federation_mode() {
+    let response = json!({
+        "topProducts": [{ "upc": "1", "name": "Table", "price": 899 }],
+    });
+
+    let data = serde_json::from_value::<products_query::ResponseData>(response).unwrap();
+
+    // There is no `price` field: it is `@external`.
+    assert_eq!(
+        data.top_products,
+        vec![products_query::RustProductsQueryTopProducts {
+            upc: "1".to_string(),
+            name: Some("Table".to_string()),
+        }]
+    );
+}
@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/float_scalar/query.graphql",
+    schema_path = "tests/float_scalar/schema.graphql",
+    scalars = "Float = f32"
+)]
+pub struct SpritesQuery;
+
+#[test]
+fn float_can_be_mapped_to_f32_in_responses() {
+    let response = json!({
+        "spritesNear": [{ "name": "ship", "scale": 0.5, "position": [1.25, -3.0] }],
+    });
+
+    let data = serde_json::from_value::<sprites_query::ResponseData>(response).unwrap();
+    let sprite = &data.sprites_near[0];
+
+    let scale: f32 = sprite.scale;
+    assert_eq!(scale, 0.5);
+    assert_eq!(sprite.position, vec![1.25f32, -3.0]);
+}
+
+#[test]
+fn float_can_be_mapped_to_f32_in_variables() {
+    let variables = sprites_query::Variables {
+        position: sprites_query::Position { x: 1.5, y: 0.25 },
+        radius: sprites_query::Variables::default_radius(),
+    };
+
+    assert_eq!(variables.radius, Some(2.5f32));
+    assert_eq!(
+        serde_json::to_value(variables).unwrap(),
+        json!({ "position": { "x": 1.5, "y": 0.25 }, "radius": 2.5 })
+    );
+}
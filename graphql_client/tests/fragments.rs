@@ -13,6 +13,21 @@ extern crate serde_json;
 )]
 pub struct FragmentReference;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/fragments/multiple_fragments.graphql",
+    schema_path = "tests/fragments/schema.graphql"
+)]
+pub struct MultipleFragments;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/fragments/overlapping_fragments.graphql",
+    schema_path = "tests/fragments/schema.graphql",
+    response_derives = "Debug, Serialize"
+)]
+pub struct OverlappingFragments;
+
 #[test]
 fn fragment_reference() {
     let valid_response = json!({
@@ -30,3 +45,47 @@ fn fragment_reference() {
         "value"
     );
 }
+
+#[test]
+fn fragment_field_names_are_listed_by_the_fragment() {
+    let empty: &[&str] = &[];
+    assert_eq!(fragment_reference::ResponseData::field_names(), empty);
+    assert_eq!(
+        fragment_reference::FragmentReference::field_names(),
+        &["inFragment"]
+    );
+}
+
+#[test]
+fn fragments_on_the_same_type_are_flattened() {
+    let response = json!({
+        "extra": "extra value",
+        "inFragment": "value",
+    });
+
+    let data = serde_json::from_value::<multiple_fragments::ResponseData>(response).unwrap();
+
+    // Each fragment struct reads its own fields from the same JSON object.
+    assert_eq!(data.in_fragment.in_fragment.unwrap(), "value");
+    assert_eq!(data.extra_fields.extra.unwrap(), "extra value");
+}
+
+#[test]
+fn fields_selected_in_several_places_are_given_to_each_struct() {
+    // `id` is selected next to the spreads and in both fragments.
+    let response = json!({
+        "id": "1",
+        "extra": "extra value",
+        "inFragment": "value",
+    });
+
+    let data =
+        serde_json::from_value::<overlapping_fragments::ResponseData>(response.clone()).unwrap();
+
+    assert_eq!(data.id, "1");
+    assert_eq!(data.with_id.id, "1");
+    assert_eq!(data.with_id.in_fragment.as_ref().unwrap(), "value");
+    assert_eq!(data.also_with_id.id, "1");
+    assert_eq!(data.also_with_id.extra.as_ref().unwrap(), "extra value");
+    assert_eq!(serde_json::to_value(&data).unwrap(), response);
+}
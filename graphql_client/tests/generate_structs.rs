@@ -0,0 +1,40 @@
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+use graphql_client::GraphQLQuery;
+
+type Email = String;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/input_object_variables/input_object_variables_query.graphql",
+    schema_path = "tests/input_object_variables/input_object_variables_schema.graphql",
+    generate_structs = "false"
+)]
+pub struct InputObjectVariablesQuery;
+
+#[test]
+fn only_the_query_and_variables_are_generated() {
+    let variables = input_object_variables_query::Variables {
+        msg: Some(input_object_variables_query::Message {
+            content: Some("hello".to_string()),
+            to: None,
+        }),
+    };
+    let body = InputObjectVariablesQuery::build_query(variables);
+
+    assert_eq!(body.query, input_object_variables_query::QUERY);
+    assert_eq!(
+        serde_json::to_value(&body.variables).unwrap(),
+        json!({ "msg": { "content": "hello", "to": null } })
+    );
+
+    let response: graphql_client::Response<
+        <InputObjectVariablesQuery as GraphQLQuery>::ResponseData,
+    > = serde_json::from_value(json!({ "data": { "echo": { "result": "hello" } } })).unwrap();
+    assert_eq!(response.data.unwrap()["echo"]["result"], "hello");
+}
@@ -0,0 +1,48 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct UserId(u64);
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/id_scalar/query.graphql",
+    schema_path = "tests/id_scalar/schema.graphql",
+    scalars = "ID = super::UserId",
+)]
+pub struct UserQuery;
+
+#[test]
+fn id_can_be_mapped_in_responses() {
+    let response = json!({
+        "user": { "id": 1, "friendIds": [2, 3] },
+        "users": [{ "id": 4 }],
+    });
+
+    let data = serde_json::from_value::<user_query::ResponseData>(response).unwrap();
+    let user = data.user.unwrap();
+
+    assert_eq!(user.id, UserId(1));
+    assert_eq!(user.friend_ids, vec![UserId(2), UserId(3)]);
+    assert_eq!(data.users[0].id, UserId(4));
+}
+
+#[test]
+fn id_can_be_mapped_in_variables() {
+    let variables = user_query::Variables {
+        id: UserId(1),
+        filter: user_query::UserFilter {
+            ids: Some(vec![UserId(2)]),
+        },
+    };
+
+    assert_eq!(
+        serde_json::to_value(variables).unwrap(),
+        json!({ "id": 1, "filter": { "ids": [2] } })
+    );
+}
@@ -0,0 +1,52 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/input_inheritance/mutation.graphql",
+    schema_path = "tests/input_inheritance/schema.graphql",
+    input_inheritance = "true"
+)]
+pub struct CreateFeaturedPost;
+
+#[test]
+fn inherited_fields_are_flattened() {
+    use create_featured_post::*;
+
+    let variables = Variables {
+        post: NewFeaturedPost {
+            new_post: NewPost {
+                title: "Hello".to_string(),
+                body: None,
+            },
+            timestamped: Timestamped {
+                created_at: Some("today".to_string()),
+            },
+            featured_until: "next week".to_string(),
+        },
+        draft: Variables::default_draft(),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&variables).unwrap(),
+        json!({
+            "post": {
+                "title": "Hello",
+                "body": null,
+                "createdAt": "today",
+                "featuredUntil": "next week",
+            },
+            "draft": {
+                "title": "Draft",
+                "body": null,
+                "createdAt": null,
+                "featuredUntil": "tomorrow",
+            },
+        })
+    );
+}
@@ -3,6 +3,7 @@ extern crate graphql_client;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde;
+#[macro_use]
 extern crate serde_json;
 
 #[derive(GraphQLQuery)]
@@ -48,3 +49,101 @@ fn input_object_variables_default() {
 
     assert_eq!(out, r#"{"msg":{"content":null,"to":{"category":null,"email":"rosa.luxemburg@example.com","name":null}}}"#);
 }
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/input_object_variables/input_object_variables_query_enum_defaults.graphql",
+    schema_path = "tests/input_object_variables/input_object_variables_schema.graphql",
+    response_derives = "Debug",
+)]
+pub struct EnumDefaultsQuery;
+
+#[test]
+fn enum_default_values() {
+    let variables = enum_defaults_query::Variables::default();
+
+    assert_eq!(
+        serde_json::to_value(&variables).unwrap(),
+        json!({
+            "category": "PERSONAL",
+            "msg": {
+                "content": null,
+                "to": {
+                    "category": "PROFESSIONAL",
+                    "email": "rosa.luxemburg@example.com",
+                    "name": null,
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn input_object_variables_schema() {
+    assert_eq!(
+        input_object_variables_query::variables_schema(),
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "msg": { "anyOf": [{ "$ref": "#/definitions/Message" }, { "type": "null" }] },
+            },
+            "required": [],
+            "definitions": {
+                "Message": {
+                    "type": "object",
+                    "properties": {
+                        "content": { "type": ["string", "null"] },
+                        "to": { "anyOf": [{ "$ref": "#/definitions/Recipient" }, { "type": "null" }] },
+                    },
+                    "required": [],
+                },
+                "Recipient": {
+                    "type": "object",
+                    "properties": {
+                        "category": {
+                            "type": ["string", "null"],
+                            "enum": ["PROFESSIONAL", "PERSONAL", null],
+                        },
+                        "email": {},
+                        "name": { "type": ["string", "null"] },
+                    },
+                    "required": ["email"],
+                },
+            },
+        })
+    );
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/input_object_variables/input_object_variables_query_camel_case_defaults.graphql",
+    schema_path = "tests/input_object_variables/input_object_variables_schema.graphql",
+)]
+pub struct CamelCaseDefaultsQuery;
+
+#[test]
+fn camel_case_fields_in_default_values() {
+    assert_eq!(
+        serde_json::to_value(camel_case_defaults_query::Variables::default_options()).unwrap(),
+        json!({ "pgpSignature": false })
+    );
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/input_object_variables/many_variables_query.graphql",
+    schema_path = "tests/input_object_variables/input_object_variables_schema.graphql",
+)]
+pub struct ManyVariablesQuery;
+
+#[test]
+fn variables_schema_with_many_variables() {
+    let schema = many_variables_query::variables_schema();
+
+    assert_eq!(schema["properties"].as_object().unwrap().len(), 40);
+    assert_eq!(
+        schema["properties"]["v40"],
+        json!({ "type": ["string", "null"] })
+    );
+}
@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_json;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Date(String);
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/mock/query.graphql",
+    schema_path = "tests/mock/schema.graphql",
+    response_derives = "Debug, PartialEq",
+    generate_mock = "true"
+)]
+pub struct MockQuery;
+
+#[test]
+fn mock_response_has_placeholder_values() {
+    let response = mock_query::mock_response();
+    let me = response.me;
+
+    assert_eq!(me.id, "");
+    assert_eq!(me.full_name, "");
+    assert_eq!(me.age, 0);
+    assert_eq!(me.score, 0.0);
+    assert!(!me.active);
+    assert_eq!(me.role, mock_query::Role::ADMIN);
+    assert_eq!(me.joined, Date::default());
+    assert_eq!(me.friends, vec![]);
+    assert_eq!(me.nickname, None);
+    assert_eq!(me.user_address.address.city, "");
+    assert_eq!(me.user_address.address.zip, None);
+}
+
+#[test]
+fn mock_unions_prefer_variants_without_fields() {
+    let response = mock_query::mock_response();
+
+    assert_eq!(response.account, mock_query::RustMockQueryAccount::Bot);
+}
@@ -0,0 +1,61 @@
+//! The generated code must not depend on the `std` prelude. The test harness still needs `std`, but it is not in scope.
+#![no_std]
+
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate std;
+
+use graphql_client::alloc::string::ToString;
+
+type Email = graphql_client::alloc::string::String;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/input_object_variables/input_object_variables_query_defaults.graphql",
+    schema_path = "tests/input_object_variables/input_object_variables_schema.graphql",
+    response_derives = "Debug",
+    no_std = "true"
+)]
+pub struct DefaultInputObjectVariablesQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/unions/union_query.graphql",
+    schema_path = "tests/unions/union_schema.graphql",
+    response_derives = "Debug, PartialEq",
+    generate_mock = "true",
+    no_std = "true"
+)]
+pub struct UnionQuery;
+
+#[test]
+fn no_std_variables() {
+    let variables = default_input_object_variables_query::Variables {
+        msg: default_input_object_variables_query::Variables::default_msg(),
+    };
+
+    assert_eq!(
+        serde_json::to_string(&variables).unwrap(),
+        r#"{"msg":{"content":null,"to":{"category":null,"email":"rosa.luxemburg@example.com","name":null}}}"#
+    );
+}
+
+#[test]
+fn no_std_responses() {
+    let response: union_query::ResponseData =
+        serde_json::from_str(r#"{"names": [{"__typename": "Dog", "name": "Laika"}]}"#).unwrap();
+
+    assert_eq!(
+        response.names.unwrap(),
+        [union_query::RustMyQueryNames::Dog(
+            union_query::RustMyQueryNamesOnDog {
+                name: "Laika".to_string(),
+            }
+        )]
+    );
+    assert_eq!(union_query::mock_response().names, None);
+}
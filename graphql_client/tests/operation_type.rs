@@ -0,0 +1,67 @@
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+use graphql_client::{GraphQLQuery, OperationType};
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/more_derives/schema.graphql",
+    query_path = "tests/more_derives/query.graphql"
+)]
+pub struct TestQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/input_inheritance/mutation.graphql",
+    schema_path = "tests/input_inheritance/schema.graphql",
+    input_inheritance = "true"
+)]
+pub struct CreateFeaturedPost;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/subscription/subscription_schema.graphql",
+    query_path = "tests/subscription/subscription_query.graphql"
+)]
+pub struct SubscriptionQuery;
+
+/// A hand-written implementation, relying on the default `operation_type`.
+pub struct ManualQuery;
+
+impl GraphQLQuery for ManualQuery {
+    type Variables = ();
+    type ResponseData = ();
+
+    fn build_query(variables: ()) -> graphql_client::QueryBody<()> {
+        graphql_client::QueryBody {
+            variables,
+            query: "{ version }",
+            operation_name: "",
+        }
+    }
+
+    fn document_hash() -> &'static str {
+        // sha256sum of "{ version }"
+        "1dee97279832c351624025387be36873845c282288b1f0a51ccf63e6b5f7549f"
+    }
+}
+
+#[test]
+fn operation_types_match_the_selected_operations() {
+    assert_eq!(TestQuery::operation_type(), OperationType::Query);
+    assert_eq!(
+        CreateFeaturedPost::operation_type(),
+        OperationType::Mutation
+    );
+    assert_eq!(
+        SubscriptionQuery::operation_type(),
+        OperationType::Subscription
+    );
+}
+
+#[test]
+fn operation_type_defaults_to_query() {
+    assert_eq!(ManualQuery::operation_type(), OperationType::Query);
+}
@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/more_derives/query.graphql",
+    schema_path = "tests/more_derives/schema.graphql",
+    response_derives = "Debug",
+    output_directory = "tests/output_directory/generated"
+)]
+pub struct GeneratedQuery;
+
+#[test]
+fn generated_modules_are_written_to_the_output_directory() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/output_directory/generated/generated_query.rs"
+    );
+    let contents = std::fs::read_to_string(path).unwrap();
+
+    assert!(contents.starts_with("// Generated by graphql_client from the GeneratedQuery query."));
+    assert!(contents.contains("pub mod generated_query"));
+    assert_eq!(generated_query::OPERATION_NAME, "Test");
+}
@@ -0,0 +1,34 @@
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+// The prelude brings the derive macro too.
+use graphql_client::prelude::*;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/more_derives/query.graphql",
+    schema_path = "tests/more_derives/schema.graphql"
+)]
+pub struct PreludeQuery;
+
+#[test]
+fn prelude_types_are_nameable() {
+    let body: QueryBody<prelude_query::Variables> =
+        PreludeQuery::build_query(prelude_query::Variables);
+    let response: Response<prelude_query::ResponseData> = serde_json::from_value(json!({
+        "data": null,
+        "errors": [{ "message": "not found", "path": ["currentUser"] }],
+    }))
+    .unwrap();
+    let errors: Vec<Error> = response.errors.unwrap();
+
+    assert_eq!(body.operation_name, "Test");
+    assert_eq!(
+        errors[0].path,
+        Some(vec![PathFragment::Key("currentUser".to_string())])
+    );
+}
@@ -0,0 +1,43 @@
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+use graphql_client::GraphQLQuery;
+use serde::{Deserialize, Deserializer};
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/more_derives/schema.graphql",
+    query_path = "tests/more_derives/query.graphql",
+    response_derives_exclusive = "Serialize, Debug, PartialEq"
+)]
+pub struct ExclusiveQuery;
+
+// Without the default derive, the response can be read from another representation.
+impl<'de> Deserialize<'de> for exclusive_query::ResponseData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = Option::<String>::deserialize(deserializer)?;
+        Ok(exclusive_query::ResponseData {
+            current_user: Some(exclusive_query::RustTestCurrentUser { name, id: None }),
+        })
+    }
+}
+
+#[test]
+fn exclusive_response_derives_replace_deserialize() {
+    let data: <ExclusiveQuery as GraphQLQuery>::ResponseData =
+        serde_json::from_value(json!("Ada")).unwrap();
+
+    assert_eq!(
+        data,
+        exclusive_query::ResponseData {
+            current_user: Some(exclusive_query::RustTestCurrentUser {
+                name: Some("Ada".to_string()),
+                id: None,
+            }),
+        }
+    );
+}
@@ -0,0 +1,51 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/fragments/query.graphql",
+    schema_path = "tests/fragments/schema.graphql",
+    response_type_prefix = "Prefixed",
+)]
+pub struct FragmentReference;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/unions/union_query.graphql",
+    schema_path = "tests/unions/union_schema.graphql",
+    response_type_prefix = "Prefixed",
+)]
+pub struct UnionQuery;
+
+#[test]
+fn fragments_are_prefixed() {
+    let response = json!({
+        "inFragment": "value",
+    });
+
+    let data = serde_json::from_value::<fragment_reference::ResponseData>(response).unwrap();
+    let fragment: fragment_reference::PrefixedFragmentReference = data.fragment_reference;
+
+    assert_eq!(fragment.in_fragment, Some("value".to_string()));
+}
+
+#[test]
+fn nested_types_are_prefixed() {
+    let response = json!({
+        "names": [{ "__typename": "Dog", "name": "Laïka" }],
+    });
+
+    let data = serde_json::from_value::<union_query::ResponseData>(response).unwrap();
+
+    match &data.names.unwrap()[0] {
+        union_query::PrefixedRustMyQueryNames::Dog(union_query::PrefixedRustMyQueryNamesOnDog {
+            name,
+        }) => assert_eq!(name, "Laïka"),
+        _ => panic!("expected a dog"),
+    }
+}
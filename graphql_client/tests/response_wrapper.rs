@@ -0,0 +1,49 @@
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+use graphql_client::{GraphQLQuery, Response};
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/more_derives/schema.graphql",
+    query_path = "tests/more_derives/query.graphql",
+    response_derives = "Debug, PartialEq",
+    response_wrapper = "Arc"
+)]
+pub struct SharedQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/more_derives/schema.graphql",
+    query_path = "tests/more_derives/query.graphql",
+    response_wrapper = "Rc"
+)]
+pub struct LocalQuery;
+
+#[test]
+fn response_data_is_wrapped() {
+    let json = json!({ "data": { "currentUser": { "name": "Ada", "id": "7" } } });
+
+    let shared: Response<<SharedQuery as GraphQLQuery>::ResponseData> =
+        serde_json::from_value(json.clone()).unwrap();
+    let data: Arc<shared_query::ResponseData> = shared.data.unwrap();
+    let other_consumer = Arc::clone(&data);
+    assert_eq!(
+        other_consumer.current_user.as_ref().unwrap().name,
+        Some("Ada".to_string())
+    );
+
+    let local: Response<<LocalQuery as GraphQLQuery>::ResponseData> =
+        serde_json::from_value(json).unwrap();
+    let data: Rc<local_query::ResponseData> = local.data.unwrap();
+    assert_eq!(
+        data.current_user.as_ref().unwrap().id,
+        Some("7".to_string())
+    );
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "scaffolding")]
+
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_json;
+
+mod domain {
+    #[derive(Debug)]
+    pub struct Heights;
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/operation_selection/queries.graphql",
+    schema_path = "tests/operation_selection/schema.graphql",
+    into = "domain::Heights",
+)]
+pub struct Heights;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/federation/query.graphql",
+    schema_path = "tests/federation/schema.graphql",
+)]
+pub struct MeQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/federation/subgraph_query.graphql",
+    schema_path = "tests/federation/schema.graphql",
+    response_derives = "Debug",
+)]
+pub struct ProductsQuery;
+
+const HEIGHTS_RESPONSE: &'static str = r##"{"mountainHeight": 224, "buildingHeight": 12}"##;
+
+#[test]
+#[should_panic(expected = "not yet implemented: convert heights::ResponseData into domain::Heights")]
+fn into_generates_a_from_impl_skeleton() {
+    let response_data: heights::ResponseData = serde_json::from_str(HEIGHTS_RESPONSE).unwrap();
+    let _: domain::Heights = response_data.into();
+}
+
+impl me_query::RustMeQueryMeResolveReference for me_query::RustMeQueryMe {
+    fn __resolve_reference(
+        key: me_query::RustMeQueryMeEntityKey,
+    ) -> Result<Self, graphql_client::Error> {
+        Ok(me_query::RustMeQueryMe {
+            username: Some(format!("user {}", key.id)),
+        })
+    }
+}
+
+impl products_query::RustProductsQueryTopProductsResolveReference
+    for products_query::RustProductsQueryTopProducts
+{
+    fn __resolve_reference(
+        key: products_query::RustProductsQueryTopProductsEntityKey,
+    ) -> Result<Self, graphql_client::Error> {
+        Err(graphql_client::Error {
+            message: format!("unknown product {}/{}", key.upc, key.sku),
+            locations: None,
+            path: None,
+            extensions: None,
+        })
+    }
+}
+
+#[test]
+fn key_generates_a_resolve_reference_trait() {
+    use me_query::RustMeQueryMeResolveReference;
+
+    let key: me_query::RustMeQueryMeEntityKey =
+        serde_json::from_str(r#"{"id": "1234"}"#).unwrap();
+    assert_eq!(key.id, "1234");
+    let me = me_query::RustMeQueryMe::__resolve_reference(key).unwrap();
+    assert_eq!(me.username.unwrap(), "user 1234");
+}
+
+#[test]
+fn composite_keys_have_all_their_fields() {
+    use products_query::RustProductsQueryTopProductsResolveReference;
+
+    let key: products_query::RustProductsQueryTopProductsEntityKey =
+        serde_json::from_str(r#"{"upc": "1", "sku": "TBL-899"}"#).unwrap();
+    assert_eq!(key.upc, "1");
+    assert_eq!(key.sku, "TBL-899");
+    let err = products_query::RustProductsQueryTopProducts::__resolve_reference(key).unwrap_err();
+    assert_eq!(err.message, "unknown product 1/TBL-899");
+}
@@ -38,3 +38,110 @@ fn scalar_variables_default() {
 
     assert_eq!(out, r#"{"msg":"o, hai","reps":3}"#);
 }
+
+#[test]
+fn scalar_variables_iter() {
+    let variables = scalar_variables_query::Variables {
+        msg: "hello".to_string(),
+        reps: Some(32),
+    };
+
+    let pairs: Vec<(&'static str, serde_json::Value)> = variables.variables_iter().collect();
+
+    assert_eq!(
+        pairs,
+        vec![
+            ("msg", serde_json::Value::from("hello")),
+            ("reps", serde_json::Value::from(32)),
+        ]
+    );
+}
+
+#[test]
+fn scalar_variables_iter_skips_unset_optional_variables() {
+    let variables = scalar_variables_query::Variables {
+        msg: "hello".to_string(),
+        reps: None,
+    };
+
+    let names: Vec<&'static str> = variables.variables_iter().map(|(name, _)| name).collect();
+
+    assert_eq!(names, vec!["msg"]);
+}
+
+#[test]
+fn variables_as_json() {
+    use graphql_client::GraphQLQuery;
+
+    let variables = scalar_variables_query::Variables {
+        msg: "hello".to_string(),
+        reps: None,
+    };
+
+    assert_eq!(
+        ScalarVariablesQuery::variables_as_json(&variables),
+        serde_json::json!({ "msg": "hello", "reps": null })
+    );
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/scalar_variables/list_variables_query.graphql",
+    schema_path = "tests/scalar_variables/scalar_variables_schema.graphql"
+)]
+pub struct ListVariablesQuery;
+
+#[test]
+fn nullable_list_variables_can_be_single_items() {
+    use graphql_client::CoercedList;
+
+    let single = list_variables_query::Variables {
+        messages: list_variables_query::Variables::default_messages(),
+        repetitions: vec![1],
+    };
+    let list = list_variables_query::Variables {
+        messages: Some(vec![Some("hello".to_string()), None].into()),
+        repetitions: vec![1, 2],
+    };
+
+    assert_eq!(
+        single.messages,
+        Some(CoercedList::Single(Some("o, hai".to_string())))
+    );
+    assert_eq!(
+        serde_json::to_string(&single).unwrap(),
+        r#"{"messages":"o, hai","repetitions":[1]}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&list).unwrap(),
+        r#"{"messages":["hello",null],"repetitions":[1,2]}"#
+    );
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/scalar_variables/scalar_variables_query.graphql",
+    schema_path = "tests/scalar_variables/scalar_variables_schema.graphql",
+    rename_variables = "SCREAMING_SNAKE_CASE"
+)]
+pub struct RenamedVariablesQuery;
+
+#[test]
+fn variables_can_be_renamed() {
+    let variables = renamed_variables_query::Variables {
+        msg: "hello".to_string(),
+        reps: Some(32),
+    };
+
+    assert_eq!(
+        serde_json::to_string(&variables).unwrap(),
+        r#"{"MSG":"hello","REPS":32}"#
+    );
+    assert_eq!(
+        variables
+            .variables_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        vec!["MSG", "REPS"]
+    );
+}
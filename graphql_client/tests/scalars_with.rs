@@ -0,0 +1,69 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+extern crate serde_with;
+
+// The server sends these as strings, so they are deserialized with `DisplayFromStr`.
+type BigInt = u64;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/scalars_with/query.graphql",
+    schema_path = "tests/scalars_with/schema.graphql",
+    scalars_with = "BigInt = ::serde_with::DisplayFromStr",
+    response_derives = "Debug, PartialEq",
+)]
+pub struct TotalsQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/scalars_with/wrapped_query.graphql",
+    schema_path = "tests/scalars_with/schema.graphql",
+    scalars_with = "BigInt = ::serde_with::DisplayFromStr",
+    response_derives = "Debug",
+)]
+pub struct WrappedTotalsQuery;
+
+#[test]
+fn scalars_with_uses_serde_as_on_fields() {
+    let response = json!({
+        "total": "18446744073709551615",
+        "previousTotal": null,
+        "history": ["1", "2"],
+    });
+
+    let data = serde_json::from_value::<totals_query::ResponseData>(response).unwrap();
+
+    assert_eq!(
+        data,
+        totals_query::ResponseData {
+            total: 18446744073709551615,
+            previous_total: None,
+            history: Some(vec![1, 2]),
+        }
+    );
+}
+
+// serde_with has no adapters for `SemanticResult` and `StreamedField`, so these fields use the
+// `Deserialize` impl of the scalar type.
+#[test]
+fn scalars_with_skips_semantic_non_null_fields() {
+    let response = json!({ "semanticTotal": 3, "streamedHistory": [] });
+
+    let data = serde_json::from_value::<wrapped_totals_query::ResponseData>(response).unwrap();
+
+    assert_eq!(data.semantic_total.ok(), Some(3));
+}
+
+#[test]
+fn scalars_with_skips_streamed_fields() {
+    let response = json!({ "semanticTotal": null, "streamedHistory": [1, 2] });
+
+    let data = serde_json::from_value::<wrapped_totals_query::ResponseData>(response).unwrap();
+
+    assert_eq!(data.streamed_history.into_vec(), vec![1, 2]);
+}
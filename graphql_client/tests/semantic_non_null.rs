@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+use graphql_client::{PathFragment, Response, SemanticResult};
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/semantic_non_null/query.graphql",
+    schema_path = "tests/semantic_non_null/schema.graphql",
+    response_derives = "Debug, PartialEq"
+)]
+pub struct SemanticNonNullQuery;
+
+#[test]
+fn semantic_non_null_fields_distinguish_errors_from_nulls() {
+    let response: Response<semantic_non_null_query::ResponseData> = serde_json::from_value(json!({
+        "data": {
+            "me": {
+                "name": null,
+                "nickname": null,
+                "friends": [{ "name": "Ada" }, null],
+            },
+        },
+        "errors": [{ "message": "name is unavailable", "path": ["me", "name"] }],
+    }))
+    .unwrap();
+
+    let me = response.data.unwrap().me.unwrap();
+    let errors = response.errors.unwrap();
+
+    assert_eq!(me.nickname, None);
+    assert_eq!(me.name, SemanticResult::Error);
+    assert_eq!(
+        me.name
+            .error_in(
+                &errors,
+                &[
+                    PathFragment::Key("me".to_owned()),
+                    PathFragment::Key("name".to_owned())
+                ]
+            )
+            .map(|error| error.message.as_str()),
+        Some("name is unavailable")
+    );
+
+    let friends = me.friends.ok().unwrap();
+    assert_eq!(
+        friends[0],
+        SemanticResult::Value(semantic_non_null_query::RustSemanticNonNullQueryMeFriends {
+            name: SemanticResult::Value("Ada".to_string()),
+        })
+    );
+    assert!(friends[1].is_error());
+}
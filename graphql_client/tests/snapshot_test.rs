@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate insta;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "tests/more_derives/schema.graphql",
+    query_path = "tests/more_derives/query.graphql",
+    snapshot_test = "true",
+)]
+pub struct SnapshotTest;
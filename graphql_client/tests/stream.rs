@@ -0,0 +1,57 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+use graphql_client::StreamedField;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/stream/query.graphql",
+    schema_path = "tests/stream/schema.graphql",
+    response_derives = "Debug, PartialEq"
+)]
+pub struct StreamQuery;
+
+#[test]
+fn streamed_lists_are_extended_by_incremental_payloads() {
+    let initial = json!({
+        "user": {
+            "name": "ada",
+            "friends": [{ "name": "grace" }],
+            "posts": null,
+        }
+    });
+    let data: stream_query::ResponseData = serde_json::from_value(initial).unwrap();
+    let mut user = data.user.unwrap();
+    assert_eq!(user.friends.len(), 1);
+    assert_eq!(user.posts, None);
+
+    let patch: Vec<stream_query::RustStreamQueryUserFriends> =
+        serde_json::from_value(json!([{ "name": "edsger" }])).unwrap();
+    user.friends.extend(patch);
+
+    let names: Vec<&str> = user
+        .friends
+        .iter()
+        .map(|friend| friend.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["grace", "edsger"]);
+}
+
+#[test]
+fn nullable_streamed_lists_are_optional() {
+    let data: stream_query::ResponseData = serde_json::from_value(json!({
+        "user": { "name": "ada", "friends": [], "posts": ["hello", null] }
+    }))
+    .unwrap();
+
+    let posts: Option<StreamedField<Option<String>>> = data.user.unwrap().posts;
+    assert_eq!(
+        posts.unwrap().into_vec(),
+        vec![Some("hello".to_string()), None]
+    );
+}
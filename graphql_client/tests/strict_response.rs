@@ -0,0 +1,78 @@
+#[macro_use]
+extern crate graphql_client;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/more_derives/query.graphql",
+    schema_path = "tests/more_derives/schema.graphql",
+    strict_response = "true"
+)]
+pub struct StrictQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/more_derives/query.graphql",
+    schema_path = "tests/more_derives/schema.graphql"
+)]
+pub struct LenientQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/deprecation/query.graphql",
+    schema_path = "tests/deprecation/schema.graphql",
+    deprecated = "deny",
+    strict_response = "true"
+)]
+pub struct StrictDenyDeprecation;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/federation/subgraph_query.graphql",
+    schema_path = "tests/federation/schema.graphql",
+    federation_mode = "true",
+    strict_response = "true"
+)]
+pub struct StrictProductsQuery;
+
+#[test]
+fn strict_responses_reject_unknown_fields() {
+    let expected = json!({ "currentUser": { "name": "Ada", "id": "1" } });
+    let drifted =
+        json!({ "currentUser": { "name": "Ada", "id": "1", "email": "ada@example.com" } });
+
+    assert!(serde_json::from_value::<strict_query::ResponseData>(expected).is_ok());
+    assert!(serde_json::from_value::<strict_query::ResponseData>(drifted.clone()).is_err());
+    assert!(serde_json::from_value::<lenient_query::ResponseData>(drifted).is_ok());
+}
+
+#[test]
+fn strict_responses_accept_the_fields_left_out_of_the_structs() {
+    // The denied deprecated fields are still selected, so the server sends them.
+    let response = json!({
+        "currentUser": {
+            "name": "Ada",
+            "id": "1",
+            "deprecatedWithReason": "old",
+            "deprecatedNoReason": null,
+        },
+    });
+    assert!(serde_json::from_value::<strict_deny_deprecation::ResponseData>(response).is_ok());
+
+    // So are the `@external` fields in federation mode.
+    let response = json!({
+        "topProducts": [{ "upc": "1", "name": "Table", "price": 899 }],
+    });
+    assert!(serde_json::from_value::<strict_products_query::ResponseData>(response).is_ok());
+
+    // The structs without such fields stay strict.
+    let drifted = json!({
+        "currentUser": null,
+        "email": "ada@example.com",
+    });
+    assert!(serde_json::from_value::<strict_deny_deprecation::ResponseData>(drifted).is_err());
+}
@@ -7,14 +7,7 @@ extern crate serde_json;
 
 const RESPONSE: &str = include_str!("subscription/subscription_query_response.json");
 
-// If you uncomment this, it will not compile because the query is not valid. We need to investigate how we can make this a real test.
-//
-// #[derive(GraphQLQuery)]
-// #[graphql(
-//     schema_path = "tests/subscription/subscription_schema.graphql",
-//     query_path = "tests/subscription/subscription_invalid_query.graphql"
-// )]
-// struct SubscriptionInvalidQuery;
+// Subscriptions selecting more than one root field are rejected, see tests/fail/multiple_subscription_fields.rs.
 
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -52,3 +45,13 @@ fn subscriptions_work() {
         Some(4)
     );
 }
+
+#[test]
+fn subscription_request_bodies_carry_the_subscription_document() {
+    use graphql_client::GraphQLQuery;
+
+    let body = SubscriptionQuery::build_query(subscription_query::Variables { filter: None });
+
+    assert_eq!(body.operation_name, "Birthdays");
+    assert!(body.query.trim_start().starts_with("subscription Birthdays"));
+}
@@ -43,3 +43,84 @@ fn union_query_deserialization() {
 
     assert_eq!(response_data.names.map(|names| names.len()), Some(4));
 }
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/unions/union_query.graphql",
+    schema_path = "tests/unions/union_schema.graphql",
+    response_derives = "Display",
+)]
+pub struct DisplayUnionQuery;
+
+impl ::std::fmt::Display for display_union_query::RustMyQueryNamesOnPerson {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.first_name)
+    }
+}
+
+impl ::std::fmt::Display for display_union_query::RustMyQueryNamesOnDog {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{} (dog)", self.name)
+    }
+}
+
+impl ::std::fmt::Display for display_union_query::RustMyQueryNamesOnOrganization {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.title)
+    }
+}
+
+#[test]
+fn union_display_delegates_to_the_variants() {
+    let response_data: display_union_query::ResponseData = serde_json::from_str(RESPONSE).unwrap();
+
+    let names: Vec<String> = response_data
+        .names
+        .unwrap()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+
+    assert_eq!(names, vec!["Audrey", "Laïka (dog)", "Mozilla", "Norbert (dog)"]);
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/unions/union_query.graphql",
+    schema_path = "tests/unions/union_schema.graphql",
+    response_derives = "Debug, PartialEq, Eq",
+    ord_derives = "true",
+)]
+pub struct OrdUnionQuery;
+
+#[test]
+fn union_can_derive_ord() {
+    let response_data: ord_union_query::ResponseData = serde_json::from_str(RESPONSE).unwrap();
+    let mut names = response_data.names.unwrap();
+    names.sort();
+
+    let dog = |name: &str| {
+        ord_union_query::RustMyQueryNames::Dog(ord_union_query::RustMyQueryNamesOnDog {
+            name: name.to_string(),
+        })
+    };
+    let laika = names.iter().position(|name| *name == dog("Laïka")).unwrap();
+    let norbert = names.iter().position(|name| *name == dog("Norbert")).unwrap();
+
+    assert_eq!(laika + 1, norbert);
+}
+
+#[test]
+fn union_variants_convert_into_the_union() {
+    let dog = union_query::RustMyQueryNamesOnDog {
+        name: "Laïka".to_string(),
+    };
+    let name: union_query::RustMyQueryNames = dog.into();
+
+    assert_eq!(
+        name,
+        union_query::RustMyQueryNames::Dog(union_query::RustMyQueryNamesOnDog {
+            name: "Laïka".to_string(),
+        })
+    );
+}
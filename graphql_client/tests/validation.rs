@@ -0,0 +1,76 @@
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/validation/mutation.graphql",
+    schema_path = "tests/validation/schema.graphql"
+)]
+pub struct CreateUser;
+
+fn user(name: &str, age: Option<i64>, nicknames: &[&str]) -> create_user::NewUser {
+    create_user::NewUser {
+        name: name.to_string(),
+        age,
+        nicknames: nicknames.iter().map(|n| n.to_string()).collect(),
+        bio: None,
+    }
+}
+
+#[test]
+fn valid_input_objects_pass() {
+    assert_eq!(user("tiramisù", Some(30), &["tira"]).validate(), Ok(()));
+    assert_eq!(user("tiramisù", None, &[]).validate(), Ok(()));
+}
+
+#[test]
+fn constraints_are_checked() {
+    let err = user("", Some(30), &[]).validate().unwrap_err();
+    assert_eq!(err.field, "NewUser.name");
+    assert_eq!(err.to_string(), "NewUser.name: the length must be at least 1");
+
+    assert_eq!(
+        user("tiramisù", Some(-1), &[])
+            .validate()
+            .unwrap_err()
+            .to_string(),
+        "NewUser.age: the value must be at least 0"
+    );
+    assert_eq!(
+        user("tiramisù", None, &["a", "b", "c", "d"])
+            .validate()
+            .unwrap_err()
+            .to_string(),
+        "NewUser.nicknames: the length must be at most 3"
+    );
+}
+
+#[cfg(feature = "validation")]
+pub mod regex {
+    #[derive(GraphQLQuery)]
+    #[graphql(
+        query_path = "tests/validation/mutation.graphql",
+        schema_path = "tests/validation/regex_schema.graphql"
+    )]
+    pub struct CreateUser;
+
+    #[test]
+    fn regexes_are_checked() {
+        let mut user = create_user::NewUser {
+            name: "tiramisu".to_string(),
+            age: None,
+            nicknames: Vec::new(),
+            bio: None,
+        };
+        assert_eq!(user.validate(), Ok(()));
+
+        user.name = "Tiramisu".to_string();
+        assert_eq!(
+            user.validate().unwrap_err().to_string(),
+            "NewUser.name: the value must match [a-z]+"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+#![deny(deprecated)]
+
+#[macro_use]
+extern crate graphql_client;
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/fail/schema.graphql",
+    query_path = "../../../../graphql_client/tests/warnings/max_query_depth.graphql",
+    max_query_depth = "1"
+)]
+pub struct MaxQueryDepth;
+
+fn main() {}
@@ -0,0 +1,16 @@
+#![deny(deprecated)]
+
+#[macro_use]
+extern crate graphql_client;
+extern crate serde_derive;
+extern crate serde;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "../../../../graphql_client/tests/fail/schema.graphql",
+    query_path = "../../../../graphql_client/tests/warnings/skip_query_validation.graphql",
+    skip_query_validation = "true"
+)]
+pub struct SkipQueryValidation;
+
+fn main() {}
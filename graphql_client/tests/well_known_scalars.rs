@@ -0,0 +1,76 @@
+#![cfg(feature = "well_known_scalars")]
+
+extern crate bigdecimal;
+#[macro_use]
+extern crate graphql_client;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/well_known_scalars/query.graphql",
+    schema_path = "tests/well_known_scalars/schema.graphql"
+)]
+pub struct WellKnownScalarsQuery;
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct CustomJson {
+    name: String,
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "tests/well_known_scalars/query.graphql",
+    schema_path = "tests/well_known_scalars/schema.graphql",
+    scalars = "JSON = ::CustomJson, Long = String"
+)]
+pub struct WellKnownScalarsQueryWithOverrides;
+
+#[test]
+fn well_known_scalars_are_mapped() {
+    let response = json!({
+        "long": 9007199254740993i64,
+        "short": -12,
+        "byte": 7,
+        "bigDecimal": "3.14159265358979323846264338327950288",
+        "json": { "name": "tiramisù", "tags": [1, 2] },
+    });
+
+    let data: well_known_scalars_query::ResponseData = serde_json::from_value(response).unwrap();
+
+    assert_eq!(data.long, 9007199254740993i64);
+    assert_eq!(data.short, -12i16);
+    assert_eq!(data.byte, 7i8);
+    assert_eq!(
+        data.big_decimal,
+        "3.14159265358979323846264338327950288"
+            .parse::<bigdecimal::BigDecimal>()
+            .unwrap()
+    );
+    assert_eq!(data.json.unwrap()["tags"], json!([1, 2]));
+}
+
+#[test]
+fn well_known_scalars_can_be_overridden() {
+    let response = json!({
+        "long": "9007199254740993",
+        "short": -12,
+        "byte": 7,
+        "bigDecimal": "3.14",
+        "json": { "name": "tiramisù" },
+    });
+
+    let data: well_known_scalars_query_with_overrides::ResponseData =
+        serde_json::from_value(response).unwrap();
+
+    assert_eq!(data.long, "9007199254740993");
+    assert_eq!(
+        data.json,
+        Some(CustomJson {
+            name: "tiramisù".to_string()
+        })
+    );
+}
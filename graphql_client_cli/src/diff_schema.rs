@@ -0,0 +1,22 @@
+use failure;
+use graphql_client_codegen::diff::diff_schemas;
+use graphql_client_codegen::read_schema;
+use std::path::Path;
+
+pub fn diff_schema(old_schema_path: &Path, new_schema_path: &Path) -> Result<(), failure::Error> {
+    let old_schema = read_schema(old_schema_path)?;
+    let new_schema = read_schema(new_schema_path)?;
+
+    let changes = diff_schemas(&old_schema, &new_schema);
+
+    if changes.is_empty() {
+        println!("No breaking changes found.");
+        return Ok(());
+    }
+
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    Err(format_err!("Found {} breaking change(s)", changes.len()))
+}
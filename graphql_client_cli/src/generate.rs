@@ -11,6 +11,8 @@ pub fn generate_code(
     schema_path: PathBuf,
     selected_operation: String,
     additional_derives: Option<String>,
+    variables_derives: Option<String>,
+    response_derives: Option<String>,
     deprecation_strategy: &Option<String>,
     no_formatting: bool,
     module_visibility: &Option<String>,
@@ -38,8 +40,11 @@ pub fn generate_code(
     let options = GraphQLClientDeriveOptions {
         struct_name: selected_operation,
         additional_derives,
+        variables_derives,
+        response_derives,
         deprecation_strategy,
         module_visibility,
+        ..Default::default()
     };
 
     let gen = generate_module_token_stream(query_path, schema_path, Some(options))?;
@@ -1,3 +1,4 @@
+#[macro_use]
 extern crate failure;
 extern crate reqwest;
 extern crate structopt;
@@ -14,6 +15,7 @@ extern crate syn;
 #[cfg(feature = "rustfmt")]
 extern crate rustfmt_nightly as rustfmt;
 
+mod diff_schema;
 mod generate;
 mod introspect_schema;
 use std::path::PathBuf;
@@ -48,6 +50,14 @@ enum Cli {
         /// --additional-derives='Serialize,PartialEq'
         #[structopt(short = "a", long = "additional-derives")]
         additional_derives: Option<String>,
+        /// Additional derives that will be added to the generated structs for the variables only.
+        /// --variables-derives='Debug,PartialEq'
+        #[structopt(long = "variables-derives")]
+        variables_derives: Option<String>,
+        /// Additional derives that will be added to the generated structs and enums for the response only.
+        /// --response-derives='Serialize,PartialEq'
+        #[structopt(long = "response-derives")]
+        response_derives: Option<String>,
         /// You can choose deprecation strategy from allow, deny, or warn.
         /// Default value is warn.
         #[structopt(short = "d", long = "deprecation-strategy")]
@@ -64,6 +74,16 @@ enum Cli {
         #[structopt(parse(from_os_str))]
         output: PathBuf,
     },
+    /// Report the changes between two schemas that would break existing queries. Exits with an error if there are any.
+    #[structopt(name = "diff-schema")]
+    DiffSchema {
+        /// Path to the old schema (.graphql or .json).
+        #[structopt(parse(from_os_str))]
+        old_schema_path: PathBuf,
+        /// Path to the new schema (.graphql or .json).
+        #[structopt(parse(from_os_str))]
+        new_schema_path: PathBuf,
+    },
 }
 
 fn main() -> Result<(), failure::Error> {
@@ -79,6 +99,8 @@ fn main() -> Result<(), failure::Error> {
             schema_path,
             selected_operation,
             additional_derives,
+            variables_derives,
+            response_derives,
             deprecation_strategy,
             no_formatting,
             module_visibility,
@@ -88,10 +110,16 @@ fn main() -> Result<(), failure::Error> {
             schema_path,
             selected_operation,
             additional_derives,
+            variables_derives,
+            response_derives,
             &deprecation_strategy,
             no_formatting,
             &module_visibility,
             &output,
         ),
+        Cli::DiffSchema {
+            old_schema_path,
+            new_schema_path,
+        } => diff_schema::diff_schema(&old_schema_path, &new_schema_path),
     }
 }
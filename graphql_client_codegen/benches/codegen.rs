@@ -0,0 +1,79 @@
+#[macro_use]
+extern crate criterion;
+extern crate graphql_client_codegen;
+extern crate graphql_parser;
+extern crate proc_macro2;
+
+use criterion::Criterion;
+use graphql_client_codegen::schema::Schema;
+use graphql_client_codegen::{
+    codegen_to_string, generate_module_token_stream, GraphQLClientDeriveOptions,
+};
+use std::path::PathBuf;
+
+const SCHEMA_PATH: &str = "src/tests/github_schema.graphql";
+const QUERY_PATH: &str = "benches/github_query.graphql";
+const SCHEMA: &str = include_str!("../src/tests/github_schema.graphql");
+const QUERY: &str = include_str!("github_query.graphql");
+
+fn options() -> GraphQLClientDeriveOptions {
+    GraphQLClientDeriveOptions {
+        struct_name: "RepositoryQuery".to_string(),
+        response_derives: Some("Debug".to_string()),
+        ..Default::default()
+    }
+}
+
+fn generate() -> proc_macro2::TokenStream {
+    generate_module_token_stream(
+        PathBuf::from(QUERY_PATH),
+        PathBuf::from(SCHEMA_PATH),
+        Some(options()),
+    )
+    .unwrap()
+}
+
+fn schema_parsing(c: &mut Criterion) {
+    c.bench_function("schema parsing (GitHub schema)", |b| {
+        b.iter(|| Schema::from_sdl(SCHEMA).unwrap())
+    });
+}
+
+fn query_parsing(c: &mut Criterion) {
+    c.bench_function("query parsing", |b| {
+        b.iter(|| graphql_parser::parse_query(QUERY).unwrap())
+    });
+}
+
+fn code_generation(c: &mut Criterion) {
+    // The parsed schema and query are cached after the first call, so this measures code generation alone.
+    generate();
+    c.bench_function("code generation (GitHub schema)", |b| b.iter(generate));
+}
+
+fn token_stream_serialization(c: &mut Criterion) {
+    let tokens = generate();
+    c.bench_function("token stream serialization", |b| {
+        b.iter(|| tokens.to_string())
+    });
+}
+
+fn full_pipeline(c: &mut Criterion) {
+    let options = options();
+    c.bench_function("full pipeline (GitHub schema)", |b| {
+        b.iter(|| {
+            let schema = Schema::from_sdl(SCHEMA).unwrap();
+            codegen_to_string(&schema, QUERY, &options).unwrap()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    schema_parsing,
+    query_parsing,
+    code_generation,
+    token_stream_serialization,
+    full_pipeline
+);
+criterion_main!(benches);
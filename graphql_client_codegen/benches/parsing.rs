@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate criterion;
+extern crate graphql_client_codegen;
+
+use criterion::Criterion;
+use graphql_client_codegen::schema::Schema;
+
+const SCHEMA_GRAPHQL: &str = include_str!("../src/tests/github_schema.graphql");
+const SCHEMA_JSON: &str = include_str!("../src/tests/github_schema.json");
+
+fn parse_sdl(c: &mut Criterion) {
+    c.bench_function("Schema::from_sdl (GitHub schema)", |b| {
+        b.iter(|| Schema::from_sdl(SCHEMA_GRAPHQL).unwrap())
+    });
+}
+
+fn parse_introspection_json(c: &mut Criterion) {
+    c.bench_function("Schema::from_introspection_json (GitHub schema)", |b| {
+        b.iter(|| Schema::from_introspection_json(SCHEMA_JSON).unwrap())
+    });
+}
+
+criterion_group!(benches, parse_sdl, parse_introspection_json);
+criterion_main!(benches);
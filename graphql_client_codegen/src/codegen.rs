@@ -1,15 +1,17 @@
-use deprecation;
 use failure;
 use fragments::GqlFragment;
 use graphql_parser::query;
 use operations::Operation;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream};
 use query::QueryContext;
 use schema;
 use selection::Selection;
+use std::collections::BTreeMap;
+use variables::VariablesCase;
+use GraphQLClientDeriveOptions;
 
 /// Selects the first operation matching `struct_name` or the first one. Returns `None` when the query document defines no operation.
-pub(crate) fn select_operation(query: &query::Document, struct_name: &str) -> Option<Operation> {
+pub(crate) fn all_operations(query: &query::Document) -> Vec<Operation> {
     let mut operations: Vec<Operation> = Vec::new();
 
     for definition in &query.definitions {
@@ -18,6 +20,12 @@ pub(crate) fn select_operation(query: &query::Document, struct_name: &str) -> Op
         }
     }
 
+    operations
+}
+
+pub(crate) fn select_operation(query: &query::Document, struct_name: &str) -> Option<Operation> {
+    let operations = all_operations(query);
+
     operations
         .iter()
         .find(|op| op.name == struct_name)
@@ -30,68 +38,111 @@ pub fn response_for_query(
     schema: schema::Schema,
     query: query::Document,
     operation: &Operation,
-    additional_derives: Option<String>,
-    deprecation_strategy: deprecation::DeprecationStrategy,
+    options: &GraphQLClientDeriveOptions,
 ) -> Result<TokenStream, failure::Error> {
+    if !options.generate_structs {
+        // These options all work on the response types.
+        let conflicting_option = [
+            ("into", options.into.is_some()),
+            ("generate_mock", options.generate_mock),
+            ("deserializer_impl", options.deserializer_impl),
+            ("strict_response", options.strict_response),
+        ]
+        .iter()
+        .find(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name);
+
+        if let Some(name) = conflicting_option {
+            Err(format_err!(
+                "The {} option needs the response types, so it cannot be used with generate_structs = \"false\"",
+                name
+            ))?
+        }
+    }
+
+    // The user can determine what to do about deprecations.
+    let deprecation_strategy = options.deprecation_strategy.unwrap_or_default();
     let mut context = QueryContext::new(schema, deprecation_strategy);
 
-    if let Some(derives) = additional_derives {
-        context.ingest_additional_derives(&derives).unwrap();
+    if let Some(derives) = &options.additional_derives {
+        context.ingest_additional_derives(derives).unwrap();
     }
 
-    let mut definitions = Vec::new();
+    context.well_known_scalars = cfg!(feature = "well_known_scalars");
+    context.bytes_scalar = cfg!(feature = "bytes");
+    context.regex_validation = cfg!(feature = "validation");
+    context.federation_mode = options.federation_mode;
+    context.strict_response = options.strict_response;
+    context.no_std = options.no_std;
+
+    if let Some(prefix) = &options.response_type_prefix {
+        context.response_type_prefix = prefix.clone();
+    }
+
+    if let Some(case) = &options.rename_variables {
+        context.rename_variables = Some(VariablesCase::parse(case)?);
+    }
+
+    if let Some(fields) = &options.allow_deprecated_fields {
+        context.ingest_allowed_deprecated_fields(fields);
+    }
+
+    if let Some(mappings) = &options.scalars {
+        context.ingest_scalar_mappings(mappings)?;
+    }
+
+    if let Some(mappings) = &options.newtype_scalars {
+        context.ingest_newtype_scalars(mappings)?;
+    }
+
+    if let Some(mappings) = &options.scalars_with {
+        context.ingest_serde_as_mappings(mappings)?;
+    }
+
+    if let Some(derives) = &options.variables_derives {
+        context.ingest_variables_derives(derives)?;
+    }
+
+    if let Some(derives) = &options.response_derives {
+        context.ingest_response_derives(derives)?;
+    }
+
+    if let Some(derives) = &options.response_derives_exclusive {
+        context.set_response_derives_exclusive(derives)?;
+    }
 
-    for definition in query.definitions {
-        match definition {
-            query::Definition::Operation(_op) => (),
+    let deprecated_arguments =
+        ::variables::deprecated_argument_uses(&context, &query, &operation.name);
+
+    let fragments = query
+        .definitions
+        .into_iter()
+        .filter_map(|definition| match definition {
+            query::Definition::Operation(_op) => None,
             query::Definition::Fragment(fragment) => {
                 let query::TypeCondition::On(on) = fragment.type_condition;
-                context.fragments.insert(
-                    fragment.name.clone(),
-                    GqlFragment {
-                        name: fragment.name,
-                        selection: Selection::from(&fragment.selection_set),
-                        on,
-                        is_required: false.into(),
-                    },
-                );
+                Some(GqlFragment {
+                    name: fragment.name,
+                    selection: Selection::from(&fragment.selection_set),
+                    on,
+                    is_required: false.into(),
+                })
             }
-        }
-    }
+        });
+    context.extend(fragments);
 
-    let response_data_fields = {
-        let opt_root_name = operation.root_name(&context.schema);
-        let root_name: String = if let Some(root_name) = opt_root_name {
-            root_name
-        } else {
-            panic!(
-                "operation type '{:?}' not in schema",
-                operation.operation_type
-            );
-        };
-        let definition = context
-            .schema
-            .objects
-            .get(&root_name)
-            .expect("schema declaration is invalid");
-        let prefix = format!("RUST_{}", operation.name);
-        let selection = &operation.selection;
-
-        if operation.is_subscription() && selection.0.len() > 1 {
-            Err(format_err!(
-                "{}",
-                ::constants::MULTIPLE_SUBSCRIPTION_FIELDS_ERROR
-            ))?
-        }
+    if let Some(cycle) = context.fragment_cycle() {
+        Err(format_err!("Fragment cycle: {}", cycle.join(" -> ")))?
+    }
 
-        definitions.extend(
-            definition
-                .field_impls_for_selection(&context, &selection, &prefix)
-                .unwrap(),
-        );
-        definition
-            .response_fields_for_selection(&context, &selection, &prefix)
-            .unwrap()
+    let response_data = if options.generate_structs {
+        Some(response_data_for_operation(
+            &mut context,
+            operation,
+            options,
+        )?)
+    } else {
+        None
     };
 
     let enum_definitions = context.schema.enums.values().filter_map(|enm| {
@@ -101,18 +152,7 @@ pub fn response_for_query(
             None
         }
     });
-    let fragment_definitions: Result<Vec<TokenStream>, _> = context
-        .fragments
-        .values()
-        .filter_map(|fragment| {
-            if fragment.is_required.get() {
-                Some(fragment.to_rust(&context))
-            } else {
-                None
-            }
-        }).collect();
-    let fragment_definitions = fragment_definitions?;
-    let variables_struct = operation.expand_variables(&context);
+    let variables_struct = operation.expand_variables(&context, &deprecated_arguments)?;
 
     let input_object_definitions: Result<Vec<TokenStream>, _> = context
         .schema
@@ -133,25 +173,52 @@ pub fn response_for_query(
         .values()
         .filter_map(|s| {
             if s.is_required.get() {
-                Some(s.to_rust())
+                Some(s.to_rust(&context))
             } else {
                 None
             }
         }).collect();
 
-    let response_derives = context.response_derives();
+    // ID can be mapped like custom scalars, for example to a newtype for primary keys.
+    let id_type = match context.scalar_mapping("ID") {
+        Some(path) => quote!(#path),
+        None => quote!(String),
+    };
+    // Float can be mapped to `f32`, e.g. for game engines and embedded targets.
+    let float_type = match context.scalar_mapping("Float") {
+        Some(path) => quote!(#path),
+        None => quote!(f64),
+    };
+
+    // `vec` is the macro, used for the default values of list variables.
+    let alloc_imports = if context.no_std {
+        Some(quote! {
+            #[allow(unused_imports)]
+            use ::graphql_client::alloc::boxed::Box;
+            #[allow(unused_imports)]
+            use ::graphql_client::alloc::string::{String, ToString};
+            #[allow(unused_imports)]
+            use ::graphql_client::alloc::vec;
+            #[allow(unused_imports)]
+            use ::graphql_client::alloc::vec::Vec;
+        })
+    } else {
+        None
+    };
 
     Ok(quote! {
         use serde_derive::*;
 
+        #alloc_imports
+
         #[allow(dead_code)]
         type Boolean = bool;
         #[allow(dead_code)]
-        type Float = f64;
+        type Float = #float_type;
         #[allow(dead_code)]
         type Int = i64;
         #[allow(dead_code)]
-        type ID = String;
+        type ID = #id_type;
 
         #(#scalar_definitions)*
 
@@ -159,16 +226,156 @@ pub fn response_for_query(
 
         #(#enum_definitions)*
 
+        #variables_struct
+
+        #response_data
+    })
+}
+
+/// The response types of the operation: the fragments, the structs for the selected fields and `ResponseData`, with the constants and impls that go with them.
+fn response_data_for_operation(
+    context: &mut QueryContext,
+    operation: &Operation,
+    options: &GraphQLClientDeriveOptions,
+) -> Result<TokenStream, failure::Error> {
+    let mut definitions = Vec::new();
+    let cache_max_age;
+    let strict_response;
+    let mut mock_response = None;
+    let response_data_field_names;
+    let (deserialize_from, deserialization);
+    let response_data_fields = {
+        let opt_root_name = operation.root_name(&context.schema);
+        let root_name: String = if let Some(root_name) = opt_root_name {
+            root_name
+        } else {
+            panic!(
+                "operation type '{:?}' not in schema",
+                operation.operation_type
+            );
+        };
+        let definition = context
+            .schema
+            .objects
+            .get(&root_name)
+            .expect("schema declaration is invalid");
+        let prefix = context.prefixed_type_name(&format!("RUST_{}", operation.name));
+        let selection = &operation.selection.merge_inline_fragments_on(&root_name);
+
+        cache_max_age = context.cache_max_age_for_selection(&root_name, selection);
+        strict_response = context.strict_response_attribute(&root_name, selection);
+        context.ord_derives = options.ord_derives
+            && context.response_derives_eq()
+            && context.selection_is_ord(&root_name, selection);
+
+        if !options.skip_query_validation && operation.is_subscription() && selection.0.len() > 1 {
+            Err(format_err!(
+                "{}",
+                ::constants::MULTIPLE_SUBSCRIPTION_FIELDS_ERROR
+            ))?
+        }
+
+        definitions.extend(definition.field_impls_for_selection(context, selection, &prefix)?);
+        let response_data_fields =
+            definition.response_fields_for_selection(context, selection, &prefix)?;
+        let response_data_name = Ident::new("ResponseData", Span::call_site());
+        response_data_field_names =
+            definition.field_names_impl(context, selection, &response_data_name);
+        let spreads_deserialization = ::shared::fragment_spreads_deserialization(
+            context,
+            &response_data_name,
+            &response_data_fields,
+            selection,
+        )?;
+        deserialize_from = spreads_deserialization.0;
+        deserialization = spreads_deserialization.1;
+
+        if options.generate_mock {
+            mock_response = Some(::mock::mock_response(context, &root_name, selection)?);
+        }
+
+        response_data_fields
+    };
+
+    // Rendering a fragment can require the fragments it spreads, so we render them until no new
+    // fragment is required. The fragments that are never required are not generated at all.
+    let mut fragment_definitions: BTreeMap<&str, TokenStream> = BTreeMap::new();
+    loop {
+        let pending: Vec<_> = context
+            .fragments
+            .values()
+            .filter(|fragment| {
+                fragment.is_required.get()
+                    && !fragment_definitions.contains_key(fragment.name.as_str())
+            }).collect();
+        if pending.is_empty() {
+            break;
+        }
+        for fragment in pending {
+            fragment_definitions.insert(&fragment.name, fragment.to_rust(context)?);
+        }
+    }
+    // Fragments come after the fragments they spread.
+    let fragment_definitions: Vec<TokenStream> = context
+        .fragments_in_dependency_order()
+        .filter_map(|(name, _)| fragment_definitions.remove(name))
+        .collect();
+
+    let response_derives = context.response_derives();
+
+    let deserializer_impl = if options.deserializer_impl {
+        if !context.response_derives_serialize() {
+            Err(format_err!(
+                "deserializer_impl requires Serialize in the response derives"
+            ))?
+        }
+        Some(quote! {
+            /// Deserializes other types from the response data, through its JSON representation.
+            impl<'de> ::serde::Deserializer<'de> for ResponseData {
+                type Error = ::graphql_client::serde_json::Error;
+
+                fn deserialize_any<V: ::serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                    let value = ::graphql_client::serde_json::to_value(self)?;
+                    ::serde::Deserializer::deserialize_any(value, visitor)
+                }
+
+                ::serde::forward_to_deserialize_any! {
+                    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+                    bytes byte_buf option unit unit_struct newtype_struct seq tuple
+                    tuple_struct map struct enum identifier ignored_any
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let cache_max_age = match cache_max_age {
+        Some(max_age) => quote!(Some(#max_age)),
+        None => quote!(None),
+    };
+
+    Ok(quote! {
         #(#fragment_definitions)*
 
         #(#definitions)*
 
-        #variables_struct
+        /// The smallest `@cacheControl` max age among the fields selected by the operation.
+        pub const CACHE_MAX_AGE: Option<u32> = #cache_max_age;
 
         #response_derives
+        #deserialize_from
+        #strict_response
         pub struct ResponseData {
             #(#response_data_fields,)*
         }
 
+        #deserialization
+
+        #response_data_field_names
+
+        #mock_response
+
+        #deserializer_impl
     })
 }
@@ -1,9 +1,13 @@
 use deprecation::DeprecationStatus;
+use directives::Directives;
 use field_type::FieldType;
 use objects::GqlObjectField;
 
 pub(crate) const TYPENAME_FIELD: &str = "__typename";
 
+/// The incremental delivery directive for list fields, see [StreamedField](https://docs.rs/graphql_client/latest/graphql_client/struct.StreamedField.html).
+pub(crate) const STREAM_DIRECTIVE: &str = "stream";
+
 pub(crate) fn string_type() -> String {
     "String".to_string()
 }
@@ -21,6 +25,8 @@ pub(crate) fn typename_field() -> GqlObjectField {
         /// https://github.com/facebook/graphql/blob/master/spec/Section%204%20--%20Introspection.md
         type_: FieldType::Named(string_type()),
         deprecation: DeprecationStatus::Current,
+        directives: Directives::default(),
+        arguments: Vec::new(),
     }
 }
 
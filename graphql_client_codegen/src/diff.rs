@@ -0,0 +1,759 @@
+use field_type::FieldType;
+use objects::GqlObjectField;
+use schema::Schema;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A change between two versions of a schema that can break queries written (and code generated) against the old version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakingChange {
+    /// A type was removed from the schema.
+    TypeRemoved {
+        /// The name of the removed type.
+        type_name: String,
+    },
+    /// A type changed from one kind to another, for example from an object to an interface.
+    TypeKindChanged {
+        /// The name of the type.
+        type_name: String,
+        /// The kind of the type in the old schema.
+        old_kind: &'static str,
+        /// The kind of the type in the new schema.
+        new_kind: &'static str,
+    },
+    /// A field was removed from an object, interface or input object.
+    FieldRemoved {
+        /// The type the field belonged to.
+        type_name: String,
+        /// The name of the removed field.
+        field_name: String,
+    },
+    /// The type of a field changed in an incompatible way.
+    FieldTypeChanged {
+        /// The type the field belongs to.
+        type_name: String,
+        /// The name of the field.
+        field_name: String,
+        /// The type of the field in the old schema, in GraphQL notation.
+        old_type: String,
+        /// The type of the field in the new schema, in GraphQL notation.
+        new_type: String,
+    },
+    /// A non-nullable field became nullable.
+    FieldMadeNullable {
+        /// The type the field belongs to.
+        type_name: String,
+        /// The name of the field.
+        field_name: String,
+    },
+    /// An argument was removed from a field.
+    ArgumentRemoved {
+        /// The type the field belongs to.
+        type_name: String,
+        /// The name of the field.
+        field_name: String,
+        /// The name of the removed argument.
+        argument_name: String,
+    },
+    /// A non-nullable argument without a default value was added to a field.
+    ArgumentAddedWithoutDefault {
+        /// The type the field belongs to.
+        type_name: String,
+        /// The name of the field.
+        field_name: String,
+        /// The name of the new argument.
+        argument_name: String,
+    },
+    /// The type of an argument changed in an incompatible way.
+    ArgumentTypeChanged {
+        /// The type the field belongs to.
+        type_name: String,
+        /// The name of the field.
+        field_name: String,
+        /// The name of the argument.
+        argument_name: String,
+        /// The type of the argument in the old schema, in GraphQL notation.
+        old_type: String,
+        /// The type of the argument in the new schema, in GraphQL notation.
+        new_type: String,
+    },
+    /// A value was removed from an enum.
+    EnumValueRemoved {
+        /// The name of the enum.
+        enum_name: String,
+        /// The removed value.
+        value: String,
+    },
+    /// A member type was removed from a union.
+    UnionMemberRemoved {
+        /// The name of the union.
+        union_name: String,
+        /// The removed member type.
+        member: String,
+    },
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BreakingChange::TypeRemoved { type_name } => {
+                write!(f, "Type {} was removed", type_name)
+            }
+            BreakingChange::TypeKindChanged {
+                type_name,
+                old_kind,
+                new_kind,
+            } => write!(
+                f,
+                "Type {} changed from {} to {}",
+                type_name, old_kind, new_kind
+            ),
+            BreakingChange::FieldRemoved {
+                type_name,
+                field_name,
+            } => write!(f, "Field {}.{} was removed", type_name, field_name),
+            BreakingChange::FieldTypeChanged {
+                type_name,
+                field_name,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "Field {}.{} changed type from {} to {}",
+                type_name, field_name, old_type, new_type
+            ),
+            BreakingChange::FieldMadeNullable {
+                type_name,
+                field_name,
+            } => write!(f, "Field {}.{} was made nullable", type_name, field_name),
+            BreakingChange::ArgumentRemoved {
+                type_name,
+                field_name,
+                argument_name,
+            } => write!(
+                f,
+                "Argument {} was removed from field {}.{}",
+                argument_name, type_name, field_name
+            ),
+            BreakingChange::ArgumentAddedWithoutDefault {
+                type_name,
+                field_name,
+                argument_name,
+            } => write!(
+                f,
+                "Required argument {} without a default value was added to field {}.{}",
+                argument_name, type_name, field_name
+            ),
+            BreakingChange::ArgumentTypeChanged {
+                type_name,
+                field_name,
+                argument_name,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "Argument {} on field {}.{} changed type from {} to {}",
+                argument_name, type_name, field_name, old_type, new_type
+            ),
+            BreakingChange::EnumValueRemoved { enum_name, value } => {
+                write!(f, "Value {} was removed from enum {}", value, enum_name)
+            }
+            BreakingChange::UnionMemberRemoved { union_name, member } => {
+                write!(f, "Member {} was removed from union {}", member, union_name)
+            }
+        }
+    }
+}
+
+/// Lists the changes from `old` to `new` that would break existing queries or generated code.
+///
+/// Additive changes (new types, new fields, new optional arguments...) are not reported.
+pub fn diff_schemas(old: &Schema, new: &Schema) -> Vec<BreakingChange> {
+    let mut changes = Vec::new();
+
+    let old_kinds = type_kinds(old);
+    let new_kinds = type_kinds(new);
+
+    for (name, old_kind) in &old_kinds {
+        match new_kinds.get(name) {
+            None => changes.push(BreakingChange::TypeRemoved {
+                type_name: name.to_string(),
+            }),
+            Some(new_kind) if new_kind != old_kind => {
+                changes.push(BreakingChange::TypeKindChanged {
+                    type_name: name.to_string(),
+                    old_kind,
+                    new_kind,
+                })
+            }
+            Some(_) => (),
+        }
+    }
+
+    for (name, old_object) in &old.objects {
+        if let Some(new_object) = new.objects.get(name) {
+            diff_output_fields(name, &old_object.fields, &new_object.fields, &mut changes);
+        }
+    }
+
+    for (name, old_interface) in &old.interfaces {
+        if let Some(new_interface) = new.interfaces.get(name) {
+            diff_output_fields(
+                name,
+                &old_interface.fields,
+                &new_interface.fields,
+                &mut changes,
+            );
+        }
+    }
+
+    for (name, old_input) in &old.inputs {
+        if let Some(new_input) = new.inputs.get(name) {
+            let mut old_fields: Vec<&GqlObjectField> = old_input.fields.values().collect();
+            old_fields.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+            for old_field in old_fields {
+                match new_input.fields.get(&old_field.name) {
+                    None => changes.push(BreakingChange::FieldRemoved {
+                        type_name: name.clone(),
+                        field_name: old_field.name.clone(),
+                    }),
+                    Some(new_field) => {
+                        if !is_compatible_input_type(&old_field.type_, &new_field.type_) {
+                            changes.push(BreakingChange::FieldTypeChanged {
+                                type_name: name.clone(),
+                                field_name: old_field.name.clone(),
+                                old_type: old_field.type_.to_string(),
+                                new_type: new_field.type_.to_string(),
+                            })
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for (name, old_enum) in &old.enums {
+        if let Some(new_enum) = new.enums.get(name) {
+            for variant in &old_enum.variants {
+                if !new_enum.variants.iter().any(|v| v.name == variant.name) {
+                    changes.push(BreakingChange::EnumValueRemoved {
+                        enum_name: name.clone(),
+                        value: variant.name.clone(),
+                    })
+                }
+            }
+        }
+    }
+
+    for (name, old_union) in &old.unions {
+        if let Some(new_union) = new.unions.get(name) {
+            for member in old_union.variants.difference(&new_union.variants) {
+                changes.push(BreakingChange::UnionMemberRemoved {
+                    union_name: name.clone(),
+                    member: member.clone(),
+                })
+            }
+        }
+    }
+
+    changes
+}
+
+/// The types and fields added, removed or changed between two versions of a schema, see [Schema::diff].
+///
+/// Unlike [diff_schemas], it lists every change to types and fields, not only the breaking ones. A type that changed kind (from an object to an interface, for example) is both removed and added.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    /// The types that are only in the new schema, sorted by name.
+    pub added_types: Vec<String>,
+    /// The types that are only in the old schema, sorted by name.
+    pub removed_types: Vec<String>,
+    /// The fields of objects, interfaces and input objects that were removed or whose type changed.
+    pub changed_fields: Vec<FieldChange>,
+    /// The fields added to existing objects, interfaces and input objects.
+    pub added_fields: Vec<FieldChange>,
+}
+
+/// A field added, removed or changed between two versions of a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// The type the field belongs to.
+    pub type_name: String,
+    /// The name of the field.
+    pub field_name: String,
+    /// The type of the field in the old schema, in GraphQL notation. `None` for added fields.
+    pub old_type: Option<String>,
+    /// The type of the field in the new schema, in GraphQL notation. `None` for removed fields.
+    pub new_type: Option<String>,
+    /// Whether the change breaks queries written against the old schema: the field was removed, or its new type does not accept the old values (for input fields) or cannot be deserialized as the old type (for output fields).
+    pub breaking: bool,
+}
+
+impl SchemaDiff {
+    pub(crate) fn between(old: &Schema, new: &Schema) -> SchemaDiff {
+        let mut diff = SchemaDiff::default();
+
+        let old_kinds = type_kinds(old);
+        let new_kinds = type_kinds(new);
+        for (name, old_kind) in &old_kinds {
+            if new_kinds.get(name) != Some(old_kind) {
+                diff.removed_types.push(name.to_string());
+            }
+        }
+        for (name, new_kind) in &new_kinds {
+            if old_kinds.get(name) != Some(new_kind) {
+                diff.added_types.push(name.to_string());
+            }
+        }
+
+        let output_fields = old
+            .objects
+            .iter()
+            .filter_map(|(name, old_object)| {
+                let new_object = new.objects.get(name)?;
+                Some((name, &old_object.fields, &new_object.fields))
+            }).chain(old.interfaces.iter().filter_map(|(name, old_interface)| {
+                let new_interface = new.interfaces.get(name)?;
+                Some((name, &old_interface.fields, &new_interface.fields))
+            }));
+        for (name, old_fields, new_fields) in output_fields {
+            let old_fields: Vec<&GqlObjectField> = old_fields.iter().collect();
+            let new_fields: Vec<&GqlObjectField> = new_fields.iter().collect();
+            diff.diff_fields(name, &old_fields, &new_fields, is_compatible_output_type);
+        }
+
+        for (name, old_input) in &old.inputs {
+            if let Some(new_input) = new.inputs.get(name) {
+                let mut old_fields: Vec<&GqlObjectField> = old_input.fields.values().collect();
+                old_fields.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+                let mut new_fields: Vec<&GqlObjectField> = new_input.fields.values().collect();
+                new_fields.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+                diff.diff_fields(name, &old_fields, &new_fields, is_compatible_input_type);
+            }
+        }
+
+        diff
+    }
+
+    /// Whether a type or field was removed, or a field type changed in a way that breaks queries written against the old schema.
+    pub fn is_breaking(&self) -> bool {
+        !self.removed_types.is_empty() || self.changed_fields.iter().any(|change| change.breaking)
+    }
+
+    fn diff_fields(
+        &mut self,
+        type_name: &str,
+        old_fields: &[&GqlObjectField],
+        new_fields: &[&GqlObjectField],
+        is_compatible: fn(&FieldType, &FieldType) -> bool,
+    ) {
+        for old_field in old_fields {
+            match new_fields.iter().find(|f| f.name == old_field.name) {
+                None => self.changed_fields.push(FieldChange {
+                    type_name: type_name.to_string(),
+                    field_name: old_field.name.clone(),
+                    old_type: Some(old_field.type_.to_string()),
+                    new_type: None,
+                    breaking: true,
+                }),
+                Some(new_field) if new_field.type_ != old_field.type_ => {
+                    self.changed_fields.push(FieldChange {
+                        type_name: type_name.to_string(),
+                        field_name: old_field.name.clone(),
+                        old_type: Some(old_field.type_.to_string()),
+                        new_type: Some(new_field.type_.to_string()),
+                        breaking: !is_compatible(&old_field.type_, &new_field.type_),
+                    })
+                }
+                Some(_) => (),
+            }
+        }
+
+        for new_field in new_fields {
+            if !old_fields.iter().any(|f| f.name == new_field.name) {
+                self.added_fields.push(FieldChange {
+                    type_name: type_name.to_string(),
+                    field_name: new_field.name.clone(),
+                    old_type: None,
+                    new_type: Some(new_field.type_.to_string()),
+                    breaking: false,
+                })
+            }
+        }
+    }
+}
+
+fn type_kinds(schema: &Schema) -> BTreeMap<&str, &'static str> {
+    let mut kinds = BTreeMap::new();
+    kinds.extend(schema.enums.keys().map(|name| (name.as_str(), "enum")));
+    kinds.extend(
+        schema
+            .inputs
+            .keys()
+            .map(|name| (name.as_str(), "input object")),
+    );
+    kinds.extend(
+        schema
+            .interfaces
+            .keys()
+            .map(|name| (name.as_str(), "interface")),
+    );
+    kinds.extend(schema.objects.keys().map(|name| (name.as_str(), "object")));
+    kinds.extend(schema.scalars.keys().map(|name| (name.as_str(), "scalar")));
+    kinds.extend(schema.unions.keys().map(|name| (name.as_str(), "union")));
+    kinds
+}
+
+fn diff_output_fields(
+    type_name: &str,
+    old_fields: &[GqlObjectField],
+    new_fields: &[GqlObjectField],
+    changes: &mut Vec<BreakingChange>,
+) {
+    for old_field in old_fields {
+        let new_field = match new_fields.iter().find(|f| f.name == old_field.name) {
+            Some(field) => field,
+            None => {
+                changes.push(BreakingChange::FieldRemoved {
+                    type_name: type_name.to_string(),
+                    field_name: old_field.name.clone(),
+                });
+                continue;
+            }
+        };
+
+        if !is_compatible_output_type(&old_field.type_, &new_field.type_) {
+            // Making the field less strict than it was is the only incompatible change left
+            // when the types are compatible as input types.
+            if is_compatible_input_type(&old_field.type_, &new_field.type_) {
+                changes.push(BreakingChange::FieldMadeNullable {
+                    type_name: type_name.to_string(),
+                    field_name: old_field.name.clone(),
+                })
+            } else {
+                changes.push(BreakingChange::FieldTypeChanged {
+                    type_name: type_name.to_string(),
+                    field_name: old_field.name.clone(),
+                    old_type: old_field.type_.to_string(),
+                    new_type: new_field.type_.to_string(),
+                })
+            }
+        }
+
+        for old_arg in &old_field.arguments {
+            match new_field.arguments.iter().find(|a| a.name == old_arg.name) {
+                None => changes.push(BreakingChange::ArgumentRemoved {
+                    type_name: type_name.to_string(),
+                    field_name: old_field.name.clone(),
+                    argument_name: old_arg.name.clone(),
+                }),
+                Some(new_arg) => {
+                    if !is_compatible_input_type(&old_arg.type_, &new_arg.type_) {
+                        changes.push(BreakingChange::ArgumentTypeChanged {
+                            type_name: type_name.to_string(),
+                            field_name: old_field.name.clone(),
+                            argument_name: old_arg.name.clone(),
+                            old_type: old_arg.type_.to_string(),
+                            new_type: new_arg.type_.to_string(),
+                        })
+                    }
+                }
+            }
+        }
+
+        for new_arg in &new_field.arguments {
+            let is_new = !old_field.arguments.iter().any(|a| a.name == new_arg.name);
+            if is_new && !new_arg.type_.is_optional() && new_arg.default_value.is_none() {
+                changes.push(BreakingChange::ArgumentAddedWithoutDefault {
+                    type_name: type_name.to_string(),
+                    field_name: old_field.name.clone(),
+                    argument_name: new_arg.name.clone(),
+                })
+            }
+        }
+    }
+}
+
+/// Responses of the new type can be deserialized as the old type: the new type can only be stricter about nulls.
+fn is_compatible_output_type(old: &FieldType, new: &FieldType) -> bool {
+    match (old, new) {
+        (FieldType::Named(old), FieldType::Named(new)) => old == new,
+        (FieldType::Vector(old), FieldType::Vector(new)) => is_compatible_output_type(old, new),
+        (FieldType::Optional(old), FieldType::Optional(new)) => is_compatible_output_type(old, new),
+        (FieldType::Optional(old), new) => is_compatible_output_type(old, new),
+        _ => false,
+    }
+}
+
+/// Values of the old type are still accepted by the new type: the new type can only be more lenient about nulls.
+fn is_compatible_input_type(old: &FieldType, new: &FieldType) -> bool {
+    is_compatible_output_type(new, old)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphql_parser;
+
+    fn schema(sdl: &str) -> Schema {
+        Schema::from(graphql_parser::parse_schema(sdl).expect("schema parse"))
+    }
+
+    const OLD: &str = r#"
+        type Query {
+            dogs(first: Int): [Dog!]!
+            owner: Person
+        }
+
+        type Dog {
+            name: String!
+            age: Int!
+            breed: Breed
+            tricks: [String!]
+            owner(id: ID!): Person
+        }
+
+        type Person {
+            name: String
+        }
+
+        enum Breed {
+            LABRADOR
+            POODLE
+        }
+
+        union Pet = Dog | Person
+
+        input DogFilter {
+            name: String
+            age: Int
+        }
+    "#;
+
+    #[test]
+    fn identical_schemas_have_no_breaking_changes() {
+        assert_eq!(diff_schemas(&schema(OLD), &schema(OLD)), vec![]);
+    }
+
+    #[test]
+    fn additive_changes_are_not_breaking() {
+        let new = r#"
+            type Query {
+                dogs(first: Int, after: String, sort: String = "name"): [Dog!]!
+                owner: Person
+                cats: [String]
+            }
+
+            type Dog {
+                name: String!
+                age: Int!
+                breed: Breed!
+                tricks: [String!]
+                owner(id: ID): Person
+            }
+
+            type Person {
+                name: String
+                email: String
+            }
+
+            enum Breed {
+                LABRADOR
+                POODLE
+                BEAGLE
+            }
+
+            union Pet = Dog | Person | Query
+
+            input DogFilter {
+                name: String
+                age: Int
+                breed: Breed
+            }
+
+            scalar Date
+        "#;
+
+        assert_eq!(diff_schemas(&schema(OLD), &schema(new)), vec![]);
+    }
+
+    #[test]
+    fn breaking_changes_are_reported() {
+        let new = r#"
+            type Query {
+                dogs(first: Int, owner: ID!): [Dog!]!
+            }
+
+            type Dog {
+                name: String
+                age: Float!
+                breed: Breed
+                tricks: [String]
+                owner: Person
+            }
+
+            interface Person {
+                name: String
+            }
+
+            enum Breed {
+                LABRADOR
+            }
+
+            union Pet = Dog
+
+            input DogFilter {
+                name: String!
+            }
+        "#;
+
+        let changes = diff_schemas(&schema(OLD), &schema(new));
+
+        assert_eq!(
+            changes,
+            vec![
+                BreakingChange::TypeKindChanged {
+                    type_name: "Person".to_string(),
+                    old_kind: "object",
+                    new_kind: "interface",
+                },
+                BreakingChange::FieldMadeNullable {
+                    type_name: "Dog".to_string(),
+                    field_name: "name".to_string(),
+                },
+                BreakingChange::FieldTypeChanged {
+                    type_name: "Dog".to_string(),
+                    field_name: "age".to_string(),
+                    old_type: "Int!".to_string(),
+                    new_type: "Float!".to_string(),
+                },
+                BreakingChange::FieldMadeNullable {
+                    type_name: "Dog".to_string(),
+                    field_name: "tricks".to_string(),
+                },
+                BreakingChange::ArgumentRemoved {
+                    type_name: "Dog".to_string(),
+                    field_name: "owner".to_string(),
+                    argument_name: "id".to_string(),
+                },
+                BreakingChange::ArgumentAddedWithoutDefault {
+                    type_name: "Query".to_string(),
+                    field_name: "dogs".to_string(),
+                    argument_name: "owner".to_string(),
+                },
+                BreakingChange::FieldRemoved {
+                    type_name: "Query".to_string(),
+                    field_name: "owner".to_string(),
+                },
+                BreakingChange::FieldRemoved {
+                    type_name: "DogFilter".to_string(),
+                    field_name: "age".to_string(),
+                },
+                BreakingChange::FieldTypeChanged {
+                    type_name: "DogFilter".to_string(),
+                    field_name: "name".to_string(),
+                    old_type: "String".to_string(),
+                    new_type: "String!".to_string(),
+                },
+                BreakingChange::EnumValueRemoved {
+                    enum_name: "Breed".to_string(),
+                    value: "POODLE".to_string(),
+                },
+                BreakingChange::UnionMemberRemoved {
+                    union_name: "Pet".to_string(),
+                    member: "Person".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn breaking_changes_display() {
+        let change = BreakingChange::ArgumentAddedWithoutDefault {
+            type_name: "Query".to_string(),
+            field_name: "dogs".to_string(),
+            argument_name: "owner".to_string(),
+        };
+        assert_eq!(
+            change.to_string(),
+            "Required argument owner without a default value was added to field Query.dogs"
+        );
+    }
+
+    #[test]
+    fn schema_diff_lists_additions_and_removals() {
+        let new = r#"
+            type Query {
+                dogs(first: Int): [Dog!]!
+                cats: [String]
+            }
+
+            type Dog {
+                name: String
+                age: Int!
+                breed: Breed!
+                tricks: [String!]
+                owner(id: ID!): Person
+            }
+
+            interface Person {
+                name: String
+            }
+
+            enum Breed {
+                LABRADOR
+                POODLE
+            }
+
+            union Pet = Dog | Person
+
+            input DogFilter {
+                name: String
+                age: Int
+                breed: Breed
+            }
+
+            scalar Date
+        "#;
+        let field_change =
+            |type_name: &str, field_name: &str, old: Option<&str>, new: Option<&str>, breaking| {
+                FieldChange {
+                    type_name: type_name.to_string(),
+                    field_name: field_name.to_string(),
+                    old_type: old.map(String::from),
+                    new_type: new.map(String::from),
+                    breaking,
+                }
+            };
+
+        let diff = schema(OLD).diff(&schema(new));
+
+        assert_eq!(
+            diff,
+            SchemaDiff {
+                added_types: vec!["Date".to_string(), "Person".to_string()],
+                removed_types: vec!["Person".to_string()],
+                changed_fields: vec![
+                    field_change("Dog", "name", Some("String!"), Some("String"), true),
+                    field_change("Dog", "breed", Some("Breed"), Some("Breed!"), false),
+                    field_change("Query", "owner", Some("Person"), None, true),
+                ],
+                added_fields: vec![
+                    field_change("Query", "cats", None, Some("[String]"), false),
+                    field_change("DogFilter", "breed", None, Some("Breed"), false),
+                ],
+            }
+        );
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn stricter_output_fields_are_not_breaking() {
+        let new = OLD.replace("breed: Breed\n", "breed: Breed!\n");
+        let diff = schema(OLD).diff(&schema(&new));
+
+        assert_eq!(diff.changed_fields.len(), 1);
+        assert!(!diff.is_breaking());
+        assert!(!schema(OLD).diff(&schema(OLD)).is_breaking());
+    }
+}
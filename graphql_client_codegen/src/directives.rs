@@ -0,0 +1,532 @@
+use graphql_parser::schema;
+
+/// Metadata collected from the directives applied to a type or a field in the schema.
+///
+/// `@deprecated` is handled separately, see [DeprecationStatus](::deprecation::DeprecationStatus).
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Directives {
+    /// The names passed to `@tag(name: "...")` (Apollo Federation contracts).
+    pub tags: Vec<String>,
+    /// The caching hints from `@cacheControl(maxAge: ..., scope: ...)`.
+    pub cache_control: Option<CacheControl>,
+    /// The field set from `@requires(fields: "...")` (Apollo Federation).
+    pub requires: Option<String>,
+    /// The field set from `@provides(fields: "...")` (Apollo Federation).
+    pub provides: Option<String>,
+    /// The field sets from the `@key(fields: "...")` directives of an entity type (Apollo Federation). There can be several.
+    pub keys: Vec<String>,
+    /// Whether `@external` is present: the field is resolved by another subgraph (Apollo Federation).
+    pub external: bool,
+    /// Whether `@shareable` is present: the type or field can be resolved by several subgraphs (Apollo Federation 2).
+    pub shareable: bool,
+    /// Whether `@inaccessible` is present: the type or field is hidden from the public schema (Apollo Federation).
+    pub inaccessible: bool,
+    /// The levels from `@semanticNonNull(levels: [...])`, sorted: `0` is the field itself, `1` the items of a list, and so on. The default is `[0]`. Empty when the directive is absent.
+    pub semantic_non_null: Vec<u32>,
+    /// The constraints from `@validate(regex: "...", min: ..., max: ...)` on an input object field.
+    pub validate: Option<Validate>,
+}
+
+/// The constraints of a `@validate` directive, checked by the generated `validate()` method of input objects.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Validate {
+    /// A regular expression that the whole value must match.
+    pub regex: Option<String>,
+    /// The minimum value of a number, or the minimum length of a string or list.
+    pub min: Option<i64>,
+    /// The maximum value of a number, or the maximum length of a string or list.
+    pub max: Option<i64>,
+}
+
+/// A directive defined in the schema, with `directive @name(...) on ...` or in the `directives` of an introspection result.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirectiveDefinition {
+    /// The name of the directive, without `@`.
+    pub name: String,
+    /// The description of the directive.
+    pub description: Option<String>,
+    /// The names of the arguments of the directive.
+    pub arguments: Vec<String>,
+    /// Where the directive can be used, as in the spec: `FIELD`, `FRAGMENT_SPREAD`, `OBJECT`...
+    pub locations: Vec<String>,
+}
+
+/// The directives that every schema defines implicitly, and `@stream`, which the generated code supports.
+pub(crate) const BUILTIN_DIRECTIVES: &[&str] = &[
+    "skip",
+    "include",
+    "deprecated",
+    "specifiedBy",
+    ::constants::STREAM_DIRECTIVE,
+];
+
+impl DirectiveDefinition {
+    pub(crate) fn from_graphql_parser(definition: schema::DirectiveDefinition) -> Self {
+        DirectiveDefinition {
+            name: definition.name,
+            description: definition.description,
+            arguments: definition
+                .arguments
+                .into_iter()
+                .map(|argument| argument.name)
+                .collect(),
+            locations: definition
+                .locations
+                .iter()
+                .map(|location| location.as_str().to_string())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn from_introspection(
+        directive: ::introspection_response::RustIntrospectionQuerySchemaDirectives,
+    ) -> Option<Self> {
+        Some(DirectiveDefinition {
+            name: directive.name?,
+            description: directive.description,
+            arguments: directive
+                .args
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|argument| argument.and_then(|argument| argument.input_value.name))
+                .collect(),
+            locations: directive
+                .locations
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|location| {
+                    location.and_then(|location| {
+                        ::serde_json::to_value(location)
+                            .ok()
+                            .and_then(|value| value.as_str().map(|s| s.to_string()))
+                    })
+                })
+                .collect(),
+        })
+    }
+}
+
+/// A caching hint, as given by the `@cacheControl` directive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CacheControl {
+    /// How long (in seconds) the value can be cached.
+    pub max_age: Option<u32>,
+    /// Who can cache the value.
+    pub scope: Option<CacheScope>,
+}
+
+/// The `scope` argument of `@cacheControl`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CacheScope {
+    /// The value can be cached by shared caches, such as CDNs.
+    Public,
+    /// The value is specific to a user and must not be cached by shared caches.
+    Private,
+}
+
+/// A specification imported in the schema with `@link(url: "...", import: [...])` (Apollo Federation 2).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LinkedSpec {
+    /// The URL of the specification, e.g. `https://specs.apollo.dev/federation/v2.0`.
+    pub url: String,
+    /// The names imported from the specification, as used in the schema: directives keep their `@` prefix, and renamed imports (`{ name: "@key", as: "@primaryKey" }`) appear under their new name.
+    pub imports: Vec<String>,
+}
+
+impl LinkedSpec {
+    /// The `@link` directives among `directives`, usually those of the schema definition.
+    pub(crate) fn from_graphql_parser(directives: &[schema::Directive]) -> Vec<LinkedSpec> {
+        directives
+            .iter()
+            .filter(|directive| directive.name == "link")
+            .filter_map(|directive| {
+                let url = string_argument(directive, "url")?;
+                let imports = match argument(directive, "import") {
+                    Some(schema::Value::List(imports)) => imports
+                        .iter()
+                        .filter_map(|import| match import {
+                            schema::Value::String(name) => Some(name.clone()),
+                            schema::Value::Object(import) => {
+                                match import.get("as").or_else(|| import.get("name")) {
+                                    Some(schema::Value::String(name)) => Some(name.clone()),
+                                    _ => None,
+                                }
+                            }
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                Some(LinkedSpec { url, imports })
+            })
+            .collect()
+    }
+
+    /// Whether the directive called `name` (without `@`) is imported from this specification.
+    pub fn imports_directive(&self, name: &str) -> bool {
+        self.imports
+            .iter()
+            .any(|import| import.starts_with('@') && &import[1..] == name)
+    }
+}
+
+impl Directives {
+    pub(crate) fn from_graphql_parser(directives: &[schema::Directive]) -> Directives {
+        let mut result = Directives::default();
+
+        for directive in directives {
+            if directive.name == "tag" {
+                if let Some(name) = string_argument(directive, "name") {
+                    result.tags.push(name);
+                }
+            } else if directive.name == "cacheControl" {
+                result.cache_control = Some(CacheControl {
+                    max_age: int_argument(directive, "maxAge").and_then(|max_age| {
+                        if max_age >= 0 {
+                            Some(max_age as u32)
+                        } else {
+                            None
+                        }
+                    }),
+                    scope: match enum_argument(directive, "scope") {
+                        Some("PUBLIC") => Some(CacheScope::Public),
+                        Some("PRIVATE") => Some(CacheScope::Private),
+                        _ => None,
+                    },
+                });
+            } else if directive.name == "key" {
+                if let Some(fields) = string_argument(directive, "fields") {
+                    result.keys.push(fields);
+                }
+            } else if directive.name == "external" {
+                result.external = true;
+            } else if directive.name == "shareable" {
+                result.shareable = true;
+            } else if directive.name == "inaccessible" {
+                result.inaccessible = true;
+            } else if directive.name == "requires" {
+                result.requires = string_argument(directive, "fields");
+            } else if directive.name == "provides" {
+                result.provides = string_argument(directive, "fields");
+            } else if directive.name == "semanticNonNull" {
+                result.semantic_non_null = match argument(directive, "levels") {
+                    Some(schema::Value::List(levels)) => levels
+                        .iter()
+                        .filter_map(|level| match level {
+                            schema::Value::Int(n) => n.as_i64().map(|n| n as u32),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => vec![0],
+                };
+                result.semantic_non_null.sort();
+                result.semantic_non_null.dedup();
+            } else if directive.name == "validate" {
+                result.validate = Some(Validate {
+                    regex: string_argument(directive, "regex"),
+                    min: int_argument(directive, "min"),
+                    max: int_argument(directive, "max"),
+                });
+            }
+        }
+
+        result
+    }
+
+    /// Whether a `@tag` directive with this name is present.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// The `maxAge` of the `@cacheControl` directive, if any.
+    pub fn cache_max_age(&self) -> Option<u32> {
+        self.cache_control
+            .and_then(|cache_control| cache_control.max_age)
+    }
+}
+
+fn argument<'a>(
+    directive: &'a schema::Directive,
+    argument_name: &str,
+) -> Option<&'a schema::Value> {
+    directive
+        .arguments
+        .iter()
+        .find(|(name, _)| name == argument_name)
+        .map(|(_, value)| value)
+}
+
+fn string_argument(directive: &schema::Directive, argument_name: &str) -> Option<String> {
+    argument(directive, argument_name).and_then(|value| match value {
+        schema::Value::String(s) => Some(s.clone()),
+        _ => None,
+    })
+}
+
+fn int_argument(directive: &schema::Directive, argument_name: &str) -> Option<i64> {
+    argument(directive, argument_name).and_then(|value| match value {
+        schema::Value::Int(n) => n.as_i64(),
+        _ => None,
+    })
+}
+
+fn enum_argument<'a>(directive: &'a schema::Directive, argument_name: &str) -> Option<&'a str> {
+    argument(directive, argument_name).and_then(|value| match value {
+        schema::Value::Enum(variant) => Some(variant.as_str()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphql_parser::Pos;
+
+    fn tag(name: &str) -> schema::Directive {
+        schema::Directive {
+            position: Pos::default(),
+            name: "tag".to_string(),
+            arguments: vec![("name".to_string(), schema::Value::String(name.to_string()))],
+        }
+    }
+
+    #[test]
+    fn tags_are_collected() {
+        let deprecated = schema::Directive {
+            position: Pos::default(),
+            name: "deprecated".to_string(),
+            arguments: vec![],
+        };
+        let directives =
+            Directives::from_graphql_parser(&[tag("public"), deprecated, tag("internal")]);
+
+        assert_eq!(
+            directives.tags,
+            vec!["public".to_string(), "internal".to_string()]
+        );
+        assert!(directives.has_tag("internal"));
+        assert!(!directives.has_tag("private"));
+    }
+
+    #[test]
+    fn cache_control_is_parsed() {
+        let document = ::graphql_parser::parse_schema(
+            "
+            type A @cacheControl(maxAge: 300, scope: PRIVATE) { a: String }
+            type B @cacheControl(scope: PUBLIC) { b: String }
+            ",
+        )
+        .unwrap();
+        let directives: Vec<Directives> = document
+            .definitions
+            .iter()
+            .map(|definition| match definition {
+                schema::Definition::TypeDefinition(schema::TypeDefinition::Object(obj)) => {
+                    Directives::from_graphql_parser(&obj.directives)
+                }
+                _ => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(
+            directives[0].cache_control,
+            Some(CacheControl {
+                max_age: Some(300),
+                scope: Some(CacheScope::Private),
+            })
+        );
+        assert_eq!(directives[0].cache_max_age(), Some(300));
+        assert_eq!(directives[1].cache_max_age(), None);
+        assert_eq!(
+            directives[1].cache_control.and_then(|c| c.scope),
+            Some(CacheScope::Public)
+        );
+    }
+
+    #[test]
+    fn requires_and_provides_are_parsed() {
+        let document = ::graphql_parser::parse_schema(
+            r#"
+            type Review {
+                author: User @provides(fields: "username")
+                shippingEstimate: Int @requires(fields: "price weight")
+                body: String
+            }
+            "#,
+        )
+        .unwrap();
+        let directives: Vec<Directives> = match &document.definitions[0] {
+            schema::Definition::TypeDefinition(schema::TypeDefinition::Object(obj)) => obj
+                .fields
+                .iter()
+                .map(|field| Directives::from_graphql_parser(&field.directives))
+                .collect(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(directives[0].provides, Some("username".to_string()));
+        assert_eq!(directives[0].requires, None);
+        assert_eq!(directives[1].requires, Some("price weight".to_string()));
+        assert_eq!(directives[2], Directives::default());
+    }
+
+    #[test]
+    fn external_and_shareable_are_parsed() {
+        let document = ::graphql_parser::parse_schema(
+            "type Product @shareable { upc: String! @external name: String }",
+        )
+        .unwrap();
+        let (type_directives, field_directives) = match &document.definitions[0] {
+            schema::Definition::TypeDefinition(schema::TypeDefinition::Object(obj)) => (
+                Directives::from_graphql_parser(&obj.directives),
+                obj.fields
+                    .iter()
+                    .map(|field| Directives::from_graphql_parser(&field.directives))
+                    .collect::<Vec<_>>(),
+            ),
+            _ => unreachable!(),
+        };
+
+        assert!(type_directives.shareable);
+        assert!(!type_directives.external);
+        assert!(field_directives[0].external);
+        assert!(!field_directives[1].external);
+    }
+
+    #[test]
+    fn inaccessible_is_parsed() {
+        let document = ::graphql_parser::parse_schema(
+            "type Product { upc: String! name: String @inaccessible }",
+        )
+        .unwrap();
+        let (type_directives, field_directives) = match &document.definitions[0] {
+            schema::Definition::TypeDefinition(schema::TypeDefinition::Object(obj)) => (
+                Directives::from_graphql_parser(&obj.directives),
+                obj.fields
+                    .iter()
+                    .map(|field| Directives::from_graphql_parser(&field.directives))
+                    .collect::<Vec<_>>(),
+            ),
+            _ => unreachable!(),
+        };
+
+        assert!(!type_directives.inaccessible);
+        assert!(!field_directives[0].inaccessible);
+        assert!(field_directives[1].inaccessible);
+    }
+
+    #[test]
+    fn semantic_non_null_levels_are_parsed() {
+        let document = ::graphql_parser::parse_schema(
+            "type User { name: String @semanticNonNull friends: [User] @semanticNonNull(levels: [1, 0]) age: Int }",
+        )
+        .unwrap();
+        let directives: Vec<Directives> = match &document.definitions[0] {
+            schema::Definition::TypeDefinition(schema::TypeDefinition::Object(obj)) => obj
+                .fields
+                .iter()
+                .map(|field| Directives::from_graphql_parser(&field.directives))
+                .collect(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(directives[0].semantic_non_null, vec![0]);
+        assert_eq!(directives[1].semantic_non_null, vec![0, 1]);
+        assert_eq!(directives[2].semantic_non_null, Vec::<u32>::new());
+    }
+
+    #[test]
+    fn validate_constraints_are_parsed() {
+        let document = ::graphql_parser::parse_schema(
+            r#"input NewUser { name: String! @validate(regex: "[a-z]+", max: 20) age: Int @validate(min: 0) }"#,
+        )
+        .unwrap();
+        let directives: Vec<Directives> = match &document.definitions[0] {
+            schema::Definition::TypeDefinition(schema::TypeDefinition::InputObject(input)) => input
+                .fields
+                .iter()
+                .map(|field| Directives::from_graphql_parser(&field.directives))
+                .collect(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            directives[0].validate,
+            Some(Validate {
+                regex: Some("[a-z]+".to_string()),
+                min: None,
+                max: Some(20),
+            })
+        );
+        assert_eq!(
+            directives[1].validate,
+            Some(Validate {
+                regex: None,
+                min: Some(0),
+                max: None,
+            })
+        );
+    }
+
+    #[test]
+    fn links_are_parsed() {
+        let document = ::graphql_parser::parse_schema(
+            r#"
+            schema
+                @link(url: "https://specs.apollo.dev/link/v1.0")
+                @link(url: "https://specs.apollo.dev/federation/v2.0", import: ["@key", { name: "@shareable", as: "@shared" }, "FieldSet"])
+            {
+                query: Query
+            }
+            "#,
+        )
+        .unwrap();
+        let links = match &document.definitions[0] {
+            schema::Definition::SchemaDefinition(definition) => {
+                LinkedSpec::from_graphql_parser(&definition.directives)
+            }
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            links,
+            vec![
+                LinkedSpec {
+                    url: "https://specs.apollo.dev/link/v1.0".to_string(),
+                    imports: vec![],
+                },
+                LinkedSpec {
+                    url: "https://specs.apollo.dev/federation/v2.0".to_string(),
+                    imports: vec![
+                        "@key".to_string(),
+                        "@shared".to_string(),
+                        "FieldSet".to_string(),
+                    ],
+                },
+            ]
+        );
+        assert!(links[1].imports_directive("key"));
+        assert!(links[1].imports_directive("shared"));
+        assert!(!links[1].imports_directive("shareable"));
+        assert!(!links[1].imports_directive("FieldSet"));
+    }
+
+    #[test]
+    fn keys_are_collected() {
+        let document = ::graphql_parser::parse_schema(
+            r#"type Product @key(fields: "upc") @key(fields: "sku brand") { upc: String! }"#,
+        )
+        .unwrap();
+        let directives = match &document.definitions[0] {
+            schema::Definition::TypeDefinition(schema::TypeDefinition::Object(obj)) => {
+                Directives::from_graphql_parser(&obj.directives)
+            }
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            directives.keys,
+            vec!["upc".to_string(), "sku brand".to_string()]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use directives::Directives;
 use proc_macro2::{Ident, Span, TokenStream};
 use std::cell::Cell;
 
@@ -14,6 +15,7 @@ pub struct GqlEnum {
     pub description: Option<String>,
     pub name: String,
     pub variants: Vec<EnumVariant>,
+    pub directives: Directives,
     pub is_required: Cell<bool>,
 }
 
@@ -30,7 +32,10 @@ impl GqlEnum {
                 quote!(#description #name)
             }).collect();
         let variant_names = &variant_names;
-        let name_ident = Ident::new(&format!("{}{}", ENUMS_PREFIX, self.name), Span::call_site());
+        let name_ident = Ident::new(
+            &query_context.prefixed_type_name(&format!("{}{}", ENUMS_PREFIX, self.name)),
+            Span::call_site(),
+        );
         let constructors: Vec<_> = self
             .variants
             .iter()
@@ -44,6 +49,22 @@ impl GqlEnum {
 
         let name = name_ident.clone();
 
+        let display_impl = if query_context.response_derives_display() {
+            let core = query_context.core_path();
+            Some(quote! {
+                impl #core::fmt::Display for #name {
+                    fn fmt(&self, f: &mut #core::fmt::Formatter) -> #core::fmt::Result {
+                        f.write_str(match *self {
+                            #(#constructors => #variant_str,)*
+                            #name::Other(ref s) => &s,
+                        })
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
         quote! {
             #derives
             pub enum #name {
@@ -70,6 +91,49 @@ impl GqlEnum {
                     }
                 }
             }
+
+            #display_impl
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use query::QueryContext;
+
+    fn episode() -> GqlEnum {
+        GqlEnum {
+            description: None,
+            name: "Episode".to_string(),
+            variants: vec![EnumVariant {
+                description: None,
+                name: "JEDI".to_string(),
+            }],
+            directives: Directives::default(),
+            is_required: false.into(),
         }
     }
+
+    #[test]
+    fn display_is_only_implemented_when_requested() {
+        let mut context = QueryContext::new_empty();
+        assert!(!episode().to_rust(&context).to_string().contains("Display"));
+
+        context.ingest_additional_derives("Display").unwrap();
+
+        let generated = episode().to_rust(&context).to_string();
+        let expected_impl = quote! {
+            impl ::std::fmt::Display for Episode {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    f.write_str(match *self {
+                        Episode::JEDI => "JEDI",
+                        Episode::Other(ref s) => &s,
+                    })
+                }
+            }
+        };
+        assert!(generated.contains(&expected_impl.to_string()));
+        assert!(!generated.contains("derive ( Display"));
+    }
 }
@@ -4,6 +4,7 @@ use introspection_response;
 use proc_macro2::{Ident, Span, TokenStream};
 use query::QueryContext;
 use schema::DEFAULT_SCALARS;
+use std::fmt;
 
 #[derive(Clone, Debug, PartialEq, Hash)]
 pub enum FieldType {
@@ -15,6 +16,61 @@ pub enum FieldType {
 impl FieldType {
     /// Takes a field type with its name
     pub(crate) fn to_rust(&self, context: &QueryContext, prefix: &str) -> TokenStream {
+        self.to_rust_semantic(context, prefix, &[])
+    }
+
+    /// Like `to_rust`, but nullable types at the `@semanticNonNull` list levels (see [Directives::semantic_non_null](::directives::Directives::semantic_non_null)) become `SemanticResult`s instead of `Option`s.
+    pub(crate) fn to_rust_semantic(
+        &self,
+        context: &QueryContext,
+        prefix: &str,
+        semantic_non_null: &[u32],
+    ) -> TokenStream {
+        self.to_rust_at_level(context, prefix, semantic_non_null, 0, false)
+    }
+
+    /// Like `to_rust_semantic`, for a list field with `@stream`: the list is a `StreamedField` instead of a `Vec`. The nested lists, if any, are still `Vec`s.
+    pub(crate) fn to_rust_streamed(
+        &self,
+        context: &QueryContext,
+        prefix: &str,
+        semantic_non_null: &[u32],
+    ) -> TokenStream {
+        self.to_rust_at_level(context, prefix, semantic_non_null, 0, true)
+    }
+
+    /// Whether `to_rust_semantic` wraps one of the levels in a `SemanticResult`, which happens for the nullable levels in `semantic_non_null`.
+    pub(crate) fn has_semantic_result(&self, semantic_non_null: &[u32]) -> bool {
+        fn at_level(field_type: &FieldType, semantic_non_null: &[u32], level: u32) -> bool {
+            match field_type {
+                FieldType::Named(_) => false,
+                FieldType::Optional(inner) => {
+                    semantic_non_null.contains(&level) || at_level(inner, semantic_non_null, level)
+                }
+                FieldType::Vector(inner) => at_level(inner, semantic_non_null, level + 1),
+            }
+        }
+
+        at_level(self, semantic_non_null, 0)
+    }
+
+    /// Whether this is a list, possibly nullable.
+    pub(crate) fn is_list(&self) -> bool {
+        match self {
+            FieldType::Vector(_) => true,
+            FieldType::Optional(inner) => inner.is_list(),
+            FieldType::Named(_) => false,
+        }
+    }
+
+    fn to_rust_at_level(
+        &self,
+        context: &QueryContext,
+        prefix: &str,
+        semantic_non_null: &[u32],
+        level: u32,
+        stream: bool,
+    ) -> TokenStream {
         let prefix: String = if prefix.is_empty() {
             self.inner_name_string()
         } else {
@@ -38,7 +94,7 @@ impl FieldType {
                     .map(|enm| enm.is_required.set(true))
                     .is_some()
                 {
-                    format!("{}{}", ENUMS_PREFIX, name)
+                    context.prefixed_type_name(&format!("{}{}", ENUMS_PREFIX, name))
                 } else {
                     if prefix.is_empty() {
                         panic!("Empty prefix for {:?}", self);
@@ -50,13 +106,45 @@ impl FieldType {
                 quote!(#full_name)
             }
             FieldType::Optional(inner) => {
-                let inner = inner.to_rust(context, &prefix);
-                quote!( Option<#inner>)
+                let inner =
+                    inner.to_rust_at_level(context, &prefix, semantic_non_null, level, stream);
+                if semantic_non_null.contains(&level) {
+                    quote!( ::graphql_client::SemanticResult<#inner>)
+                } else {
+                    quote!( Option<#inner>)
+                }
             }
             FieldType::Vector(inner) => {
-                let inner = inner.to_rust(context, &prefix);
-                quote!( Vec<#inner>)
+                let inner =
+                    inner.to_rust_at_level(context, &prefix, semantic_non_null, level + 1, false);
+                if stream {
+                    quote!( ::graphql_client::StreamedField<#inner>)
+                } else {
+                    quote!( Vec<#inner>)
+                }
+            }
+        }
+    }
+
+    /// The type of a variable: like `to_rust`, except that nullable lists are `CoercedList`s, which can also hold a single item.
+    pub(crate) fn to_rust_variable(&self, context: &QueryContext) -> TokenStream {
+        match self.optional_list_item() {
+            Some(item) => {
+                let item = item.to_rust(context, "");
+                quote!( Option<::graphql_client::CoercedList<#item>>)
             }
+            None => self.to_rust(context, ""),
+        }
+    }
+
+    /// The item type, if this is a nullable list (see `to_rust_variable`).
+    pub(crate) fn optional_list_item(&self) -> Option<&FieldType> {
+        match self {
+            FieldType::Optional(inner) => match inner.as_ref() {
+                FieldType::Vector(item) => Some(item),
+                _ => None,
+            },
+            _ => None,
         }
     }
 
@@ -77,6 +165,21 @@ impl FieldType {
     }
 }
 
+/// Formats the type in GraphQL notation, e.g. `[String!]`.
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldType::Named(name) => write!(f, "{}!", name),
+            FieldType::Vector(inner) => write!(f, "[{}]!", inner),
+            FieldType::Optional(inner) => match inner.as_ref() {
+                FieldType::Named(name) => write!(f, "{}", name),
+                FieldType::Vector(inner) => write!(f, "[{}]", inner),
+                FieldType::Optional(_) => inner.fmt(f),
+            },
+        }
+    }
+}
+
 impl ::std::convert::From<graphql_parser::schema::Type> for FieldType {
     fn from(schema_type: graphql_parser::schema::Type) -> FieldType {
         from_schema_type_inner(schema_type, false)
@@ -195,4 +298,30 @@ mod tests {
         };
         assert_eq!(FieldType::from(ty), FieldType::Named("Cat".to_string()));
     }
+
+    #[test]
+    fn field_type_display_uses_graphql_notation() {
+        let ty = FieldType::Optional(Box::new(FieldType::Vector(Box::new(FieldType::Named(
+            "Cat".to_string(),
+        )))));
+        assert_eq!(ty.to_string(), "[Cat!]");
+
+        let ty = FieldType::Vector(Box::new(FieldType::Optional(Box::new(FieldType::Named(
+            "Cat".to_string(),
+        )))));
+        assert_eq!(ty.to_string(), "[Cat]!");
+    }
+
+    #[test]
+    fn semantic_results_are_only_on_nullable_levels() {
+        // [String]!
+        let ty = FieldType::Vector(Box::new(FieldType::Optional(Box::new(FieldType::Named(
+            "String".to_string(),
+        )))));
+
+        assert!(!ty.has_semantic_result(&[]));
+        assert!(!ty.has_semantic_result(&[0]));
+        assert!(ty.has_semantic_result(&[1]));
+        assert!(ty.has_semantic_result(&[0, 1]));
+    }
 }
@@ -20,20 +20,23 @@ impl GqlFragment {
     /// Generate all the Rust code required by the fragment's selection.
     pub(crate) fn to_rust(&self, context: &QueryContext) -> Result<TokenStream, ::failure::Error> {
         let derives = context.response_derives();
-        let name_ident = Ident::new(&self.name, Span::call_site());
+        let name = context.prefixed_type_name(&self.name);
+        let name_ident = Ident::new(&name, Span::call_site());
         let opt_object = context.schema.objects.get(&self.on);
-        let (field_impls, fields) = if let Some(object) = opt_object {
+        let (field_impls, fields, field_names) = if let Some(object) = opt_object {
             let field_impls =
-                object.field_impls_for_selection(context, &self.selection, &self.name)?;
+                object.field_impls_for_selection(context, &self.selection, &name)?;
             let fields =
-                object.response_fields_for_selection(context, &self.selection, &self.name)?;
-            (field_impls, fields)
+                object.response_fields_for_selection(context, &self.selection, &name)?;
+            let field_names = object.field_names_impl(context, &self.selection, &name_ident);
+            (field_impls, fields, field_names)
         } else if let Some(iface) = context.schema.interfaces.get(&self.on) {
             let field_impls =
-                iface.field_impls_for_selection(context, &self.selection, &self.name)?;
+                iface.field_impls_for_selection(context, &self.selection, &name)?;
             let fields =
-                iface.response_fields_for_selection(context, &self.selection, &self.name)?;
-            (field_impls, fields)
+                iface.response_fields_for_selection(context, &self.selection, &name)?;
+            let field_names = iface.field_names_impl(context, &self.selection, &name_ident);
+            (field_impls, fields, field_names)
         } else {
             panic!(
                 "fragment '{}' cannot operate on unknown type '{}'",
@@ -41,12 +44,24 @@ impl GqlFragment {
             );
         };
 
+        let (deserialize_from, deserialization) = ::shared::fragment_spreads_deserialization(
+            context,
+            &name_ident,
+            &fields,
+            &self.selection,
+        )?;
+
         Ok(quote!{
             #derives
+            #deserialize_from
             pub struct #name_ident {
                 #(#fields,)*
             }
 
+            #deserialization
+
+            #field_names
+
             #(#field_impls)*
         })
     }
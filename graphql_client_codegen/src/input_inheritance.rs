@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+/// Blanks out the `implements` clauses of the input object definitions in `sdl` (`input Child implements Parent`, an extension to the schema definition language enabled with the `input_inheritance` option). Returns the SDL without them, and the parents of each input object that had one.
+///
+/// graphql_parser does not accept `implements` on input objects, so the clauses have to be removed before parsing. They are replaced with spaces, so the positions in parse errors still point to the original SDL.
+pub(crate) fn strip_input_parents(sdl: &str) -> (String, BTreeMap<String, Vec<String>>) {
+    let tokens = tokenize(sdl);
+    let mut stripped = sdl.to_string();
+    let mut parents = BTreeMap::new();
+
+    let mut idx = 0;
+    while idx + 2 < tokens.len() {
+        let is_clause = tokens[idx].text == "input"
+            && tokens[idx + 1].is_name
+            && tokens[idx + 2].text == "implements";
+        if !is_clause {
+            idx += 1;
+            continue;
+        }
+
+        let start = tokens[idx + 2].start;
+        let mut end = tokens[idx + 2].end;
+        let mut names = Vec::new();
+        let mut next = idx + 3;
+        while let Some(token) = tokens.get(next) {
+            if token.is_name {
+                names.push(token.text.to_string());
+            } else if token.text != "&" {
+                break;
+            }
+            end = token.end;
+            next += 1;
+        }
+
+        if !names.is_empty() {
+            let blank: String = sdl.as_bytes()[start..end]
+                .iter()
+                .map(|&byte| if byte == b'\n' { '\n' } else { ' ' })
+                .collect();
+            stripped.replace_range(start..end, &blank);
+            parents.insert(tokens[idx + 1].text.to_string(), names);
+        }
+        idx = next;
+    }
+
+    (stripped, parents)
+}
+
+struct Token<'a> {
+    text: &'a str,
+    is_name: bool,
+    start: usize,
+    end: usize,
+}
+
+/// Splits the SDL into names and punctuation, leaving out whitespace, commas, comments and strings.
+fn tokenize(sdl: &str) -> Vec<Token<'_>> {
+    let bytes = sdl.as_bytes();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        let byte = bytes[idx];
+        if byte == b'#' {
+            while idx < bytes.len() && bytes[idx] != b'\n' {
+                idx += 1;
+            }
+        } else if bytes[idx..].starts_with(b"\"\"\"") {
+            idx += 3;
+            while idx < bytes.len() && !bytes[idx..].starts_with(b"\"\"\"") {
+                idx += if bytes[idx..].starts_with(b"\\\"\"\"") {
+                    4
+                } else {
+                    1
+                };
+            }
+            idx += 3;
+        } else if byte == b'"' {
+            idx += 1;
+            while idx < bytes.len() && bytes[idx] != b'"' && bytes[idx] != b'\n' {
+                idx += if bytes[idx] == b'\\' { 2 } else { 1 };
+            }
+            idx += 1;
+        } else if byte == b'_' || byte.is_ascii_alphabetic() {
+            let start = idx;
+            while idx < bytes.len() && (bytes[idx] == b'_' || bytes[idx].is_ascii_alphanumeric()) {
+                idx += 1;
+            }
+            tokens.push(Token {
+                text: &sdl[start..idx],
+                is_name: true,
+                start,
+                end: idx,
+            });
+        } else if byte.is_ascii_whitespace() || byte == b',' || !byte.is_ascii() {
+            idx += 1;
+        } else {
+            tokens.push(Token {
+                text: &sdl[idx..idx + 1],
+                is_name: false,
+                start: idx,
+                end: idx + 1,
+            });
+            idx += 1;
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn implements_clauses_are_blanked_out() {
+        let sdl = r#"
+            "input Described implements Nothing"
+            input Child implements Parent & Other @deprecated {
+              # input Commented implements Nothing
+              extra: String
+            }
+            input Parent { name: String }
+            type Query implements Node { id: ID! }
+        "#;
+        let (stripped, parents) = strip_input_parents(sdl);
+
+        assert_eq!(stripped.len(), sdl.len());
+        assert!(stripped.contains("input Child                           @deprecated {"));
+        assert!(stripped.contains("type Query implements Node"));
+        assert_eq!(
+            parents.into_iter().collect::<Vec<_>>(),
+            vec![(
+                "Child".to_string(),
+                vec!["Parent".to_string(), "Other".to_string()]
+            )]
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use deprecation::DeprecationStatus;
+use directives::{Directives, Validate};
 use failure;
+use field_type::FieldType;
 use graphql_parser;
 use heck::SnakeCase;
 use introspection_response;
 use objects::GqlObjectField;
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use query::QueryContext;
 use schema::Schema;
 use std::cell::Cell;
@@ -16,6 +18,9 @@ pub struct GqlInput {
     pub description: Option<String>,
     pub name: String,
     pub fields: HashMap<String, GqlObjectField>,
+    pub directives: Directives,
+    /// The input objects whose fields this one inherits, with the `input_inheritance` extension (`input Child implements Parent`).
+    pub parents: Vec<String>,
     pub is_required: Cell<bool>,
 }
 
@@ -27,7 +32,10 @@ impl GqlInput {
         self.is_required.set(true);
         self.fields.values().for_each(|field| {
             schema.require(&field.type_.inner_name_string());
-        })
+        });
+        self.parents
+            .iter()
+            .for_each(|parent| schema.require(parent));
     }
 
     pub(crate) fn to_rust(&self, context: &QueryContext) -> Result<TokenStream, failure::Error> {
@@ -44,13 +52,128 @@ impl GqlInput {
 
             quote!(#rename pub #name: #ty)
         });
+        // The inherited fields are serialized next to the fields of the input object itself.
+        let parents = self.parents.iter().map(|parent| {
+            context.schema.require(parent);
+            let ty = Ident::new(parent, Span::call_site());
+            let name = Ident::new(&parent.to_snake_case(), Span::call_site());
+            quote!(#[serde(flatten)] pub #name: #ty)
+        });
         let variables_derives = context.variables_derives();
+        let validate_impl = self.validate_impl(context)?;
 
         Ok(quote! {
             #variables_derives
             pub struct #name {
+                #(#parents,)*
                 #(#fields,)*
             }
+
+            #validate_impl
+        })
+    }
+
+    /// The `validate()` method checking the `@validate` constraints of the fields, if there are any.
+    fn validate_impl(&self, context: &QueryContext) -> Result<Option<TokenStream>, failure::Error> {
+        let mut fields: Vec<(&GqlObjectField, &Validate)> = self
+            .fields
+            .values()
+            .filter_map(|field| field.directives.validate.as_ref().map(|v| (field, v)))
+            .collect();
+        if fields.is_empty() {
+            return Ok(None);
+        }
+        fields.sort_unstable_by(|a, b| a.0.name.cmp(&b.0.name));
+
+        let checks: Result<Vec<TokenStream>, failure::Error> = fields
+            .into_iter()
+            .map(|(field, validate)| self.field_checks(context, field, validate))
+            .collect();
+        let checks = checks?;
+        let name = Ident::new(&self.name, Span::call_site());
+
+        Ok(Some(quote! {
+            impl #name {
+                /// Checks the `@validate` constraints of the schema on the fields.
+                pub fn validate(&self) -> Result<(), ::graphql_client::validation::ValidationError> {
+                    #(#checks)*
+                    Ok(())
+                }
+            }
+        }))
+    }
+
+    fn field_checks(
+        &self,
+        context: &QueryContext,
+        field: &GqlObjectField,
+        validate: &Validate,
+    ) -> Result<TokenStream, failure::Error> {
+        let path = format!("{}.{}", self.name, field.name);
+        let (is_optional, ty) = match &field.type_ {
+            FieldType::Optional(inner) => (true, &**inner),
+            ty => (false, ty),
+        };
+        let (measurable, is_string) = match ty {
+            FieldType::Vector(_) => (true, false),
+            FieldType::Named(name) => match name.as_str() {
+                "Int" | "Float" => (true, false),
+                "String" | "ID" => (true, true),
+                _ => (false, false),
+            },
+            FieldType::Optional(_) => unreachable!("nested optional type"),
+        };
+        if !measurable {
+            Err(format_err!(
+                "@validate is not supported on {}, of type {}",
+                path,
+                ty.inner_name_string()
+            ))?
+        }
+
+        let mut checks = Vec::new();
+        if let Some(min) = validate.min {
+            let min = Literal::f64_unsuffixed(min as f64);
+            checks.push(quote!(::graphql_client::validation::check_min(value, #min, #path)?;));
+        }
+        if let Some(max) = validate.max {
+            let max = Literal::f64_unsuffixed(max as f64);
+            checks.push(quote!(::graphql_client::validation::check_max(value, #max, #path)?;));
+        }
+        if let Some(regex) = &validate.regex {
+            if !is_string {
+                Err(format_err!(
+                    "The @validate regex of {} only applies to String and ID fields",
+                    path
+                ))?
+            }
+            if !context.regex_validation {
+                Err(format_err!(
+                    "The @validate regex of {} requires the validation feature of graphql_client",
+                    path
+                ))?
+            }
+            checks.push(quote! {
+                {
+                    static PATTERN: ::graphql_client::validation::Pattern =
+                        ::graphql_client::validation::Pattern::new(#regex);
+                    ::graphql_client::validation::check_regex(value, &PATTERN, #path)?;
+                }
+            });
+        }
+
+        let field_ident = Ident::new(&field.name.to_snake_case(), Span::call_site());
+        Ok(if is_optional {
+            quote! {
+                if let Some(value) = &self.#field_ident {
+                    #(#checks)*
+                }
+            }
+        } else {
+            quote! {
+                let value = &self.#field_ident;
+                #(#checks)*
+            }
         })
     }
 }
@@ -70,9 +193,13 @@ impl ::std::convert::From<graphql_parser::schema::InputObjectType> for GqlInput
                         name: field.name,
                         type_: field.value_type.into(),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::from_graphql_parser(&field.directives),
+                        arguments: Vec::new(),
                     };
                     (name, field)
                 }).collect(),
+            directives: Directives::from_graphql_parser(&schema_input.directives),
+            parents: Vec::new(),
             is_required: false.into(),
         }
     }
@@ -99,9 +226,13 @@ impl ::std::convert::From<introspection_response::FullType> for GqlInput {
                             .expect("type on input object field")
                             .into(),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     };
                     (name, field)
                 }).collect(),
+            directives: Directives::default(),
+            parents: Vec::new(),
             is_required: false.into(),
         }
     }
@@ -126,6 +257,8 @@ mod tests {
                         name: "pawsCount".to_string(),
                         type_: FieldType::Named(float_type()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                 ),
                 (
@@ -135,6 +268,8 @@ mod tests {
                         name: "offsprings".to_string(),
                         type_: FieldType::Vector(Box::new(FieldType::Named("Cat".to_string()))),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                 ),
                 (
@@ -146,10 +281,14 @@ mod tests {
                             "CatRequirements".to_string(),
                         ))),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                 ),
             ].into_iter()
             .collect(),
+            directives: Directives::default(),
+            parents: Vec::new(),
             is_required: false.into(),
         };
 
@@ -182,4 +321,60 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn unsupported_validate_constraints_are_rejected() {
+        let field = |name: &str, type_name: &str, validate: Validate| GqlObjectField {
+            description: None,
+            name: name.to_string(),
+            type_: FieldType::Named(type_name.to_string()),
+            deprecation: DeprecationStatus::Current,
+            directives: Directives {
+                validate: Some(validate),
+                ..Directives::default()
+            },
+            arguments: Vec::new(),
+        };
+        let input = |field: GqlObjectField| GqlInput {
+            description: None,
+            name: "NewUser".to_string(),
+            fields: vec![(field.name.clone(), field)].into_iter().collect(),
+            directives: Directives::default(),
+            parents: Vec::new(),
+            is_required: false.into(),
+        };
+        let regex = Validate {
+            regex: Some("[a-z]+".to_string()),
+            ..Validate::default()
+        };
+        let mut context = QueryContext::new_empty();
+
+        let err = input(field("admin", "Boolean", Validate::default()))
+            .to_rust(&context)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "@validate is not supported on NewUser.admin, of type Boolean"
+        );
+
+        let err = input(field("age", "Int", regex.clone()))
+            .to_rust(&context)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The @validate regex of NewUser.age only applies to String and ID fields"
+        );
+
+        let name = input(field("name", "String", regex));
+        assert_eq!(
+            name.to_rust(&context).unwrap_err().to_string(),
+            "The @validate regex of NewUser.name requires the validation feature of graphql_client"
+        );
+        context.regex_validation = true;
+        assert!(name
+            .to_rust(&context)
+            .unwrap()
+            .to_string()
+            .contains("pub fn validate ( & self )"));
+    }
 }
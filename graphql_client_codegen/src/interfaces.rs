@@ -1,3 +1,4 @@
+use directives::Directives;
 use failure;
 use objects::GqlObjectField;
 use proc_macro2::{Ident, Span, TokenStream};
@@ -18,6 +19,7 @@ pub struct GqlInterface {
     pub name: String,
     /// The interface's fields. Analogous to object fields.
     pub fields: Vec<GqlObjectField>,
+    pub directives: Directives,
     pub is_required: Cell<bool>,
 }
 
@@ -45,6 +47,7 @@ impl GqlInterface {
             name: name.into_owned(),
             implemented_by: HashSet::new(),
             fields: vec![],
+            directives: Directives::default(),
             is_required: false.into(),
         }
     }
@@ -64,6 +67,21 @@ impl GqlInterface {
         )
     }
 
+    pub(crate) fn field_names_impl(
+        &self,
+        context: &QueryContext,
+        selection: &Selection,
+        struct_name: &Ident,
+    ) -> TokenStream {
+        field_names_impl(
+            struct_name,
+            &self.name,
+            &self.fields,
+            context,
+            &self.object_selection(selection),
+        )
+    }
+
     /// The code for the interface's corresponding struct's fields.
     pub(crate) fn response_fields_for_selection(
         &self,
@@ -106,10 +124,11 @@ impl GqlInterface {
                 .collect(),
         );
 
-        let object_fields =
+        let mut object_fields =
             self.response_fields_for_selection(query_context, &selection, prefix)?;
 
         let object_children = self.field_impls_for_selection(query_context, &selection, prefix)?;
+        let field_names = self.field_names_impl(query_context, selection, &name);
         let (mut union_variants, union_children, used_variants) =
             union_variants(&union_selection, query_context, prefix)?;
 
@@ -124,19 +143,20 @@ impl GqlInterface {
         );
 
         let attached_enum_name = Ident::new(&format!("{}On", name), Span::call_site());
-        let (attached_enum, last_object_field) = if !union_variants.is_empty() {
-            let attached_enum = quote! {
+        let attached_enum = if !union_variants.is_empty() {
+            object_fields.push(quote!(#[serde(flatten)] pub on: #attached_enum_name));
+            quote! {
                 #derives
                 #[serde(tag = "__typename")]
                 pub enum #attached_enum_name {
                     #(#union_variants,)*
                 }
-            };
-            let last_object_field = quote!(#[serde(flatten)] pub on: #attached_enum_name,);
-            (attached_enum, last_object_field)
+            }
         } else {
-            (quote!(), quote!())
+            quote!()
         };
+        let (deserialize_from, deserialization) =
+            fragment_spreads_deserialization(query_context, &name, &object_fields, selection)?;
 
         Ok(quote! {
 
@@ -147,10 +167,14 @@ impl GqlInterface {
             #attached_enum
 
             #derives
+            #deserialize_from
             pub struct #name {
                 #(#object_fields,)*
-                #last_object_field
             }
+
+            #deserialization
+
+            #field_names
         })
     }
 }
@@ -143,7 +143,7 @@ pub struct FullType {
 #[serde(rename_all = "camelCase")]
 pub struct FullTypeFieldsArgs {
     #[serde(flatten)]
-    input_value: InputValue,
+    pub input_value: InputValue,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -203,6 +203,8 @@ pub struct InputValue {
     #[serde(rename = "type")]
     pub type_: Option<InputValueType>,
     pub default_value: Option<String>,
+    pub is_deprecated: Option<bool>,
+    pub deprecation_reason: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -304,7 +306,7 @@ pub struct RustIntrospectionQuerySchemaTypes {
 #[serde(rename_all = "camelCase")]
 pub struct RustIntrospectionQuerySchemaDirectivesArgs {
     #[serde(flatten)]
-    input_value: InputValue,
+    pub input_value: InputValue,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -323,7 +325,7 @@ pub struct RustIntrospectionQuerySchema {
     pub mutation_type: Option<RustIntrospectionQuerySchemaMutationType>,
     pub subscription_type: Option<RustIntrospectionQuerySchemaSubscriptionType>,
     pub types: Option<Vec<Option<RustIntrospectionQuerySchemaTypes>>>,
-    directives: Option<Vec<Option<RustIntrospectionQuerySchemaDirectives>>>,
+    pub directives: Option<Vec<Option<RustIntrospectionQuerySchemaDirectives>>>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -18,7 +18,10 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde_json;
+extern crate sha2;
 extern crate syn;
+#[cfg(feature = "http_schema")]
+extern crate ureq;
 #[macro_use]
 extern crate quote;
 
@@ -30,22 +33,36 @@ pub mod attributes;
 mod codegen;
 /// Deprecation-related code
 pub mod deprecation;
+/// Detection of breaking changes between two versions of a schema.
+pub mod diff;
+/// Metadata from schema directives.
+pub mod directives;
 mod introspection_response;
+/// Warnings about query documents that do not prevent code generation.
+pub mod lint;
 mod query;
+/// Fetching and caching schemas from `schema_path` URLs.
+pub mod remote_schema;
 /// Contains the [Schema] type and its implementation.
 pub mod schema;
+/// Checking queries against a schema in tests, without generating code.
+pub mod test_utils;
 
 mod constants;
 mod enums;
 mod field_type;
 mod fragments;
+mod input_inheritance;
 mod inputs;
 mod interfaces;
+mod mock;
 mod objects;
 mod operations;
 mod scalars;
+mod scaffolding;
 mod selection;
 mod shared;
+mod snapshot;
 mod unions;
 mod variables;
 
@@ -60,6 +77,8 @@ type CacheMap<T> =
 
 lazy_static! {
     static ref SCHEMA_CACHE: CacheMap<schema::Schema> = CacheMap::default();
+    /// Schemas read with `input_inheritance` are parsed differently, so they are cached separately.
+    static ref INPUT_INHERITANCE_SCHEMA_CACHE: CacheMap<schema::Schema> = CacheMap::default();
     static ref QUERY_CACHE: CacheMap<(String, graphql_parser::query::Document)> =
         CacheMap::default();
 }
@@ -70,10 +89,94 @@ pub struct GraphQLClientDeriveOptions {
     pub struct_name: String,
     /// Comma-separated list of additional traits we want to derive.
     pub additional_derives: Option<String>,
+    /// Comma-separated list of additional traits to derive on the variables structs only.
+    pub variables_derives: Option<String>,
+    /// Comma-separated list of additional traits to derive on the response structs and enums only.
+    pub response_derives: Option<String>,
+    /// Comma-separated list of traits that replaces all the derives of the response structs, including the default `Deserialize`, for custom `Deserialize` implementations. It must include `Serialize` or `Deserialize`, for the serde attributes on the fields. Enums keep their generated serde implementations.
+    pub response_derives_exclusive: Option<String>,
     /// The deprecation strategy to adopt.
     pub deprecation_strategy: Option<deprecation::DeprecationStrategy>,
     /// target struct visibility.
     pub module_visibility: Visibility,
+    /// Comma-separated list of `ScalarName = path::to::Type` mappings for custom scalars.
+    pub scalars: Option<String>,
+    /// Comma-separated list of `ScalarName = path::to::SerdeAs` pairs: response fields of these scalars are (de)serialized with `serde_with::serde_as`.
+    pub scalars_with: Option<String>,
+    /// Accept `input Child implements Parent` in SDL schemas, a non-standard extension: the struct of the child input object has a flattened field for each parent.
+    pub input_inheritance: bool,
+    /// Comma-separated list of `ScalarName = path::to::Type` pairs: these scalars are generated as newtypes around the given types, implementing `Deref` and `DerefMut` to them.
+    pub newtype_scalars: Option<String>,
+    /// Path to a type to generate a `From<ResponseData>` impl skeleton for. Only used with the `scaffolding` feature.
+    pub into: Option<String>,
+    /// Derive `PartialOrd` and `Ord` on the response types. Only applied when `PartialEq` and `Eq` are derived too and every selected field is known to implement `Ord`.
+    pub ord_derives: bool,
+    /// Warn about fields nested deeper than this in the operation, see [lint::check_depth].
+    pub max_query_depth: Option<usize>,
+    /// Where the warnings of the generated code point to. rustc does not report warnings at spans from macro output, so the derive sets it to the name of the deriving struct.
+    pub warning_span: Span,
+    /// Warn about the non-deprecated fields of the selected types that the operation leaves out, see [lint::check_missing_fields].
+    pub warn_missing_fields: bool,
+    /// Generate a test comparing the generated code to an `insta` snapshot in a `snapshots` directory next to the query.
+    pub snapshot_test: bool,
+    /// Prepended to the names of all the generated response types except `ResponseData`, so they do not collide with the types of other queries when imported together.
+    pub response_type_prefix: Option<String>,
+    /// `Arc` or `Rc`: the `ResponseData` of the `GraphQLQuery` impl is wrapped in this smart pointer, so responses can be shared without cloning.
+    pub response_wrapper: Option<String>,
+    /// Comma-separated list of deprecated fields (`Type.field`, or `field` on any type) that are always generated, as with the `allow` deprecation strategy.
+    pub allow_deprecated_fields: Option<String>,
+    /// Apollo Federation subgraph mode: `@external` fields are left out of the response types, and `@shareable` types are documented as such.
+    pub federation_mode: bool,
+    /// Generate a `mock_response()` function returning a `ResponseData` filled with placeholder values, for tests. Custom scalars must implement `Default` and `Serialize`.
+    pub generate_mock: bool,
+    /// Generate the response types. Without them, the module only has the query constants and the `Variables` type (with the input objects, enums and scalars it needs), and `GraphQLQuery::ResponseData` is a `serde_json::Value`. The selections are then not checked against the schema, and `into`, `generate_mock`, `deserializer_impl` and `strict_response` are rejected.
+    pub generate_structs: bool,
+    /// The case of the serialized variable names: `camelCase`, `snake_case`, `PascalCase` or `SCREAMING_SNAKE_CASE`. By default, variables keep their name from the query.
+    pub rename_variables: Option<String>,
+    /// Reject responses with fields that were not selected (`#[serde(deny_unknown_fields)]`), to catch schema drift early. Structs with fragment spreads are exempt, because serde does not support this with `flatten`, and so are structs leaving out some selected fields (denied deprecated, `@inaccessible` or `@external` fields), which the server still sends.
+    pub strict_response: bool,
+    /// Skip the validation of the query document against the spec (unique operation names, a single root field in subscriptions, defined directives), for valid queries that the checks reject. The generated module emits a warning when this is set. Fields missing from the schema are still errors, since their types cannot be generated.
+    pub skip_query_validation: bool,
+    /// Implement `serde::Deserializer` for `ResponseData`, so that a response can drive the deserialization of another type. It requires `Serialize` in the response derives.
+    pub deserializer_impl: bool,
+    /// Generate code for `no_std` crates: `String`, `Vec` and `Box` are imported from `alloc` (through `graphql_client`, which must be used with `default-features = false`) and the other paths point to `core`.
+    pub no_std: bool,
+}
+
+/// The options used when no attributes are given. `struct_name` is left empty.
+impl Default for GraphQLClientDeriveOptions {
+    fn default() -> Self {
+        GraphQLClientDeriveOptions {
+            struct_name: String::new(),
+            additional_derives: None,
+            variables_derives: None,
+            response_derives: None,
+            response_derives_exclusive: None,
+            deprecation_strategy: None,
+            module_visibility: Visibility::Inherited,
+            scalars: None,
+            scalars_with: None,
+            input_inheritance: false,
+            newtype_scalars: None,
+            into: None,
+            ord_derives: false,
+            max_query_depth: None,
+            warning_span: Span::call_site(),
+            warn_missing_fields: false,
+            snapshot_test: false,
+            response_type_prefix: None,
+            response_wrapper: None,
+            allow_deprecated_fields: None,
+            federation_mode: false,
+            generate_mock: false,
+            generate_structs: true,
+            rename_variables: None,
+            strict_response: false,
+            skip_query_validation: false,
+            deserializer_impl: false,
+            no_std: false,
+        }
+    }
 }
 
 /// Generates the code for a Rust module given a query, a schema and options.
@@ -84,16 +187,15 @@ pub fn generate_module_token_stream(
 ) -> Result<TokenStream, failure::Error> {
     let options = options.unwrap();
 
-    let module_visibility = options.module_visibility;
-    let response_derives = options.additional_derives;
-
-    // The user can determine what to do about deprecations.
-    let deprecation_strategy = options.deprecation_strategy.unwrap_or_default();
+    let snapshot_dir = query_path
+        .parent()
+        .map(|dir| dir.join("snapshots"))
+        .unwrap_or_else(|| "snapshots".into());
 
     // We need to qualify the query with the path to the crate it is part of
     let (query_string, query) = {
         let mut lock = QUERY_CACHE.lock().expect("query cache is poisoned");
-        match lock.entry(query_path) {
+        match lock.entry(query_path.clone()) {
             ::std::collections::hash_map::Entry::Occupied(o) => o.get().clone(),
             ::std::collections::hash_map::Entry::Vacant(v) => {
                 let query_string = read_file(v.key())?;
@@ -103,6 +205,81 @@ pub fn generate_module_token_stream(
         }
     };
 
+    // Check the schema cache.
+    let schema = {
+        let cache: &CacheMap<schema::Schema> = if options.input_inheritance {
+            &INPUT_INHERITANCE_SCHEMA_CACHE
+        } else {
+            &SCHEMA_CACHE
+        };
+        let mut lock = cache.lock().expect("schema cache is poisoned");
+        match lock.entry(schema_path) {
+            ::std::collections::hash_map::Entry::Occupied(o) => o.get().clone(),
+            ::std::collections::hash_map::Entry::Vacant(v) => {
+                let schema = read_schema_file(v.key(), options.input_inheritance)?;
+                v.insert(schema).clone()
+            }
+        }
+    };
+
+    module_token_stream(query_string, query, schema, &options, &snapshot_dir)
+        .map_err(|err| selection::SelectionError::in_file(err, &query_path))
+}
+
+/// Generates the source of the Rust module for the query, as the derive would, given a query document and a schema that are already in memory. This is meant for build scripts and test helpers.
+///
+/// The source is not formatted. Options that refer to the query file, like `snapshot_test`, assume a query in the current directory.
+///
+/// ```
+/// # extern crate graphql_client_codegen;
+/// # extern crate syn;
+/// use graphql_client_codegen::{codegen_to_string, schema::Schema, GraphQLClientDeriveOptions};
+///
+/// let schema = Schema::from_sdl("schema { query: Query } type Query { version: String }").unwrap();
+/// let options = GraphQLClientDeriveOptions {
+///     struct_name: "VersionQuery".to_string(),
+///     ..Default::default()
+/// };
+///
+/// let generated = codegen_to_string(&schema, "query VersionQuery { version }", &options).unwrap();
+/// assert!(generated.contains("mod version_query"));
+/// ```
+pub fn codegen_to_string(
+    schema: &schema::Schema,
+    query: &str,
+    options: &GraphQLClientDeriveOptions,
+) -> Result<String, failure::Error> {
+    let document = graphql_parser::parse_query(query)?;
+
+    Ok(module_token_stream(
+        query.to_string(),
+        document,
+        schema.clone(),
+        options,
+        ::std::path::Path::new("snapshots"),
+    )?.to_string())
+}
+
+/// A warning in the generated code. There is no way to emit warnings from a proc macro on stable, so this is the use of a deprecated constant with the warning as the note. The use has the `span` from the user's code, otherwise rustc would not report it.
+fn deprecation_warning(name: &str, note: &str, span: Span) -> TokenStream {
+    let definition = Ident::new(name, Span::call_site());
+    let usage = Ident::new(name, span);
+    quote! {
+        #[deprecated(note = #note)]
+        const #definition: () = ();
+        const _: () = #usage;
+    }
+}
+
+fn module_token_stream(
+    query_string: String,
+    query: graphql_parser::query::Document,
+    schema: schema::Schema,
+    options: &GraphQLClientDeriveOptions,
+    snapshot_dir: &::std::path::Path,
+) -> Result<TokenStream, failure::Error> {
+    let module_visibility = &options.module_visibility;
+
     // Determine which operation we are generating code for. This will be used in operationName.
 
     let operation = if let Some(op) = codegen::select_operation(&query, &options.struct_name) {
@@ -111,38 +288,77 @@ pub fn generate_module_token_stream(
         panic!("Query document defines no operation.")
     };
 
+    if !options.skip_query_validation
+        && codegen::all_operations(&query)
+            .iter()
+            .filter(|op| op.name == operation.name)
+            .count()
+            > 1
+    {
+        Err(format_err!(
+            "Query document defines more than one operation named `{}`.",
+            operation.name
+        ))?
+    }
+
+    if !options.skip_query_validation {
+        if let Some(warning) = lint::check_directives(&query, &schema).into_iter().next() {
+            Err(format_err!("{}", warning))?
+        }
+    }
+
     let operation_name_literal = &operation.name;
+    let operation_type = match operation.operation_type {
+        operations::OperationType::Query => quote!(Query),
+        operations::OperationType::Mutation => quote!(Mutation),
+        operations::OperationType::Subscription => quote!(Subscription),
+    };
+    let document_hash = document_hash(&query_string);
 
-    // Check the schema cache.
-    let schema = {
-        let mut lock = SCHEMA_CACHE.lock().expect("schema cache is poisoned");
-        match lock.entry(schema_path) {
-            ::std::collections::hash_map::Entry::Occupied(o) => o.get().clone(),
-            ::std::collections::hash_map::Entry::Vacant(v) => {
-                let schema_string = read_file(v.key())?;
-                let schema = {
-                    let extension = v
-                        .key()
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .unwrap_or("INVALID");
-
-                    match extension {
-                        "graphql" | "gql" => {
-                            let s = graphql_parser::schema::parse_schema(&schema_string)?;
-                            schema::Schema::from(s)
-                        }
-                        "json" => {
-                            let parsed: introspection_response::IntrospectionResponse = ::serde_json::from_str(&schema_string)?;
-                            schema::Schema::from(parsed)
-                        }
-                        extension => panic!("Unsupported extension for the GraphQL schema: {} (only .json and .graphql are supported)", extension)
-                    }
-                };
+    let depth_warnings = options
+        .max_query_depth
+        .map(|max_depth| lint::check_depth(&query, max_depth))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|warning| warning.operation.as_ref() == Some(&operation.name))
+        .enumerate()
+        .map(|(idx, warning)| {
+            deprecation_warning(
+                &format!("QUERY_DEPTH_WARNING_{}", idx),
+                &warning.to_string(),
+                options.warning_span,
+            )
+        });
 
-                v.insert(schema).clone()
-            }
-        }
+    let missing_field_warnings = if options.warn_missing_fields {
+        lint::check_missing_fields(&query, &schema)
+    } else {
+        Vec::new()
+    };
+    let missing_field_warnings = missing_field_warnings
+        .into_iter()
+        .filter(|warning| warning.operation.as_ref() == Some(&operation.name))
+        .enumerate()
+        .map(|(idx, warning)| {
+            deprecation_warning(
+                &format!("MISSING_FIELD_WARNING_{}", idx),
+                &warning.to_string(),
+                options.warning_span,
+            )
+        });
+
+    let skip_validation_warning = if options.skip_query_validation {
+        let note = format!(
+            "skip_query_validation is set on {}: the query is not validated",
+            options.struct_name
+        );
+        Some(deprecation_warning(
+            "SKIP_QUERY_VALIDATION_WARNING",
+            &note,
+            options.warning_span,
+        ))
+    } else {
+        None
     };
 
     let module_name = Ident::new(
@@ -150,13 +366,39 @@ pub fn generate_module_token_stream(
         Span::call_site(),
     );
     let struct_name = Ident::new(options.struct_name.as_str(), Span::call_site());
-    let schema_output = codegen::response_for_query(
-        schema,
-        query,
-        &operation,
-        response_derives,
-        deprecation_strategy,
-    )?;
+    let schema_output = codegen::response_for_query(schema, query, &operation, options)?;
+
+    let snapshot_test = if options.snapshot_test {
+        Some(snapshot::snapshot_test(
+            &module_name.to_string(),
+            snapshot_dir,
+            &schema_output,
+        ))
+    } else {
+        None
+    };
+
+    let response_data_type = if options.generate_structs {
+        quote!(#module_name::ResponseData)
+    } else {
+        quote!(::graphql_client::serde_json::Value)
+    };
+    let response_data_type = match options.response_wrapper.as_deref() {
+        None => response_data_type,
+        Some("Arc") => quote!(::graphql_client::alloc::sync::Arc<#response_data_type>),
+        Some("Rc") => quote!(::graphql_client::alloc::rc::Rc<#response_data_type>),
+        Some(other) => Err(format_err!(
+            "response_wrapper must be `Arc` or `Rc`, not `{}`",
+            other
+        ))?,
+    };
+
+    let into_impl = match options.into {
+        Some(ref target_path) if cfg!(feature = "scaffolding") => Some(
+            scaffolding::response_data_into_impl(&module_name, target_path)?,
+        ),
+        _ => None,
+    };
 
     let result = quote!(
         #module_visibility mod #module_name {
@@ -167,14 +409,24 @@ pub fn generate_module_token_stream(
             use serde;
 
             pub const QUERY: &'static str = #query_string;
+            /// The UTF-8 bytes of `QUERY`, for hashing or binary protocols.
+            pub const QUERY_BYTES: &'static [u8] = QUERY.as_bytes();
+            /// The hex-encoded SHA-256 hash of `QUERY`, for use as a persisted query key.
+            pub const DOCUMENT_HASH: &'static str = #document_hash;
+
+            #(#depth_warnings)*
+            #(#missing_field_warnings)*
+            #skip_validation_warning
             pub const OPERATION_NAME: &'static str = #operation_name_literal;
 
             #schema_output
+
+            #snapshot_test
         }
 
         impl ::graphql_client::GraphQLQuery for #struct_name {
             type Variables = #module_name::Variables;
-            type ResponseData = #module_name::ResponseData;
+            type ResponseData = #response_data_type;
 
             fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
                 ::graphql_client::QueryBody {
@@ -184,12 +436,81 @@ pub fn generate_module_token_stream(
                 }
 
             }
+
+            fn document_hash() -> &'static str {
+                #module_name::DOCUMENT_HASH
+            }
+
+            fn operation_type() -> ::graphql_client::OperationType {
+                ::graphql_client::OperationType::#operation_type
+            }
         }
+
+        #into_impl
     );
 
     Ok(result)
 }
 
+/// Writes generated code to `<output_directory>/<module name>.rs`, so it can be checked in and reviewed, and returns the path to the file. The derive then includes it with `include!`.
+///
+/// The file is not rewritten when it is up to date, so it does not trigger rebuilds. Its contents are not formatted.
+pub fn write_generated_module(
+    tokens: &TokenStream,
+    struct_name: &str,
+    output_directory: &::std::path::Path,
+) -> Result<::std::path::PathBuf, failure::Error> {
+    let path = output_directory.join(format!("{}.rs", struct_name.to_snake_case()));
+    // Unlike macro output, included code is linted. The module comes first.
+    let contents = format!(
+        "// Generated by graphql_client from the {} query. Do not edit.\n#[allow(unused_imports, clippy::all)]\n{}\n",
+        struct_name, tokens
+    );
+
+    if read_file(&path).ok().as_ref() != Some(&contents) {
+        ::std::fs::create_dir_all(output_directory)?;
+        ::std::fs::write(&path, contents)?;
+    }
+
+    Ok(path)
+}
+
+/// The hex-encoded SHA-256 hash of a query document, as expected by automatic persisted queries.
+fn document_hash(query_string: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!("{:x}", Sha256::digest(query_string.as_bytes()))
+}
+
+/// Reads and parses a schema from a `.graphql`/`.gql` (SDL) or `.json` (introspection response) file.
+pub fn read_schema(path: &::std::path::Path) -> Result<schema::Schema, failure::Error> {
+    read_schema_file(path, false)
+}
+
+/// [read_schema], where SDL schemas can use `input Child implements Parent` if `input_inheritance` is set.
+fn read_schema_file(
+    path: &::std::path::Path,
+    input_inheritance: bool,
+) -> Result<schema::Schema, failure::Error> {
+    let schema_string = read_file(path)?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("INVALID");
+
+    match extension {
+        "graphql" | "gql" if input_inheritance => Ok(
+            schema::Schema::from_sdl_with_input_inheritance(&schema_string)?,
+        ),
+        "graphql" | "gql" => Ok(schema::Schema::from_sdl(&schema_string)?),
+        "json" => Ok(schema::Schema::from_introspection_json(&schema_string)?),
+        extension => Err(format_err!(
+            "Unsupported extension for the GraphQL schema: {} (only .json and .graphql are supported)",
+            extension
+        )),
+    }
+}
+
 fn read_file(path: &::std::path::Path) -> Result<String, failure::Error> {
     use std::io::prelude::*;
 
@@ -0,0 +1,663 @@
+use constants::TYPENAME_FIELD;
+use deprecation::DeprecationStatus;
+use graphql_parser::query::{
+    Definition, Directive, Document, Field, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet, TypeCondition,
+};
+use graphql_parser::Pos;
+use schema::{Schema, TypeDef};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A problem found in a query document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintWarning {
+    /// The operation the problem was found in, if it is named.
+    pub operation: Option<String>,
+    /// The line of the offending item in the query document (starting from 1).
+    pub line: usize,
+    /// The column of the offending item in the query document (starting from 1).
+    pub column: usize,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(operation) = &self.operation {
+            write!(f, "{}: ", operation)?;
+        }
+        write!(
+            f,
+            "{} (line {}, column {})",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+/// Warns about each field nested more than `max_depth` levels deep in the operations of the document. Fragment spreads count as if the fragment was inlined.
+///
+/// Only the outermost field exceeding the limit is reported, not every field below it.
+///
+/// The derive reports these as deprecation warnings when `max_query_depth` is set. From a build script, you can print them yourself:
+///
+/// ```rust,ignore
+/// for warning in graphql_client_codegen::lint::check_depth(&document, 5) {
+///     println!("cargo:warning={}", warning);
+/// }
+/// ```
+pub fn check_depth(document: &Document, max_depth: usize) -> Vec<LintWarning> {
+    let fragments: BTreeMap<&str, &FragmentDefinition> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect();
+
+    let mut checker = DepthChecker {
+        fragments,
+        max_depth,
+        operation: None,
+        visited_fragments: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    for definition in &document.definitions {
+        if let Definition::Operation(operation) = definition {
+            let (name, selection_set) = match operation {
+                OperationDefinition::Query(q) => (q.name.clone(), &q.selection_set),
+                OperationDefinition::Mutation(m) => (m.name.clone(), &m.selection_set),
+                OperationDefinition::Subscription(s) => (s.name.clone(), &s.selection_set),
+                OperationDefinition::SelectionSet(s) => (None, s),
+            };
+            checker.operation = name;
+            checker.check(selection_set, 1);
+        }
+    }
+
+    checker.warnings
+}
+
+/// Reports each use of a directive that the schema does not define, see [Schema::defines_directive].
+///
+/// The derive fails on these unless `skip_query_validation` is set.
+pub fn check_directives(document: &Document, schema: &Schema) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for definition in &document.definitions {
+        let (operation, directives, selection_set) = match definition {
+            Definition::Operation(OperationDefinition::Query(q)) => {
+                (q.name.clone(), &q.directives[..], &q.selection_set)
+            }
+            Definition::Operation(OperationDefinition::Mutation(m)) => {
+                (m.name.clone(), &m.directives[..], &m.selection_set)
+            }
+            Definition::Operation(OperationDefinition::Subscription(s)) => {
+                (s.name.clone(), &s.directives[..], &s.selection_set)
+            }
+            Definition::Operation(OperationDefinition::SelectionSet(s)) => (None, &[][..], s),
+            Definition::Fragment(f) => (None, &f.directives[..], &f.selection_set),
+        };
+        check_directive_list(schema, &operation, directives, &mut warnings);
+        check_selection_directives(schema, &operation, selection_set, &mut warnings);
+    }
+
+    warnings
+}
+
+fn check_selection_directives(
+    schema: &Schema,
+    operation: &Option<String>,
+    selection_set: &SelectionSet,
+    warnings: &mut Vec<LintWarning>,
+) {
+    for item in &selection_set.items {
+        match item {
+            Selection::Field(field) => {
+                check_directive_list(schema, operation, &field.directives, warnings);
+                check_selection_directives(schema, operation, &field.selection_set, warnings);
+            }
+            Selection::FragmentSpread(spread) => {
+                check_directive_list(schema, operation, &spread.directives, warnings)
+            }
+            Selection::InlineFragment(fragment) => {
+                check_directive_list(schema, operation, &fragment.directives, warnings);
+                check_selection_directives(schema, operation, &fragment.selection_set, warnings);
+            }
+        }
+    }
+}
+
+fn check_directive_list(
+    schema: &Schema,
+    operation: &Option<String>,
+    directives: &[Directive],
+    warnings: &mut Vec<LintWarning>,
+) {
+    for directive in directives {
+        if !schema.defines_directive(&directive.name) {
+            warnings.push(LintWarning {
+                operation: operation.clone(),
+                line: directive.position.line,
+                column: directive.position.column,
+                message: format!(
+                    "directive `@{}` is not defined in the schema",
+                    directive.name
+                ),
+            });
+        }
+    }
+}
+
+/// Reports the fields that are not defined on their parent type, the type conditions and the fragment spreads referencing unknown types or fragments, and the fields that have a selection set when their type is a scalar or an enum, or none when it is an object, an interface or a union.
+///
+/// The derive fails on most of these too, with less precise messages.
+pub fn check_fields(document: &Document, schema: &Schema) -> Vec<LintWarning> {
+    let fragment_names: Vec<&str> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some(fragment.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    let mut checker = FieldChecker {
+        schema,
+        fragment_names,
+        operation: None,
+        warnings: Vec::new(),
+    };
+
+    for definition in &document.definitions {
+        let (operation, root_type, selection_set, position) = match definition {
+            Definition::Operation(OperationDefinition::Query(q)) => (
+                q.name.clone(),
+                &schema.query_type,
+                &q.selection_set,
+                q.position,
+            ),
+            Definition::Operation(OperationDefinition::Mutation(m)) => (
+                m.name.clone(),
+                &schema.mutation_type,
+                &m.selection_set,
+                m.position,
+            ),
+            Definition::Operation(OperationDefinition::Subscription(s)) => (
+                s.name.clone(),
+                &schema.subscription_type,
+                &s.selection_set,
+                s.position,
+            ),
+            Definition::Operation(OperationDefinition::SelectionSet(s)) => {
+                (None, &schema.query_type, s, s.span.0)
+            }
+            Definition::Fragment(f) => {
+                let TypeCondition::On(on) = &f.type_condition;
+                checker.operation = None;
+                checker.check_type_condition(on, f.position, &f.selection_set);
+                continue;
+            }
+        };
+        checker.operation = operation;
+        match root_type {
+            Some(root_type) => checker.check(root_type, selection_set),
+            None => checker.warn(
+                position,
+                "the schema does not define the root type of this operation".to_string(),
+            ),
+        }
+    }
+
+    checker.warnings
+}
+
+struct FieldChecker<'a> {
+    schema: &'a Schema,
+    fragment_names: Vec<&'a str>,
+    operation: Option<String>,
+    warnings: Vec<LintWarning>,
+}
+
+impl<'a> FieldChecker<'a> {
+    fn warn(&mut self, position: Pos, message: String) {
+        self.warnings.push(LintWarning {
+            operation: self.operation.clone(),
+            line: position.line,
+            column: position.column,
+            message,
+        });
+    }
+
+    fn check_type_condition(&mut self, on: &str, position: Pos, selection_set: &SelectionSet) {
+        match self.schema.type_definition(on) {
+            Some(TypeDef::Object(_)) | Some(TypeDef::Interface(_)) | Some(TypeDef::Union(_)) => {
+                self.check(on, selection_set)
+            }
+            _ => self.warn(
+                position,
+                format!(
+                    "type condition `{}` is not an object, interface or union type",
+                    on
+                ),
+            ),
+        }
+    }
+
+    /// `type_name` is an object, interface or union type.
+    fn check(&mut self, type_name: &str, selection_set: &SelectionSet) {
+        let fields = match self.schema.type_definition(type_name) {
+            Some(TypeDef::Object(obj)) => &obj.fields[..],
+            Some(TypeDef::Interface(iface)) => &iface.fields[..],
+            _ => &[][..],
+        };
+
+        for item in &selection_set.items {
+            match item {
+                Selection::Field(field) => {
+                    if field.name == TYPENAME_FIELD {
+                        continue;
+                    }
+                    let field_type = match fields.iter().find(|f| f.name == field.name) {
+                        Some(schema_field) => schema_field.type_.inner_name_string(),
+                        None => {
+                            self.warn(
+                                field.position,
+                                format!(
+                                    "field `{}` is not defined on type `{}`",
+                                    field.name, type_name
+                                ),
+                            );
+                            continue;
+                        }
+                    };
+                    let is_composite = matches!(
+                        self.schema.type_definition(&field_type),
+                        Some(TypeDef::Object(_))
+                            | Some(TypeDef::Interface(_))
+                            | Some(TypeDef::Union(_))
+                    );
+                    if is_composite && field.selection_set.items.is_empty() {
+                        self.warn(
+                            field.position,
+                            format!(
+                                "field `{}` of type `{}` must have a selection of subfields",
+                                field.name, field_type
+                            ),
+                        );
+                    } else if !is_composite && !field.selection_set.items.is_empty() {
+                        self.warn(
+                            field.position,
+                            format!(
+                                "field `{}` of type `{}` cannot have a selection of subfields",
+                                field.name, field_type
+                            ),
+                        );
+                    } else if is_composite {
+                        self.check(&field_type, &field.selection_set);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    if !self.fragment_names.contains(&spread.fragment_name.as_str()) {
+                        self.warn(
+                            spread.position,
+                            format!("fragment `{}` is not defined", spread.fragment_name),
+                        );
+                    }
+                }
+                Selection::InlineFragment(fragment) => match &fragment.type_condition {
+                    Some(TypeCondition::On(on)) => {
+                        self.check_type_condition(on, fragment.position, &fragment.selection_set)
+                    }
+                    None => self.check(type_name, &fragment.selection_set),
+                },
+            }
+        }
+    }
+}
+
+/// Warns about each non-deprecated field of the selected object and interface types that the operations of the document leave out, for example to find the fields that are not covered during a schema migration. Fragments are inlined where they are spread, and the fields selected through them count as selected.
+///
+/// The derive reports these as deprecation warnings when `warn_missing_fields` is set. From a build script, you can print them as `cargo:warning=` lines like the warnings of [check_depth].
+pub fn check_missing_fields(document: &Document, schema: &Schema) -> Vec<LintWarning> {
+    let fragments: BTreeMap<&str, &FragmentDefinition> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect();
+    let mut checker = MissingFieldChecker {
+        schema,
+        fragments,
+        operation: None,
+        visited_fragments: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    for definition in &document.definitions {
+        let (operation, root_type, selection_set, position) = match definition {
+            Definition::Operation(OperationDefinition::Query(q)) => (
+                q.name.clone(),
+                &schema.query_type,
+                &q.selection_set,
+                q.position,
+            ),
+            Definition::Operation(OperationDefinition::Mutation(m)) => (
+                m.name.clone(),
+                &schema.mutation_type,
+                &m.selection_set,
+                m.position,
+            ),
+            Definition::Operation(OperationDefinition::Subscription(s)) => (
+                s.name.clone(),
+                &schema.subscription_type,
+                &s.selection_set,
+                s.position,
+            ),
+            Definition::Operation(OperationDefinition::SelectionSet(s)) => {
+                (None, &schema.query_type, s, s.span.0)
+            }
+            Definition::Fragment(_) => continue,
+        };
+        if let Some(root_type) = root_type {
+            checker.operation = operation;
+            checker.check(root_type, selection_set, position);
+        }
+    }
+
+    checker.warnings
+}
+
+struct MissingFieldChecker<'a> {
+    schema: &'a Schema,
+    fragments: BTreeMap<&'a str, &'a FragmentDefinition>,
+    operation: Option<String>,
+    /// The fragments we are inside of, to avoid looping on fragment cycles.
+    visited_fragments: Vec<&'a str>,
+    warnings: Vec<LintWarning>,
+}
+
+/// The fields selected on a type, with the fragments on other types found next to them.
+#[derive(Default)]
+struct MergedSelection<'a> {
+    fields: Vec<&'a Field>,
+    /// The name of the fragment (for spreads), its type condition, its selection set and where it is used.
+    fragments: Vec<(Option<&'a str>, &'a str, &'a SelectionSet, Pos)>,
+}
+
+impl<'a> MissingFieldChecker<'a> {
+    /// `position` is where the selection set is, for the warnings about the fields it leaves out.
+    fn check(&mut self, type_name: &str, selection_set: &'a SelectionSet, position: Pos) {
+        let mut merged = MergedSelection::default();
+        self.merge(type_name, selection_set, &mut merged);
+
+        let fields = match self.schema.type_definition(type_name) {
+            Some(TypeDef::Object(obj)) => &obj.fields[..],
+            Some(TypeDef::Interface(iface)) => &iface.fields[..],
+            _ => &[][..],
+        };
+        for schema_field in fields {
+            let is_selected = merged
+                .fields
+                .iter()
+                .any(|field| field.name == schema_field.name);
+            let is_deprecated = schema_field.deprecation != DeprecationStatus::Current;
+            if !is_selected && !is_deprecated && schema_field.name != TYPENAME_FIELD {
+                self.warnings.push(LintWarning {
+                    operation: self.operation.clone(),
+                    line: position.line,
+                    column: position.column,
+                    message: format!(
+                        "field `{}` of type `{}` is not selected",
+                        schema_field.name, type_name
+                    ),
+                });
+            }
+        }
+
+        for field in merged.fields {
+            if field.selection_set.items.is_empty() {
+                continue;
+            }
+            if let Some(schema_field) = fields.iter().find(|f| f.name == field.name) {
+                let field_type = schema_field.type_.inner_name_string();
+                self.check(&field_type, &field.selection_set, field.position);
+            }
+        }
+        for (name, on, selection_set, position) in merged.fragments {
+            match name {
+                Some(name) if self.visited_fragments.contains(&name) => {}
+                Some(name) => {
+                    self.visited_fragments.push(name);
+                    self.check(on, selection_set, position);
+                    self.visited_fragments.pop();
+                }
+                None => self.check(on, selection_set, position),
+            }
+        }
+    }
+
+    /// Collects the fields selected on `type_name`, directly or through fragments on the same type.
+    fn merge(
+        &mut self,
+        type_name: &str,
+        selection_set: &'a SelectionSet,
+        merged: &mut MergedSelection<'a>,
+    ) {
+        for item in &selection_set.items {
+            match item {
+                Selection::Field(field) => merged.fields.push(field),
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_str();
+                    if self.visited_fragments.contains(&name) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.fragments.get(name).cloned() {
+                        let TypeCondition::On(on) = &fragment.type_condition;
+                        if on == type_name {
+                            self.visited_fragments.push(name);
+                            self.merge(type_name, &fragment.selection_set, merged);
+                            self.visited_fragments.pop();
+                        } else {
+                            merged.fragments.push((
+                                Some(name),
+                                on,
+                                &fragment.selection_set,
+                                spread.position,
+                            ));
+                        }
+                    }
+                }
+                Selection::InlineFragment(fragment) => match &fragment.type_condition {
+                    Some(TypeCondition::On(on)) if on != type_name => merged.fragments.push((
+                        None,
+                        on,
+                        &fragment.selection_set,
+                        fragment.position,
+                    )),
+                    _ => self.merge(type_name, &fragment.selection_set, merged),
+                },
+            }
+        }
+    }
+}
+
+struct DepthChecker<'a> {
+    fragments: BTreeMap<&'a str, &'a FragmentDefinition>,
+    max_depth: usize,
+    operation: Option<String>,
+    /// The fragments we are inside of, to avoid looping on fragment cycles.
+    visited_fragments: Vec<&'a str>,
+    warnings: Vec<LintWarning>,
+}
+
+impl<'a> DepthChecker<'a> {
+    /// `depth` is the depth of the fields directly in the selection set.
+    fn check(&mut self, selection_set: &'a SelectionSet, depth: usize) {
+        for item in &selection_set.items {
+            match item {
+                Selection::Field(field) => {
+                    if depth > self.max_depth {
+                        self.warnings.push(LintWarning {
+                            operation: self.operation.clone(),
+                            line: field.position.line,
+                            column: field.position.column,
+                            message: format!(
+                                "field `{}` is nested {} levels deep, the maximum is {}",
+                                field.name, depth, self.max_depth
+                            ),
+                        });
+                    } else {
+                        self.check(&field.selection_set, depth + 1);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.fragment_name.as_str();
+                    if self.visited_fragments.contains(&name) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.fragments.get(name).cloned() {
+                        self.visited_fragments.push(name);
+                        self.check(&fragment.selection_set, depth);
+                        self.visited_fragments.pop();
+                    }
+                }
+                Selection::InlineFragment(fragment) => self.check(&fragment.selection_set, depth),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphql_parser;
+
+    fn check(query: &str, max_depth: usize) -> Vec<LintWarning> {
+        check_depth(&graphql_parser::parse_query(query).unwrap(), max_depth)
+    }
+
+    #[test]
+    fn shallow_queries_pass() {
+        assert_eq!(check("query Q { a { b { c } } }", 3), vec![]);
+    }
+
+    #[test]
+    fn the_outermost_field_exceeding_the_limit_is_reported() {
+        let warnings = check(
+            "query Q {\n  a {\n    b {\n      c { d }\n      e\n    }\n  }\n}",
+            2,
+        );
+
+        assert_eq!(
+            warnings,
+            vec![
+                LintWarning {
+                    operation: Some("Q".to_string()),
+                    line: 4,
+                    column: 7,
+                    message: "field `c` is nested 3 levels deep, the maximum is 2".to_string(),
+                },
+                LintWarning {
+                    operation: Some("Q".to_string()),
+                    line: 5,
+                    column: 7,
+                    message: "field `e` is nested 3 levels deep, the maximum is 2".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "Q: field `c` is nested 3 levels deep, the maximum is 2 (line 4, column 7)"
+        );
+    }
+
+    #[test]
+    fn undefined_directives_are_reported() {
+        let schema = Schema::from_sdl(
+            "
+            directive @cached(ttl: Int) on FIELD
+            type Query { a: String b: String }
+            ",
+        )
+        .unwrap();
+        let query = "
+            query Q($skip: Boolean!) {
+              a @cached(ttl: 10) @skip(if: $skip)
+              ... on Query @unknown { b }
+            }
+        ";
+        let warnings = check_directives(&graphql_parser::parse_query(query).unwrap(), &schema);
+
+        assert_eq!(
+            warnings,
+            vec![LintWarning {
+                operation: Some("Q".to_string()),
+                line: 4,
+                column: 28,
+                message: "directive `@unknown` is not defined in the schema".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn fragments_are_inlined() {
+        let query = "
+            fragment Inner on B { c { d } }
+            fragment Cycle on B { ...Cycle b }
+            query Q { a { b { ...Inner ...Cycle } } }
+        ";
+        let warnings = check(query, 3);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "field `d` is nested 4 levels deep, the maximum is 3"
+        );
+    }
+
+    #[test]
+    fn unselected_fields_are_reported() {
+        let schema = Schema::from_sdl(
+            "
+            schema { query: Query }
+            type Query { viewer: User version: String }
+            interface Node { id: ID! }
+            type User implements Node {
+              id: ID!
+              name: String
+              login: String @deprecated
+              friends: [User!]!
+            }
+            type Bot implements Node { id: ID! owner: String }
+            ",
+        )
+        .unwrap();
+        let query = "
+            fragment UserName on User { name }
+            query Q {
+              viewer {
+                id
+                ...UserName
+                friends { ... on User { id name friends { id name } } }
+              }
+            }
+        ";
+        let document = graphql_parser::parse_query(query).unwrap();
+        let messages: Vec<String> = check_missing_fields(&document, &schema)
+            .into_iter()
+            .map(|warning| warning.to_string())
+            .collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "Q: field `version` of type `Query` is not selected (line 3, column 13)",
+                "Q: field `friends` of type `User` is not selected (line 7, column 49)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use constants::TYPENAME_FIELD;
+use failure;
+use field_type::FieldType;
+use proc_macro2::{Ident, Span, TokenStream};
+use query::QueryContext;
+use selection::{Selection, SelectionItem};
+
+/// Generates a `mock_response()` function building a `ResponseData` with placeholder values: `None` for nullable fields, empty lists, zero values for the built-in scalars, the first variant of enums and `Default::default()` for custom scalars.
+///
+/// The placeholder is built as JSON and deserialized, so that the same code works for every kind of generated type.
+pub(crate) fn mock_response(
+    context: &QueryContext,
+    root_type: &str,
+    selection: &Selection,
+) -> Result<TokenStream, failure::Error> {
+    let response = mock_object(context, root_type, root_type, selection)?;
+
+    Ok(quote! {
+        /// A `ResponseData` with placeholder values, for tests.
+        pub fn mock_response() -> ResponseData {
+            ::graphql_client::serde_json::from_value(#response).expect("mock response")
+        }
+    })
+}
+
+/// A JSON object with the fields selected on `on` (an object or interface). `typename` is the concrete type reported for `__typename`.
+fn mock_object(
+    context: &QueryContext,
+    on: &str,
+    typename: &str,
+    selection: &Selection,
+) -> Result<TokenStream, failure::Error> {
+    let mut inserts = Vec::new();
+    mock_fields(context, on, typename, selection, &mut inserts)?;
+
+    Ok(quote!({
+        let mut map = ::graphql_client::serde_json::Map::new();
+        #(#inserts)*
+        ::graphql_client::serde_json::Value::Object(map)
+    }))
+}
+
+fn mock_fields(
+    context: &QueryContext,
+    on: &str,
+    typename: &str,
+    selection: &Selection,
+    inserts: &mut Vec<TokenStream>,
+) -> Result<(), failure::Error> {
+    for item in &selection.0 {
+        match item {
+            SelectionItem::Field(field) => {
+                let key = field.alias.as_ref().unwrap_or(&field.name);
+                let value = if field.name == TYPENAME_FIELD {
+                    quote!(::graphql_client::serde_json::Value::String(#typename.to_string()))
+                } else {
+                    let schema_field = context
+                        .schema_field(on, &field.name)
+                        .ok_or_else(|| format_err!("could not find field `{}`", field.name))?;
+                    if context.skips_field(schema_field) {
+                        continue;
+                    }
+                    mock_value(context, &schema_field.type_, &field.fields)?
+                };
+                inserts.push(quote!(map.insert(#key.to_string(), #value);));
+            }
+            SelectionItem::FragmentSpread(spread) => {
+                let fragment = context
+                    .fragments
+                    .get(&spread.fragment_name)
+                    .ok_or_else(|| format_err!("Unknown fragment: {}", spread.fragment_name))?;
+                mock_fields(
+                    context,
+                    &fragment.on,
+                    typename,
+                    &fragment.selection,
+                    inserts,
+                )?;
+            }
+            // Handled when choosing the concrete type of unions and interfaces.
+            SelectionItem::InlineFragment(_) => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn mock_value(
+    context: &QueryContext,
+    field_type: &FieldType,
+    selection: &Selection,
+) -> Result<TokenStream, failure::Error> {
+    let name = match field_type {
+        FieldType::Optional(_) => return Ok(quote!(::graphql_client::serde_json::Value::Null)),
+        FieldType::Vector(_) => {
+            return Ok(quote!(::graphql_client::serde_json::Value::Array(
+                Vec::new()
+            )))
+        }
+        FieldType::Named(name) => name.as_str(),
+    };
+    let schema = &context.schema;
+
+    let value = match name {
+        _ if context.scalar_mapping(name).is_some() || schema.scalars.contains_key(name) => {
+            let ident = Ident::new(name, Span::call_site());
+            let core = context.core_path();
+            quote! {
+                ::graphql_client::serde_json::to_value(<#ident as #core::default::Default>::default())
+                    .expect("mock scalar serialization")
+            }
+        }
+        "String" | "ID" => quote!(::graphql_client::serde_json::Value::String(String::new())),
+        "Int" => quote!(::graphql_client::serde_json::Value::from(0)),
+        "Float" => quote!(::graphql_client::serde_json::Value::from(0.0)),
+        "Boolean" => quote!(::graphql_client::serde_json::Value::Bool(false)),
+        _ if schema.enums.contains_key(name) => {
+            let variant = schema.enums[name]
+                .variants
+                .first()
+                .map(|variant| variant.name.clone())
+                .unwrap_or_default();
+            quote!(::graphql_client::serde_json::Value::String(#variant.to_string()))
+        }
+        _ if schema.objects.contains_key(name) => mock_object(context, name, name, selection)?,
+        _ if schema.interfaces.contains_key(name) => {
+            let implementors: Vec<&str> = schema.interfaces[name]
+                .implemented_by
+                .iter()
+                .map(|implementor| implementor.as_str())
+                .collect();
+            mock_abstract(context, name, &implementors, selection)?
+        }
+        _ if schema.unions.contains_key(name) => {
+            let variants: Vec<&str> = schema.unions[name]
+                .variants
+                .iter()
+                .map(|variant| variant.as_str())
+                .collect();
+            mock_abstract(context, name, &variants, selection)?
+        }
+        _ => Err(format_err!("Unknown type in a mock response: {}", name))?,
+    };
+
+    Ok(value)
+}
+
+/// A union or interface value. The concrete type is preferably one without an inline fragment in the selection, since it needs no other fields.
+fn mock_abstract(
+    context: &QueryContext,
+    name: &str,
+    possible_types: &[&str],
+    selection: &Selection,
+) -> Result<TokenStream, failure::Error> {
+    let inline_fragments: Vec<_> = selection
+        .0
+        .iter()
+        .filter_map(|item| match item {
+            SelectionItem::InlineFragment(fragment) => Some(fragment),
+            _ => None,
+        })
+        .collect();
+
+    let unselected = possible_types.iter().find(|possible_type| {
+        inline_fragments
+            .iter()
+            .all(|fragment| &fragment.on != *possible_type)
+    });
+
+    let (typename, fragment_selection) = match (unselected, inline_fragments.first()) {
+        (Some(possible_type), _) => (possible_type.to_string(), None),
+        (None, Some(fragment)) => (fragment.on.clone(), Some(&fragment.fields)),
+        (None, None) => Err(format_err!("{} has no possible types", name))?,
+    };
+
+    // Unions have no fields of their own.
+    let mut inserts = Vec::new();
+    if context.schema.interfaces.contains_key(name) {
+        mock_fields(context, name, &typename, selection, &mut inserts)?;
+    } else {
+        let typename_field =
+            quote!(::graphql_client::serde_json::Value::String(#typename.to_string()));
+        inserts.push(quote!(map.insert(#TYPENAME_FIELD.to_string(), #typename_field);));
+    }
+    if let Some(fragment_selection) = fragment_selection {
+        mock_fields(
+            context,
+            &typename,
+            &typename,
+            fragment_selection,
+            &mut inserts,
+        )?;
+    }
+
+    Ok(quote!({
+        let mut map = ::graphql_client::serde_json::Map::new();
+        #(#inserts)*
+        ::graphql_client::serde_json::Value::Object(map)
+    }))
+}
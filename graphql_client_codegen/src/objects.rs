@@ -1,21 +1,28 @@
 use constants::*;
 use deprecation::DeprecationStatus;
+use directives::Directives;
 use failure;
 use field_type::FieldType;
 use graphql_parser::schema;
+use heck::{CamelCase, SnakeCase};
 use proc_macro2::{Ident, Span, TokenStream};
 use query::QueryContext;
-use schema::Schema;
+use schema::{Schema, DEFAULT_SCALARS};
 use selection::*;
-use shared::{field_impls_for_selection, response_fields_for_selection};
+use shared::{
+    field_impls_for_selection, field_names_impl, fragment_spreads_deserialization, is_rendered,
+    response_fields_for_selection,
+};
 use std::borrow::Cow;
 use std::cell::Cell;
+use std::hash::{Hash, Hasher};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GqlObject {
     pub description: Option<String>,
     pub fields: Vec<GqlObjectField>,
     pub name: String,
+    pub directives: Directives,
     pub is_required: Cell<bool>,
 }
 
@@ -25,14 +32,100 @@ pub struct GqlObjectField {
     pub name: String,
     pub type_: FieldType,
     pub deprecation: DeprecationStatus,
+    pub arguments: Vec<GqlFieldArgument>,
+    pub directives: Directives,
+}
+
+/// An argument on an object or interface field.
+#[derive(Clone, Debug)]
+pub struct GqlFieldArgument {
+    pub name: String,
+    pub type_: FieldType,
+    /// The default value, in GraphQL syntax.
+    pub default_value: Option<String>,
+    /// From `@deprecated` in SDL schemas, or `isDeprecated` in introspection results.
+    pub deprecation: DeprecationStatus,
+}
+
+// Introspection results do not always format default values the way they are written in SDL (quoted enum values for
+// example), so only their presence is compared.
+impl PartialEq for GqlFieldArgument {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.type_ == other.type_
+            && self.default_value.is_some() == other.default_value.is_some()
+            && self.deprecation == other.deprecation
+    }
+}
+
+impl Hash for GqlFieldArgument {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.type_.hash(state);
+        self.default_value.is_some().hash(state);
+        self.deprecation.hash(state);
+    }
+}
+
+impl GqlFieldArgument {
+    pub(crate) fn from_graphql_parser_arguments(
+        arguments: &[schema::InputValue],
+    ) -> Vec<GqlFieldArgument> {
+        let mut arguments: Vec<GqlFieldArgument> = arguments
+            .iter()
+            .map(|arg| GqlFieldArgument {
+                name: arg.name.clone(),
+                type_: FieldType::from(arg.value_type.clone()),
+                default_value: arg.default_value.as_ref().map(|value| value.to_string()),
+                deprecation: deprecation_from_directives(&arg.directives),
+            }).collect();
+        // Sorted so schemas parsed from SDL and from JSON compare equal.
+        arguments.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        arguments
+    }
+
+    pub(crate) fn from_introspected_arguments(
+        arguments: Option<Vec<Option<::introspection_response::FullTypeFieldsArgs>>>,
+    ) -> Vec<GqlFieldArgument> {
+        let mut arguments: Vec<GqlFieldArgument> = arguments
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|arg| arg.map(|arg| arg.input_value))
+            .map(|arg| GqlFieldArgument {
+                name: arg.name.expect("argument name"),
+                type_: FieldType::from(arg.type_.expect("argument type")),
+                deprecation: if arg.is_deprecated.unwrap_or(false) {
+                    DeprecationStatus::Deprecated(arg.deprecation_reason)
+                } else {
+                    DeprecationStatus::Current
+                },
+                default_value: arg.default_value,
+            }).collect();
+        arguments.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        arguments
+    }
+}
+
+impl GqlObjectField {
+    /// The fields of the parent type this field depends on, from `@requires(fields: "...")` (Apollo Federation).
+    pub fn requires(&self) -> Option<&str> {
+        self.directives.requires.as_deref()
+    }
+
+    /// The fields of the returned entity this subgraph can resolve, from `@provides(fields: "...")` (Apollo Federation).
+    pub fn provides(&self) -> Option<&str> {
+        self.directives.provides.as_deref()
+    }
 }
 
 fn parse_deprecation_info(field: &schema::Field) -> DeprecationStatus {
-    let deprecated = field
-        .directives
+    deprecation_from_directives(&field.directives)
+}
+
+fn deprecation_from_directives(directives: &[schema::Directive]) -> DeprecationStatus {
+    let deprecated = directives
         .iter()
-        .filter(|x| x.name.to_lowercase() == "deprecated")
-        .nth(0);
+        .find(|x| x.name.to_lowercase() == "deprecated");
     let reason = if let Some(d) = deprecated {
         if let Some((_, value)) = d
             .arguments
@@ -63,6 +156,7 @@ impl GqlObject {
             description: description.map(|s| s.to_owned()),
             name: name.into_owned(),
             fields: vec![typename_field()],
+            directives: Directives::default(),
             is_required: false.into(),
         }
     }
@@ -70,6 +164,7 @@ impl GqlObject {
     pub fn from_graphql_parser_object(obj: schema::ObjectType) -> Self {
         let description = obj.description.as_ref().map(|s| s.as_str());
         let mut item = GqlObject::new(obj.name.into(), description);
+        item.directives = Directives::from_graphql_parser(&obj.directives);
         item.fields.extend(obj.fields.iter().map(|f| {
             let deprecation = parse_deprecation_info(&f);
             GqlObjectField {
@@ -77,6 +172,8 @@ impl GqlObject {
                 name: f.name.clone(),
                 type_: FieldType::from(f.field_type.clone()),
                 deprecation,
+                directives: Directives::from_graphql_parser(&f.directives),
+                arguments: GqlFieldArgument::from_graphql_parser_arguments(&f.arguments),
             }
         }));
         item
@@ -100,6 +197,8 @@ impl GqlObject {
                     name: t.name.expect("field name"),
                     type_: FieldType::from(t.type_.expect("field type")),
                     deprecation,
+                    directives: Directives::default(),
+                    arguments: GqlFieldArgument::from_introspected_arguments(t.args),
                 }
             })
         });
@@ -126,21 +225,189 @@ impl GqlObject {
         prefix: &str,
     ) -> Result<TokenStream, failure::Error> {
         let derives = query_context.response_derives();
+        let strict = query_context.strict_response_attribute(&self.name, selection);
         let name = Ident::new(prefix, Span::call_site());
         let fields = self.response_fields_for_selection(query_context, selection, prefix)?;
+        let (deserialize_from, deserialization) =
+            fragment_spreads_deserialization(query_context, &name, &fields, selection)?;
         let field_impls = self.field_impls_for_selection(query_context, selection, &prefix)?;
+        let field_names = self.field_names_impl(query_context, selection, &name);
+        let connection_iter =
+            self.connection_into_iter_impl(query_context, selection, &name, prefix);
         let description = self.description.as_ref().map(|desc| quote!(#[doc = #desc]));
+        let shareable = if query_context.federation_mode && self.directives.shareable {
+            Some(quote!(#[doc = "`@shareable`: other subgraphs can resolve this type too."]))
+        } else {
+            None
+        };
+        let resolve_reference = if cfg!(feature = "scaffolding") {
+            self.resolve_reference_stub(query_context, &name)?
+        } else {
+            None
+        };
         Ok(quote! {
             #(#field_impls)*
 
             #derives
+            #deserialize_from
+            #strict
             #description
+            #shareable
             pub struct #name {
                 #(#fields,)*
             }
+
+            #deserialization
+
+            #field_names
+
+            #connection_iter
+
+            #resolve_reference
+        })
+    }
+
+    /// For Relay connection types (with `edges` and `pageInfo` fields), when `edges { node }` is selected: an `IntoIterator` impl on references to the struct, yielding the nodes of the non-null edges.
+    fn connection_into_iter_impl(
+        &self,
+        query_context: &QueryContext,
+        selection: &Selection,
+        name: &Ident,
+        prefix: &str,
+    ) -> Option<TokenStream> {
+        fn schema_field<'a>(
+            fields: &'a [GqlObjectField],
+            name: &str,
+        ) -> Option<&'a GqlObjectField> {
+            fields.iter().find(|field| field.name == name)
+        }
+        fn selected_field<'a>(selection: &'a Selection, name: &str) -> Option<&'a SelectionField> {
+            selection.0.iter().find_map(|item| match item {
+                SelectionItem::Field(f) if f.name == name && !f.stream => Some(f),
+                _ => None,
+            })
+        }
+
+        let schema = &query_context.schema;
+        schema_field(&self.fields, "pageInfo")?;
+        let edges_field = schema_field(&self.fields, "edges")?;
+        let edge_type = schema.objects.get(&edges_field.type_.inner_name_string())?;
+        let node_field = schema_field(&edge_type.fields, "node")?;
+
+        let edges = selected_field(selection, "edges")?;
+        let node = selected_field(&edges.fields, "node")?;
+
+        let renders_plainly = |type_name: &str, field: &GqlObjectField| {
+            is_rendered(query_context, type_name, field)
+                && field.directives.semantic_non_null.is_empty()
+        };
+        if !renders_plainly(&self.name, edges_field)
+            || !renders_plainly(&edge_type.name, node_field)
+        {
+            return None;
+        }
+
+        let edges_alias = edges.alias.as_ref().unwrap_or(&edges.name);
+        let node_alias = node.alias.as_ref().unwrap_or(&node.name);
+        let node_prefix = format!(
+            "{}{}{}",
+            prefix.to_camel_case(),
+            edges_alias.to_camel_case(),
+            node_alias.to_camel_case()
+        );
+        let node_type = FieldType::Named(node_field.type_.inner_name_string())
+            .to_rust(query_context, &node_prefix);
+        let edges_ident = Ident::new(&edges_alias.to_snake_case(), Span::call_site());
+        let node_ident = Ident::new(&node_alias.to_snake_case(), Span::call_site());
+        let core = query_context.core_path();
+
+        // `Option` and `Vec` both have an `iter()` method, so each level of nullability or list is flattened the same way.
+        let flatten = |field_type: &FieldType| {
+            let mut levels = Vec::new();
+            let mut field_type = field_type;
+            loop {
+                field_type = match field_type {
+                    FieldType::Optional(inner) | FieldType::Vector(inner) => inner,
+                    FieldType::Named(_) => break,
+                };
+                levels.push(quote!(.flat_map(|value| value.iter())));
+            }
+            levels
+        };
+        let edges_levels = flatten(&edges_field.type_);
+        let node_levels = flatten(&node_field.type_);
+
+        Some(quote! {
+            impl<'a> IntoIterator for &'a #name {
+                type Item = &'a #node_type;
+                type IntoIter = Box<dyn Iterator<Item = &'a #node_type> + 'a>;
+
+                /// Iterates over the nodes of the connection, skipping the null edges and nodes.
+                fn into_iter(self) -> Self::IntoIter {
+                    Box::new(
+                        #core::iter::once(&self.#edges_ident)
+                            #(#edges_levels)*
+                            .map(|edge| &edge.#node_ident)
+                            #(#node_levels)*
+                    )
+                }
+            }
         })
     }
 
+    /// The `__resolve_reference` scaffolding for entity types, with the fields of their first `@key` (one or more, separated by whitespace). Keys with nested field sets are not supported, so no stub is generated for them.
+    fn resolve_reference_stub(
+        &self,
+        query_context: &QueryContext,
+        name: &Ident,
+    ) -> Result<Option<TokenStream>, failure::Error> {
+        let key = match self.directives.keys.first() {
+            Some(key) if !key.contains('{') => key,
+            _ => return Ok(None),
+        };
+
+        let key_fields: Result<Vec<TokenStream>, failure::Error> = key
+            .split_whitespace()
+            .map(|field_name| {
+                let field = self
+                    .fields
+                    .iter()
+                    .find(|field| field.name == field_name)
+                    .ok_or_else(|| {
+                        format_err!(
+                            "The @key of {} references the unknown field {}",
+                            self.name,
+                            field_name
+                        )
+                    })?;
+                let type_name = field.type_.inner_name_string();
+                let schema = &query_context.schema;
+                if !DEFAULT_SCALARS.contains(&type_name.as_str())
+                    && !schema.scalars.contains_key(&type_name)
+                    && !schema.enums.contains_key(&type_name)
+                {
+                    Err(format_err!(
+                        "The @key field {}.{} is not a scalar or an enum",
+                        self.name,
+                        field_name
+                    ))?
+                }
+
+                let ty = field.type_.to_rust(query_context, "");
+                let snake_case_name = field_name.to_snake_case();
+                let rename = ::shared::field_rename_annotation(field_name, &snake_case_name);
+                let field_ident = Ident::new(&snake_case_name, Span::call_site());
+                Ok(quote!(#rename pub #field_ident: #ty))
+            }).collect();
+
+        Ok(Some(::scaffolding::resolve_reference_stub(
+            &self.name,
+            name,
+            &key_fields?,
+            &query_context.response_derives(),
+        )))
+    }
+
     pub(crate) fn field_impls_for_selection(
         &self,
         query_context: &QueryContext,
@@ -158,6 +425,15 @@ impl GqlObject {
     ) -> Result<Vec<TokenStream>, failure::Error> {
         response_fields_for_selection(&self.name, &self.fields, query_context, selection, prefix)
     }
+
+    pub(crate) fn field_names_impl(
+        &self,
+        query_context: &QueryContext,
+        selection: &Selection,
+        struct_name: &Ident,
+    ) -> TokenStream {
+        field_names_impl(struct_name, &self.name, &self.fields, query_context, selection)
+    }
 }
 
 #[cfg(test)]
@@ -232,4 +508,32 @@ mod test {
         let result = parse_deprecation_info(&mock_field(vec![]));
         assert_eq!(DeprecationStatus::Current, result);
     }
+
+    #[test]
+    fn federation_field_directives() {
+        let schema = Schema::from_sdl(
+            r#"
+            type User @key(fields: "id") {
+                id: ID!
+                weight: Int @external
+                shippingEstimate: Int @requires(fields: "weight")
+                reviews: [Review] @provides(fields: "body")
+            }
+            type Review { body: String }
+            "#,
+        ).unwrap();
+        let field = |name: &str| {
+            schema.objects["User"]
+                .fields
+                .iter()
+                .find(|field| field.name == name)
+                .unwrap()
+                .clone()
+        };
+
+        assert_eq!(field("shippingEstimate").requires(), Some("weight"));
+        assert_eq!(field("shippingEstimate").provides(), None);
+        assert_eq!(field("reviews").provides(), Some("body"));
+        assert_eq!(field("id").requires(), None);
+    }
 }
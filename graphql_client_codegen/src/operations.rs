@@ -1,11 +1,14 @@
 use constants::*;
+use deprecation::DeprecationStrategy;
+use failure;
 use graphql_parser::query::OperationDefinition;
 use heck::SnakeCase;
 use proc_macro2::{Span, TokenStream};
 use query::QueryContext;
 use selection::Selection;
+use std::collections::{BTreeMap, BTreeSet};
 use syn::Ident;
-use variables::Variable;
+use variables::{DeprecatedArgumentUse, Variable};
 
 #[derive(Debug, Clone)]
 pub enum OperationType {
@@ -39,31 +42,90 @@ impl Operation {
     }
 
     /// Generate the Variables struct and all the necessary supporting code.
-    pub(crate) fn expand_variables(&self, context: &QueryContext) -> TokenStream {
+    pub(crate) fn expand_variables(
+        &self,
+        context: &QueryContext,
+        deprecated_arguments: &BTreeMap<String, DeprecatedArgumentUse>,
+    ) -> Result<TokenStream, failure::Error> {
         let variables = &self.variables;
 
         let variables_derives = context.variables_derives();
+        let variables_schema = ::variables::variables_schema_fn(variables, context);
 
         if variables.is_empty() {
-            return quote!(#variables_derives
-            pub struct Variables;);
+            return Ok(quote!(#variables_derives
+            pub struct Variables;
+
+            impl Variables {
+                /// Always true: this operation has no variables, so they are left out of the serialized query body.
+                pub fn is_empty(&self) -> bool {
+                    true
+                }
+
+                /// Yields the name and the serialized value of each variable. There are none for this operation.
+                pub fn variables_iter(&self) -> ::graphql_client::alloc::vec::IntoIter<(&'static str, ::graphql_client::serde_json::Value)> {
+                    ::graphql_client::alloc::vec::Vec::new().into_iter()
+                }
+            }
+
+            impl ::graphql_client::QueryVariables for Variables {
+                fn is_empty(&self) -> bool {
+                    Variables::is_empty(self)
+                }
+            }
+
+            #variables_schema));
         }
 
-        let fields = variables.iter().map(|variable| {
-            let name = &variable.name;
-            let ty = variable.ty.to_rust(context, "");
-            let snake_case_name = name.to_snake_case();
+        let mut fields = Vec::with_capacity(variables.len());
+        for variable in variables {
+            let name = context.variable_name(&variable.name);
+            let ty = variable.ty.to_rust_variable(context);
+            let snake_case_name = variable.name.to_snake_case();
             let rename = ::shared::field_rename_annotation(&name, &snake_case_name);
             let name = Ident::new(&snake_case_name, Span::call_site());
+            let deprecation = match deprecated_arguments.get(&variable.name) {
+                Some(used) => deprecated_variable_attribute(context, &variable.name, used)?,
+                None => None,
+            };
 
-            quote!(#rename pub #name: #ty)
-        });
+            fields.push(quote!(#deprecation #rename pub #name: #ty));
+        }
+
+        let serializable_scalars = self
+            .custom_scalars_in_variables(context)
+            .into_iter()
+            .map(|name| Ident::new(&name, Span::call_site()));
 
         let default_constructors = variables
             .iter()
             .map(|variable| variable.generate_default_value_constructor(context));
+        let default_impl = self.variables_default_impl(context);
+
+        let variables_iter_pushes = variables.iter().map(|variable| {
+            let name = context.variable_name(&variable.name);
+            let field = Ident::new(&variable.name.to_snake_case(), Span::call_site());
+
+            if variable.ty.is_optional() {
+                quote! {
+                    if let Some(ref value) = self.#field {
+                        variables.push((#name, ::graphql_client::serde_json::to_value(value).expect("variable serialization")));
+                    }
+                }
+            } else {
+                quote! {
+                    variables.push((#name, ::graphql_client::serde_json::to_value(&self.#field).expect("variable serialization")));
+                }
+            }
+        });
+
+        Ok(quote! {
+            // Custom scalar types that do not implement `Serialize` are reported here first, before the errors in the derived impl and in `variables_iter`. That one returns a concrete iterator type: the bodies of functions returning `impl Trait` are checked earlier.
+            const _: fn() = || {
+                fn variables_are_serializable<T: ::serde::Serialize>() {}
+                #(variables_are_serializable::<#serializable_scalars>();)*
+            };
 
-        quote! {
             #variables_derives
             pub struct Variables {
                 #(#fields,)*
@@ -71,8 +133,114 @@ impl Operation {
 
             impl Variables {
                 #(#default_constructors)*
+
+                /// Yields the name and the serialized value of each variable, skipping the optional ones that are not set.
+                #[allow(deprecated)]
+                pub fn variables_iter(&self) -> ::graphql_client::alloc::vec::IntoIter<(&'static str, ::graphql_client::serde_json::Value)> {
+                    let mut variables = ::graphql_client::alloc::vec::Vec::new();
+                    #(#variables_iter_pushes)*
+                    variables.into_iter()
+                }
+            }
+
+            impl ::graphql_client::QueryVariables for Variables {}
+
+            #default_impl
+
+            #variables_schema
+        })
+    }
+
+    /// `Default` for the variables, when they all have a default value or are optional, and at least one has a default value. Optional variables without default are `None`.
+    fn variables_default_impl(&self, context: &QueryContext) -> Option<TokenStream> {
+        let has_defaults = self
+            .variables
+            .iter()
+            .any(|variable| variable.default.is_some());
+        let all_defaultable = self
+            .variables
+            .iter()
+            .all(|variable| variable.default.is_some() || variable.ty.is_optional());
+        if !has_defaults || !all_defaultable || context.variables_derive_default() {
+            return None;
+        }
+
+        let fields = self.variables.iter().map(|variable| {
+            let field = Ident::new(&variable.name.to_snake_case(), Span::call_site());
+            if variable.default.is_some() {
+                let constructor =
+                    Ident::new(&format!("default_{}", variable.name), Span::call_site());
+                quote!(#field: Variables::#constructor())
+            } else {
+                quote!(#field: None)
+            }
+        });
+        let core = context.core_path();
+
+        Some(quote! {
+            #[allow(deprecated)]
+            impl #core::default::Default for Variables {
+                fn default() -> Self {
+                    Variables {
+                        #(#fields,)*
+                    }
+                }
+            }
+        })
+    }
+
+    /// The custom scalars used by the variables, directly or in input objects, and `ID` and `Float` if they are mapped with the `scalars` option.
+    fn custom_scalars_in_variables(&self, context: &QueryContext) -> BTreeSet<String> {
+        let schema = &context.schema;
+        let mut scalars = BTreeSet::new();
+        let mut visited_inputs = BTreeSet::new();
+        let mut pending: Vec<String> = self
+            .variables
+            .iter()
+            .map(|variable| variable.ty.inner_name_string())
+            .collect();
+
+        while let Some(name) = pending.pop() {
+            if schema.scalars.contains_key(&name)
+                || ((name == "ID" || name == "Float") && context.scalar_mapping(&name).is_some())
+            {
+                scalars.insert(name);
+            } else if let Some(input) = schema.inputs.get(&name) {
+                if visited_inputs.insert(name) {
+                    pending.extend(
+                        input
+                            .fields
+                            .values()
+                            .map(|field| field.type_.inner_name_string()),
+                    );
+                    pending.extend(input.parents.iter().cloned());
+                }
             }
         }
+
+        scalars
+    }
+}
+
+/// The `#[deprecated]` attribute for a variable passed to a deprecated argument, following the deprecation strategy of the field. Denied deprecations are errors, as the variable cannot be left out.
+fn deprecated_variable_attribute(
+    context: &QueryContext,
+    variable_name: &str,
+    used: &DeprecatedArgumentUse,
+) -> Result<Option<TokenStream>, failure::Error> {
+    match context.deprecation_strategy_for(&used.type_name, &used.field_name) {
+        DeprecationStrategy::Allow => Ok(None),
+        DeprecationStrategy::Warn => match &used.reason {
+            Some(reason) => Ok(Some(quote!(#[deprecated(note = #reason)]))),
+            None => Ok(Some(quote!(#[deprecated]))),
+        },
+        DeprecationStrategy::Deny => Err(format_err!(
+            "The variable ${} is passed to the deprecated argument {}.{}({}:)",
+            variable_name,
+            used.type_name,
+            used.field_name,
+            used.argument_name
+        )),
     }
 }
 
@@ -1,21 +1,68 @@
 use deprecation::DeprecationStrategy;
 use failure;
+use field_type::FieldType;
 use fragments::GqlFragment;
 use itertools::Itertools;
+use objects::GqlObjectField;
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use schema::Schema;
-use selection::Selection;
-use std::collections::BTreeMap;
+use selection::{Selection, SelectionItem};
+use shared::is_rendered;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use syn;
 use syn::Ident;
+use variables::VariablesCase;
 
 /// This holds all the information we need during the code generation phase.
 pub(crate) struct QueryContext {
     pub fragments: BTreeMap<String, GqlFragment>,
     pub schema: Schema,
     pub deprecation_strategy: DeprecationStrategy,
+    pub well_known_scalars: bool,
+    /// Whether the `Bytes` scalar is mapped to `graphql_client::bytes::Base64Bytes`.
+    pub bytes_scalar: bool,
+    /// Whether the `regex` constraints of `@validate` directives are allowed (they need the `validation` feature of graphql_client).
+    pub regex_validation: bool,
+    /// Whether `PartialOrd` and `Ord` should be added to the response derives.
+    pub ord_derives: bool,
+    /// Whether `@external` fields are left out of the response types, see [GraphQLClientDeriveOptions::federation_mode](::GraphQLClientDeriveOptions::federation_mode).
+    pub federation_mode: bool,
+    /// Whether unknown fields are rejected when deserializing responses, see [strict_response_attribute](QueryContext::strict_response_attribute).
+    pub strict_response: bool,
+    /// Whether the code is generated for a `no_std` crate, see [core_path](QueryContext::core_path).
+    pub no_std: bool,
+    /// Prepended to the names of the generated response types, see [prefixed_type_name](QueryContext::prefixed_type_name).
+    pub response_type_prefix: String,
+    /// How the variable names are serialized, see [variable_name](QueryContext::variable_name).
+    pub(crate) rename_variables: Option<VariablesCase>,
+    /// Deprecated fields allowed regardless of the deprecation strategy, as `Type.field` or just `field`.
+    allowed_deprecated_fields: BTreeSet<String>,
     variables_derives: Vec<Ident>,
     response_derives: Vec<Ident>,
+    scalar_mappings: BTreeMap<String, syn::Path>,
+    /// The `serde_with` adapters from `scalars_with`, as strings for the `serde_as` attribute.
+    serde_as_mappings: BTreeMap<String, String>,
+    /// The wrapped types of the scalars from `newtype_scalars`.
+    newtype_scalars: BTreeMap<String, syn::Type>,
+}
+
+impl Default for QueryContext {
+    /// A QueryContext with an empty Schema and the default deprecation strategy.
+    fn default() -> QueryContext {
+        QueryContext::new(Schema::new(), DeprecationStrategy::default())
+    }
+}
+
+impl Extend<GqlFragment> for QueryContext {
+    /// Adds the fragments, keyed by name. A fragment replaces any previous fragment with the same name.
+    fn extend<I: IntoIterator<Item = GqlFragment>>(&mut self, fragments: I) {
+        self.fragments.extend(
+            fragments
+                .into_iter()
+                .map(|fragment| (fragment.name.clone(), fragment)),
+        );
+    }
 }
 
 impl QueryContext {
@@ -25,8 +72,34 @@ impl QueryContext {
             fragments: BTreeMap::new(),
             schema,
             deprecation_strategy,
+            well_known_scalars: false,
+            bytes_scalar: false,
+            no_std: false,
+            regex_validation: false,
+            ord_derives: false,
+            federation_mode: false,
+            strict_response: false,
+            response_type_prefix: String::new(),
+            rename_variables: None,
+            allowed_deprecated_fields: BTreeSet::new(),
             variables_derives: vec![Ident::new("Serialize", Span::call_site())],
             response_derives: vec![Ident::new("Deserialize", Span::call_site())],
+            scalar_mappings: BTreeMap::new(),
+            serde_as_mappings: BTreeMap::new(),
+            newtype_scalars: BTreeMap::new(),
+        }
+    }
+
+    /// The name of a generated type, with the `response_type_prefix` prepended.
+    pub(crate) fn prefixed_type_name(&self, name: &str) -> String {
+        format!("{}{}", self.response_type_prefix, name)
+    }
+
+    /// The serialized name of a variable, following `rename_variables`.
+    pub(crate) fn variable_name(&self, name: &str) -> String {
+        match self.rename_variables {
+            Some(case) => case.apply(name),
+            None => name.to_string(),
         }
     }
 
@@ -43,8 +116,21 @@ impl QueryContext {
             fragments: BTreeMap::new(),
             schema: Schema::new(),
             deprecation_strategy: DeprecationStrategy::Allow,
+            well_known_scalars: false,
+            bytes_scalar: false,
+            no_std: false,
+            regex_validation: false,
+            ord_derives: false,
+            federation_mode: false,
+            strict_response: false,
+            response_type_prefix: String::new(),
+            rename_variables: None,
+            allowed_deprecated_fields: BTreeSet::new(),
             variables_derives: vec![Ident::new("Serialize", Span::call_site())],
             response_derives: vec![Ident::new("Deserialize", Span::call_site())],
+            scalar_mappings: BTreeMap::new(),
+            serde_as_mappings: BTreeMap::new(),
+            newtype_scalars: BTreeMap::new(),
         }
     }
 
@@ -81,44 +167,492 @@ impl QueryContext {
             ));
         }
 
-        self.variables_derives.extend(
-            attribute_value
-                .split(',')
-                .map(|s| s.trim())
-                .map(|s| Ident::new(s, Span::call_site())),
-        );
-        self.response_derives.extend(
+        self.ingest_variables_derives(attribute_value)?;
+        self.ingest_response_derives(attribute_value)
+    }
+
+    /// Adds derives to the generated variables structs only.
+    pub(crate) fn ingest_variables_derives(
+        &mut self,
+        attribute_value: &str,
+    ) -> Result<(), failure::Error> {
+        for derive in parse_derives(attribute_value) {
+            if !self.variables_derives.contains(&derive) {
+                self.variables_derives.push(derive);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds derives to the generated response structs and enums only.
+    pub(crate) fn ingest_response_derives(
+        &mut self,
+        attribute_value: &str,
+    ) -> Result<(), failure::Error> {
+        for derive in parse_derives(attribute_value) {
+            if !self.response_derives.contains(&derive) {
+                self.response_derives.push(derive);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces all the derives of the generated response structs, including the default `Deserialize`, for custom implementations. `Serialize` or `Deserialize` must remain, for the serde attributes on the fields.
+    pub(crate) fn set_response_derives_exclusive(
+        &mut self,
+        attribute_value: &str,
+    ) -> Result<(), failure::Error> {
+        let derives: Vec<Ident> = parse_derives(attribute_value).unique().collect();
+
+        // The fields carry `#[serde(...)]` attributes, which need one of the serde derives.
+        if !derives
+            .iter()
+            .any(|derive| derive == "Serialize" || derive == "Deserialize")
+        {
+            Err(format_err!(
+                "The exclusive response derives must include Serialize or Deserialize: {}",
+                attribute_value
+            ))?
+        }
+
+        self.response_derives = derives;
+        Ok(())
+    }
+
+    /// Reads scalar mappings of the form `Name = path::to::Type`, separated by commas.
+    pub(crate) fn ingest_scalar_mappings(
+        &mut self,
+        attribute_value: &str,
+    ) -> Result<(), failure::Error> {
+        for mapping in split_mappings(attribute_value) {
+            let mut parts = mapping.splitn(2, '=');
+            let (name, path) = match (parts.next(), parts.next()) {
+                (Some(name), Some(path)) => (name.trim(), path.trim()),
+                _ => Err(format_err!(
+                    "Invalid scalar mapping: {} (expected ScalarName = path::to::Type)",
+                    mapping.trim()
+                ))?,
+            };
+            let path: syn::Path = syn::parse_str(path).map_err(|err| {
+                format_err!("Invalid type path for the {} scalar: {} ({})", name, path, err)
+            })?;
+            self.scalar_mappings.insert(name.to_string(), path);
+        }
+        Ok(())
+    }
+
+    /// `#[serde(deny_unknown_fields)]` for the structs of strict responses, for a selection on the object `type_name`. serde does not support it with `flatten`, so structs with fragment spreads are still lenient. So are the structs leaving out some of the selected fields (see [is_rendered]), since the server still sends them.
+    pub(crate) fn strict_response_attribute(
+        &self,
+        type_name: &str,
+        selection: &Selection,
+    ) -> TokenStream {
+        let schema_fields = self
+            .schema
+            .objects
+            .get(type_name)
+            .map(|object| object.fields.as_slice())
+            .unwrap_or(&[]);
+        let is_lenient = selection.0.iter().any(|item| match item {
+            SelectionItem::FragmentSpread(_) => true,
+            SelectionItem::Field(f) => schema_fields
+                .iter()
+                .any(|field| field.name == f.name && !is_rendered(self, type_name, field)),
+            SelectionItem::InlineFragment(_) => false,
+        });
+
+        if self.strict_response && !is_lenient {
+            quote!(#[serde(deny_unknown_fields)])
+        } else {
+            quote!()
+        }
+    }
+
+    /// Whether a selected field is left out of the generated code: `@inaccessible` fields and fields of `@inaccessible` types, and `@external` fields in federation mode.
+    pub(crate) fn skips_field(&self, field: &GqlObjectField) -> bool {
+        let inaccessible_type = self
+            .schema
+            .type_definition(&field.type_.inner_name_string())
+            .map(|ty| ty.directives().inaccessible)
+            .unwrap_or(false);
+
+        field.directives.inaccessible
+            || inaccessible_type
+            || (self.federation_mode && field.directives.external)
+    }
+
+    /// Reads a comma-separated list of deprecated fields to allow, as `Type.field` or just `field`.
+    pub(crate) fn ingest_allowed_deprecated_fields(&mut self, attribute_value: &str) {
+        self.allowed_deprecated_fields.extend(
             attribute_value
                 .split(',')
-                .map(|s| s.trim())
-                .map(|s| Ident::new(s, Span::call_site())),
+                .map(|field| field.trim().to_string())
+                .filter(|field| !field.is_empty()),
         );
+    }
+
+    /// The deprecation strategy for a field: `Allow` if it was listed in `allow_deprecated_fields`, the global one otherwise.
+    pub(crate) fn deprecation_strategy_for(
+        &self,
+        type_name: &str,
+        field_name: &str,
+    ) -> &DeprecationStrategy {
+        if self.allowed_deprecated_fields.contains(field_name)
+            || self
+                .allowed_deprecated_fields
+                .contains(&format!("{}.{}", type_name, field_name))
+        {
+            &DeprecationStrategy::Allow
+        } else {
+            &self.deprecation_strategy
+        }
+    }
+
+    /// Reads `serde_with` adapters for scalars, of the form `Name = path::to::SerdeAs`, separated by commas.
+    pub(crate) fn ingest_serde_as_mappings(
+        &mut self,
+        attribute_value: &str,
+    ) -> Result<(), failure::Error> {
+        for mapping in split_mappings(attribute_value) {
+            let mut parts = mapping.splitn(2, '=');
+            let (name, path) = match (parts.next(), parts.next()) {
+                (Some(name), Some(path)) => (name.trim(), path.trim()),
+                _ => Err(format_err!(
+                    "Invalid scalars_with mapping: {} (expected ScalarName = path::to::SerdeAs)",
+                    mapping.trim()
+                ))?,
+            };
+            syn::parse_str::<syn::Type>(path).map_err(|err| {
+                format_err!("Invalid serde_as type for the {} scalar: {} ({})", name, path, err)
+            })?;
+            self.serde_as_mappings
+                .insert(name.to_string(), path.to_string());
+        }
         Ok(())
     }
 
+    /// Reads the scalars to generate newtypes for, of the form `Name = path::to::Type`, separated by commas.
+    pub(crate) fn ingest_newtype_scalars(
+        &mut self,
+        attribute_value: &str,
+    ) -> Result<(), failure::Error> {
+        for mapping in split_mappings(attribute_value) {
+            let mut parts = mapping.splitn(2, '=');
+            let (name, ty) = match (parts.next(), parts.next()) {
+                (Some(name), Some(ty)) => (name.trim(), ty.trim()),
+                _ => Err(format_err!(
+                    "Invalid newtype_scalars mapping: {} (expected ScalarName = path::to::Type)",
+                    mapping.trim()
+                ))?,
+            };
+            if self.scalar_mappings.contains_key(name) {
+                Err(format_err!(
+                    "The {} scalar is in both scalars and newtype_scalars",
+                    name
+                ))?
+            }
+            let ty: syn::Type = syn::parse_str(ty).map_err(|err| {
+                format_err!("Invalid wrapped type for the {} scalar: {} ({})", name, ty, err)
+            })?;
+            self.newtype_scalars.insert(name.to_string(), ty);
+        }
+        Ok(())
+    }
+
+    /// The type wrapped by the newtype generated for a scalar, if it is in `newtype_scalars`.
+    pub(crate) fn newtype_scalar(&self, name: &str) -> Option<&syn::Type> {
+        self.newtype_scalars.get(name)
+    }
+
+    /// The `#[serde_as(as = "...")]` attribute for a response field, if its scalar has a `serde_with` adapter. Lists and nullable types are wrapped in `Vec` and `Option` the same way as the field type.
+    pub(crate) fn serde_as_annotation(&self, field_type: &FieldType) -> Option<TokenStream> {
+        fn serde_as_type(field_type: &FieldType, adapter: &str) -> String {
+            match field_type {
+                FieldType::Named(_) => adapter.to_string(),
+                FieldType::Optional(inner) => format!("Option<{}>", serde_as_type(inner, adapter)),
+                FieldType::Vector(inner) => format!("Vec<{}>", serde_as_type(inner, adapter)),
+            }
+        }
+
+        self.serde_as_mappings
+            .get(&field_type.inner_name_string())
+            .map(|adapter| {
+                let serde_as_type = serde_as_type(field_type, adapter);
+                quote!(#[serde_as(as = #serde_as_type)])
+            })
+    }
+
+    /// The type the user mapped this scalar to, if any.
+    pub(crate) fn scalar_mapping(&self, name: &str) -> Option<&syn::Path> {
+        self.scalar_mappings.get(name)
+    }
+
+    /// Whether `Display` was requested in the response derives. It cannot be derived, so we generate the implementations for enums and unions instead.
+    pub(crate) fn response_derives_display(&self) -> bool {
+        self.response_derives.iter().any(is_display)
+    }
+
+    /// Whether `Deserialize` is among the response derives. It is not with a `response_derives_exclusive` list leaving it out.
+    pub(crate) fn response_derives_deserialize(&self) -> bool {
+        self.response_derives
+            .iter()
+            .any(|derive| derive == "Deserialize")
+    }
+
+    /// Whether `Serialize` is among the response derives.
+    pub(crate) fn response_derives_serialize(&self) -> bool {
+        self.response_derives
+            .iter()
+            .any(|derive| derive == "Serialize")
+    }
+
+    /// Whether `Default` is derived on the variables, in which case no `Default` impl is generated for them.
+    pub(crate) fn variables_derive_default(&self) -> bool {
+        self.variables_derives
+            .iter()
+            .any(|derive| derive == "Default")
+    }
+
+    /// Whether both `PartialEq` and `Eq` are in the response derives, which `Ord` requires.
+    pub(crate) fn response_derives_eq(&self) -> bool {
+        ["PartialEq", "Eq"].iter().all(|required| {
+            self.response_derives
+                .iter()
+                .any(|derive| derive == required)
+        })
+    }
+
+    /// Whether every field in the selection, on the type called `on`, has a type known to implement `Ord`. Floats and custom scalars are not.
+    pub(crate) fn selection_is_ord(&self, on: &str, selection: &Selection) -> bool {
+        selection.0.iter().all(|item| match item {
+            SelectionItem::Field(field) => {
+                if field.name == ::constants::TYPENAME_FIELD {
+                    return true;
+                }
+
+                self.schema_field(on, &field.name)
+                    .map(|f| self.type_is_ord(&f.type_.inner_name_string(), &field.fields))
+                    .unwrap_or(false)
+            }
+            SelectionItem::FragmentSpread(spread) => self
+                .fragments
+                .get(&spread.fragment_name)
+                .map(|fragment| self.selection_is_ord(&fragment.on, &fragment.selection))
+                .unwrap_or(false),
+            SelectionItem::InlineFragment(fragment) => {
+                self.selection_is_ord(&fragment.on, &fragment.fields)
+            }
+        })
+    }
+
+    /// Which fragments each fragment spreads, directly or in nested selections. Every fragment has an entry, and the names in each list are sorted.
+    pub(crate) fn fragment_dependency_graph(&self) -> HashMap<String, Vec<String>> {
+        self.fragments
+            .values()
+            .map(|fragment| {
+                let dependencies = fragment
+                    .selection
+                    .fragment_spreads()
+                    .into_iter()
+                    .map(|name| name.to_string())
+                    .collect();
+                (fragment.name.clone(), dependencies)
+            })
+            .collect()
+    }
+
+    /// A cycle of fragments spreading each other, as the list of fragment names starting and ending with the same one. The spec forbids these, and they cannot be generated as structs.
+    pub(crate) fn fragment_cycle(&self) -> Option<Vec<String>> {
+        fn visit<'a>(
+            graph: &'a HashMap<String, Vec<String>>,
+            name: &'a str,
+            path: &mut Vec<&'a str>,
+            done: &mut BTreeSet<&'a str>,
+        ) -> Option<Vec<String>> {
+            if let Some(start) = path.iter().position(|visited| *visited == name) {
+                let mut cycle: Vec<String> = path[start..].iter().map(|s| s.to_string()).collect();
+                cycle.push(name.to_string());
+                return Some(cycle);
+            }
+            if !done.insert(name) {
+                return None;
+            }
+            path.push(name);
+            for dependency in graph.get(name).map(Vec::as_slice).unwrap_or_default() {
+                if let Some(cycle) = visit(graph, dependency, path, done) {
+                    return Some(cycle);
+                }
+            }
+            path.pop();
+            None
+        }
+
+        let graph = self.fragment_dependency_graph();
+        let mut names: Vec<&String> = graph.keys().collect();
+        names.sort();
+        let mut done = BTreeSet::new();
+        names
+            .into_iter()
+            .filter_map(|name| visit(&graph, name, &mut Vec::new(), &mut done))
+            .next()
+    }
+
+    /// The fragments sorted so that each one comes after the fragments it spreads, and otherwise by name. Fragments in a cycle (see `fragment_cycle`) are yielded once, in an unspecified position.
+    pub(crate) fn fragments_in_dependency_order(
+        &self,
+    ) -> impl Iterator<Item = (&str, &GqlFragment)> {
+        fn visit<'a>(
+            graph: &HashMap<String, Vec<String>>,
+            fragments: &'a BTreeMap<String, GqlFragment>,
+            name: &str,
+            visited: &mut BTreeSet<String>,
+            order: &mut Vec<(&'a str, &'a GqlFragment)>,
+        ) {
+            if !visited.insert(name.to_string()) {
+                return;
+            }
+            for dependency in graph.get(name).map(Vec::as_slice).unwrap_or_default() {
+                visit(graph, fragments, dependency, visited, order);
+            }
+            if let Some((name, fragment)) = fragments.get_key_value(name) {
+                order.push((name.as_str(), fragment));
+            }
+        }
+
+        let graph = self.fragment_dependency_graph();
+        let mut visited = BTreeSet::new();
+        let mut order = Vec::with_capacity(self.fragments.len());
+        for name in self.fragments.keys() {
+            visit(&graph, &self.fragments, name, &mut visited, &mut order);
+        }
+        order.into_iter()
+    }
+
+    /// The effective `@cacheControl` max age for the selection on the type called `on`: the smallest hint among the selected fields, including nested ones. Fields without a hint of their own use the hint on their type, if any.
+    pub(crate) fn cache_max_age_for_selection(
+        &self,
+        on: &str,
+        selection: &Selection,
+    ) -> Option<u32> {
+        selection
+            .0
+            .iter()
+            .filter_map(|item| match item {
+                SelectionItem::Field(field) => {
+                    let schema_field = self.schema_field(on, &field.name)?;
+                    let type_name = schema_field.type_.inner_name_string();
+                    let hint = schema_field.directives.cache_max_age().or_else(|| {
+                        self.schema
+                            .type_definition(&type_name)
+                            .and_then(|ty| ty.directives().cache_max_age())
+                    });
+                    let nested = self.cache_max_age_for_selection(&type_name, &field.fields);
+
+                    hint.into_iter().chain(nested).min()
+                }
+                SelectionItem::FragmentSpread(spread) => {
+                    self.fragments.get(&spread.fragment_name).and_then(|fragment| {
+                        self.cache_max_age_for_selection(&fragment.on, &fragment.selection)
+                    })
+                }
+                SelectionItem::InlineFragment(fragment) => {
+                    self.cache_max_age_for_selection(&fragment.on, &fragment.fields)
+                }
+            }).min()
+    }
+
+    /// The field called `name` on the object or interface called `on`.
+    pub(crate) fn schema_field(&self, on: &str, name: &str) -> Option<&GqlObjectField> {
+        self.schema
+            .objects
+            .get(on)
+            .map(|object| &object.fields)
+            .or_else(|| self.schema.interfaces.get(on).map(|iface| &iface.fields))
+            .and_then(|fields| fields.iter().find(|f| f.name == name))
+    }
+
+    fn type_is_ord(&self, name: &str, selection: &Selection) -> bool {
+        match name {
+            "Float" => false,
+            "Boolean" | "ID" | "Int" | "String" => true,
+            _ if self.schema.enums.contains_key(name) => true,
+            _ if self.schema.scalars.contains_key(name) => false,
+            _ => self.selection_is_ord(name, selection),
+        }
+    }
+
     pub(crate) fn variables_derives(&self) -> TokenStream {
-        let derives = self.variables_derives.iter().unique();
+        let derives = self
+            .variables_derives
+            .iter()
+            .filter(|derive| !is_display(derive))
+            .unique();
+
+        quote! {
+            #[derive( #(#derives),* )]
+        }
+    }
+
+    /// The derives of the newtypes generated for scalars. They are used in both the variables and the responses, so they get the derives of both.
+    pub(crate) fn newtype_scalar_derives(&self) -> TokenStream {
+        let derives = self
+            .variables_derives
+            .iter()
+            .chain(self.response_derives.iter())
+            .filter(|derive| !is_display(derive))
+            .unique();
 
         quote! {
             #[derive( #(#derives),* )]
         }
     }
 
+    /// The root of the paths to `core` items (`fmt`, `iter`...) in the generated code: `::std`, or `::core` in `no_std` crates.
+    pub(crate) fn core_path(&self) -> TokenStream {
+        if self.no_std {
+            quote!(::core)
+        } else {
+            quote!(::std)
+        }
+    }
+
+    /// `#[serde_as]` for the response structs when `scalars_with` is used. It has to come before the derives it rewrites the field attributes for.
+    pub(crate) fn serde_as_attribute(&self) -> TokenStream {
+        if self.serde_as_mappings.is_empty() {
+            quote!()
+        } else {
+            quote!(#[::serde_with::serde_as])
+        }
+    }
+
     pub(crate) fn response_derives(&self) -> TokenStream {
-        let derives = self.response_derives.iter().unique();
+        let ord_derives = self.ord_derives_idents();
+        let derives = self
+            .response_derives
+            .iter()
+            .chain(ord_derives.iter())
+            .filter(|derive| !is_display(derive))
+            .unique();
+
+        let serde_as = self.serde_as_attribute();
 
         quote! {
+            #serde_as
             #[derive( #(#derives),* )]
         }
     }
 
     pub(crate) fn response_enum_derives(&self) -> TokenStream {
+        let ord_derives = self.ord_derives_idents();
         let enum_derives: Vec<_> = self
             .response_derives
             .iter()
+            .chain(ord_derives.iter())
+            .unique()
             .filter(|derive| {
                 !derive.to_string().contains("erialize")
                     && !derive.to_string().contains("Deserialize")
+                    && !is_display(derive)
             }).collect();
 
         if !enum_derives.is_empty() {
@@ -129,12 +663,192 @@ impl QueryContext {
             quote!()
         }
     }
+
+    fn ord_derives_idents(&self) -> Vec<Ident> {
+        if self.ord_derives {
+            vec![
+                Ident::new("PartialOrd", Span::call_site()),
+                Ident::new("Ord", Span::call_site()),
+            ]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+fn is_display(derive: &Ident) -> bool {
+    derive == "Display"
+}
+
+/// Splits a list of mappings on the commas that are not inside generic arguments, tuples or arrays, like the one in `HashMap<String, u8>`.
+fn split_mappings(attribute_value: &str) -> Vec<&str> {
+    let mut mappings = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in attribute_value.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                mappings.push(&attribute_value[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    mappings.push(&attribute_value[start..]);
+    mappings
+}
+
+fn parse_derives(attribute_value: &str) -> impl Iterator<Item = Ident> + '_ {
+    attribute_value
+        .split(',')
+        .map(|s| s.trim())
+        .map(|s| Ident::new(s, Span::call_site()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn allowed_deprecated_fields_override_the_strategy() {
+        let mut context = QueryContext::new(Schema::new(), DeprecationStrategy::Deny);
+        context.ingest_allowed_deprecated_fields("User.oldName, legacyId,");
+
+        assert_eq!(
+            context.deprecation_strategy_for("User", "oldName"),
+            &DeprecationStrategy::Allow
+        );
+        assert_eq!(
+            context.deprecation_strategy_for("Organization", "legacyId"),
+            &DeprecationStrategy::Allow
+        );
+        assert_eq!(
+            context.deprecation_strategy_for("Organization", "oldName"),
+            &DeprecationStrategy::Deny
+        );
+    }
+
+    #[test]
+    fn fragments_can_be_extended_in_bulk() {
+        let mut context = QueryContext::new_empty();
+        let fragment = |name: &str, on: &str| GqlFragment {
+            name: name.to_string(),
+            on: on.to_string(),
+            selection: Selection(Vec::new()),
+            is_required: false.into(),
+        };
+
+        context.extend(vec![fragment("A", "User"), fragment("B", "User")]);
+        context.extend(vec![fragment("A", "Organization")]);
+
+        assert_eq!(context.fragments.keys().collect::<Vec<_>>(), vec!["A", "B"]);
+        assert_eq!(context.fragments["A"].on, "Organization");
+    }
+
+    fn context_with_fragments(query: &str) -> QueryContext {
+        let document = ::graphql_parser::parse_query(query).unwrap();
+        let fragments = document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                ::graphql_parser::query::Definition::Fragment(fragment) => {
+                    let ::graphql_parser::query::TypeCondition::On(on) = &fragment.type_condition;
+                    Some(GqlFragment {
+                        name: fragment.name.clone(),
+                        on: on.clone(),
+                        selection: Selection::from(&fragment.selection_set),
+                        is_required: false.into(),
+                    })
+                }
+                _ => None,
+            });
+        let mut context = QueryContext::new_empty();
+        context.extend(fragments);
+        context
+    }
+
+    #[test]
+    fn fragment_dependencies_are_collected() {
+        let context = context_with_fragments(
+            "
+            fragment A on User { name ...B friends { ...C } }
+            fragment B on User { ... on Admin { ...C } }
+            fragment C on User { id }
+            ",
+        );
+
+        let graph = context.fragment_dependency_graph();
+        assert_eq!(graph["A"], vec!["B".to_string(), "C".to_string()]);
+        assert_eq!(graph["B"], vec!["C".to_string()]);
+        assert_eq!(graph["C"], Vec::<String>::new());
+        assert_eq!(context.fragment_cycle(), None);
+    }
+
+    #[test]
+    fn fragments_are_ordered_by_dependency() {
+        let context = context_with_fragments(
+            "
+            fragment A on User { ...B ...C }
+            fragment B on User { name ...D }
+            fragment C on User { friends { ...D } }
+            fragment D on User { id }
+            ",
+        );
+
+        assert_eq!(
+            context
+                .fragments_in_dependency_order()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            vec!["D", "B", "C", "A"]
+        );
+    }
+
+    #[test]
+    fn fragment_cycles_are_detected() {
+        let context = context_with_fragments(
+            "
+            fragment A on User { name ...B }
+            fragment B on User { friends { ...A } }
+            ",
+        );
+
+        assert_eq!(
+            context.fragment_cycle(),
+            Some(vec!["A".to_string(), "B".to_string(), "A".to_string()])
+        );
+    }
+
+    #[test]
+    fn mappings_are_split_outside_of_generic_arguments() {
+        assert_eq!(
+            split_mappings("Map = HashMap<String, u8>, Pair = (i32, [u8; 2]), Long = i64"),
+            vec![
+                "Map = HashMap<String, u8>",
+                " Pair = (i32, [u8; 2])",
+                " Long = i64"
+            ]
+        );
+    }
+
+    #[test]
+    fn serde_as_mappings_can_have_several_generic_arguments() {
+        let mut context = QueryContext::new_empty();
+        context
+            .ingest_serde_as_mappings(
+                "Pairs = ::serde_with::Map<DisplayFromStr, Same>, ID = DisplayFromStr",
+            )
+            .unwrap();
+
+        assert_eq!(
+            context.serde_as_mappings["Pairs"],
+            "::serde_with::Map<DisplayFromStr, Same>"
+        );
+        assert_eq!(context.serde_as_mappings["ID"], "DisplayFromStr");
+    }
+
     #[test]
     fn response_derives_ingestion_works() {
         let mut context = QueryContext::new_empty();
@@ -169,6 +883,179 @@ mod tests {
         );
     }
 
+    #[test]
+    fn variables_and_response_derives_can_be_ingested_separately() {
+        let mut context = QueryContext::new_empty();
+
+        context.ingest_variables_derives("Debug, Hash").unwrap();
+        context.ingest_response_derives("PartialEq").unwrap();
+
+        assert_eq!(
+            context.variables_derives().to_string(),
+            "# [ derive ( Serialize , Debug , Hash ) ]"
+        );
+        assert_eq!(
+            context.response_derives().to_string(),
+            "# [ derive ( Deserialize , PartialEq ) ]"
+        );
+    }
+
+    #[test]
+    fn default_derives_are_not_duplicated() {
+        let mut context = QueryContext::new_empty();
+
+        context
+            .ingest_additional_derives("Serialize, PartialEq, Deserialize, PartialEq")
+            .unwrap();
+
+        assert_eq!(
+            context.variables_derives,
+            vec![
+                Ident::new("Serialize", Span::call_site()),
+                Ident::new("PartialEq", Span::call_site()),
+                Ident::new("Deserialize", Span::call_site()),
+            ]
+        );
+        assert_eq!(
+            context.response_derives,
+            vec![
+                Ident::new("Deserialize", Span::call_site()),
+                Ident::new("Serialize", Span::call_site()),
+                Ident::new("PartialEq", Span::call_site()),
+            ]
+        );
+    }
+
+    #[test]
+    fn display_is_not_derived() {
+        let mut context = QueryContext::new_empty();
+        assert!(!context.response_derives_display());
+
+        context
+            .ingest_additional_derives("PartialEq, Display")
+            .unwrap();
+
+        assert!(context.response_derives_display());
+        assert_eq!(
+            context.variables_derives().to_string(),
+            "# [ derive ( Serialize , PartialEq ) ]"
+        );
+        assert_eq!(
+            context.response_derives().to_string(),
+            "# [ derive ( Deserialize , PartialEq ) ]"
+        );
+        assert_eq!(
+            context.response_enum_derives().to_string(),
+            "# [ derive ( PartialEq ) ]"
+        );
+    }
+
+    #[test]
+    fn ord_derives_are_added_once() {
+        let mut context = QueryContext::new_empty();
+
+        context
+            .ingest_additional_derives("PartialEq, Eq, PartialOrd")
+            .unwrap();
+        assert!(context.response_derives_eq());
+        context.ord_derives = true;
+
+        assert_eq!(
+            context.response_derives().to_string(),
+            "# [ derive ( Deserialize , PartialEq , Eq , PartialOrd , Ord ) ]"
+        );
+        assert_eq!(
+            context.response_enum_derives().to_string(),
+            "# [ derive ( PartialEq , Eq , PartialOrd , Ord ) ]"
+        );
+        assert_eq!(
+            context.variables_derives().to_string(),
+            "# [ derive ( Serialize , PartialEq , Eq , PartialOrd ) ]"
+        );
+    }
+
+    #[test]
+    fn floats_and_custom_scalars_are_not_ord() {
+        use graphql_parser;
+
+        let schema = graphql_parser::parse_schema(
+            "
+            scalar Date
+            enum Color { RED, BLUE }
+            type Paint { name: String!, color: Color, price: Float, expires: Date }
+            type Query { paint: Paint }
+            ",
+        )
+        .unwrap();
+        let context = QueryContext::new(Schema::from(schema), DeprecationStrategy::Allow);
+        let selection = |query: &str| {
+            let query = graphql_parser::parse_query(query).unwrap();
+            match &query.definitions[0] {
+                graphql_parser::query::Definition::Operation(op) => {
+                    ::operations::Operation::from(op).selection
+                }
+                _ => unreachable!(),
+            }
+        };
+
+        assert!(context.selection_is_ord(
+            "Query",
+            &selection("query A { paint { name color __typename } }")
+        ));
+        assert!(!context.selection_is_ord("Query", &selection("query B { paint { name price } }")));
+        assert!(!context.selection_is_ord("Query", &selection("query C { paint { expires } }")));
+        assert!(!context.selection_is_ord("Query", &selection("query D { paint { unknown } }")));
+    }
+
+    #[test]
+    fn cache_max_age_is_the_smallest_hint() {
+        use graphql_parser;
+
+        let schema = graphql_parser::parse_schema(
+            "
+            type Author @cacheControl(maxAge: 600) { name: String }
+            type Post {
+              title: String @cacheControl(maxAge: 300)
+              votes: Int @cacheControl(maxAge: 30)
+              author: Author
+            }
+            type Query { post: Post @cacheControl(maxAge: 1200), uncached: String }
+            ",
+        )
+        .unwrap();
+        let context = QueryContext::new(Schema::from(schema), DeprecationStrategy::Allow);
+        let selection = |query: &str| {
+            let query = graphql_parser::parse_query(query).unwrap();
+            match &query.definitions[0] {
+                graphql_parser::query::Definition::Operation(op) => {
+                    ::operations::Operation::from(op).selection
+                }
+                _ => unreachable!(),
+            }
+        };
+        let max_age = |query: &str| context.cache_max_age_for_selection("Query", &selection(query));
+
+        assert_eq!(max_age("query A { post { title author { name } } }"), Some(300));
+        assert_eq!(max_age("query B { post { title votes } }"), Some(30));
+        assert_eq!(max_age("query C { post { author { name } } }"), Some(600));
+        assert_eq!(max_age("query D { uncached }"), None);
+    }
+
+    #[test]
+    fn exclusive_response_derives_replace_the_defaults() {
+        let mut context = QueryContext::new_empty();
+        context.ingest_response_derives("PartialEq").unwrap();
+        context
+            .set_response_derives_exclusive("Debug, Serialize, Debug")
+            .unwrap();
+
+        assert_eq!(
+            context.response_derives().to_string(),
+            "# [ derive ( Debug , Serialize ) ]"
+        );
+        assert!(context.set_response_derives_exclusive("Debug").is_err());
+    }
+
     #[test]
     fn response_derives_fails_when_called_twice() {
         let mut context = QueryContext::new_empty();
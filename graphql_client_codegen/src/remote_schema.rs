@@ -0,0 +1,131 @@
+use failure;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// The directory, relative to the crate root, where introspected schemas are cached.
+pub const CACHE_DIR: &str = ".graphql_client_cache";
+
+/// When this environment variable is set to `1`, schemas are never fetched: the cached introspection result must exist.
+pub const OFFLINE_ENV_VAR: &str = "GRAPHQL_CLIENT_OFFLINE";
+
+/// Whether a `schema_path` is the URL of a GraphQL API rather than a file.
+pub fn is_url(schema_path: &str) -> bool {
+    schema_path.starts_with("http://") || schema_path.starts_with("https://")
+}
+
+/// Where the introspection result for `url` is cached in `cache_dir`. The file name is the SHA-256 hash of the URL.
+pub fn cached_schema_path(url: &str, cache_dir: &Path) -> PathBuf {
+    cache_dir.join(format!("{:x}.json", Sha256::digest(url.as_bytes())))
+}
+
+/// Returns the path to the introspection result of the API at `url`, running the introspection query first if the result is not cached in `cache_dir` yet.
+///
+/// Fetching requires the `http_schema` feature. It never happens when `GRAPHQL_CLIENT_OFFLINE=1`.
+pub fn fetch_schema(url: &str, cache_dir: &Path) -> Result<PathBuf, failure::Error> {
+    let offline = ::std::env::var(OFFLINE_ENV_VAR)
+        .map(|value| value == "1")
+        .unwrap_or(false);
+    fetch_schema_inner(url, cache_dir, offline)
+}
+
+fn fetch_schema_inner(
+    url: &str,
+    cache_dir: &Path,
+    offline: bool,
+) -> Result<PathBuf, failure::Error> {
+    let cached = cached_schema_path(url, cache_dir);
+
+    if cached.exists() {
+        return Ok(cached);
+    }
+
+    if offline {
+        Err(format_err!(
+            "{} is set but the schema for {} is not cached (expected at {})",
+            OFFLINE_ENV_VAR,
+            url,
+            cached.display()
+        ))?
+    }
+
+    let response = introspect(url)?;
+    // Make sure we only cache valid responses.
+    let _: ::serde_json::Value = ::serde_json::from_str(&response)
+        .map_err(|err| format_err!("Invalid introspection response from {}: {}", url, err))?;
+
+    ::std::fs::create_dir_all(cache_dir)?;
+    ::std::fs::write(&cached, response)?;
+
+    Ok(cached)
+}
+
+#[cfg(feature = "http_schema")]
+fn introspect(url: &str) -> Result<String, failure::Error> {
+    const INTROSPECTION_QUERY: &str = include_str!("introspection_query.graphql");
+
+    let body = ::serde_json::json!({
+        "query": INTROSPECTION_QUERY,
+        "operationName": "IntrospectionQuery",
+        "variables": {},
+    });
+
+    let response = ::ureq::post(url)
+        .set("Content-Type", "application/json")
+        .set("Accept", "application/json")
+        .send_string(&body.to_string())?;
+
+    Ok(response.into_string()?)
+}
+
+#[cfg(not(feature = "http_schema"))]
+fn introspect(url: &str) -> Result<String, failure::Error> {
+    Err(format_err!(
+        "Fetching the schema from {} requires the http_schema feature",
+        url
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache_dir(name: &str) -> PathBuf {
+        let dir = ::std::env::temp_dir().join(format!("graphql_client_cache_{}", name));
+        let _ = ::std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn urls_are_detected() {
+        assert!(is_url("https://api.example.com/graphql"));
+        assert!(is_url("http://localhost:8080/graphql"));
+        assert!(!is_url("src/schema.graphql"));
+        assert!(!is_url("/abs/path/schema.json"));
+    }
+
+    #[test]
+    fn cached_schemas_are_not_fetched_again() {
+        let cache_dir = temp_cache_dir("cached");
+        let url = "https://api.example.com/graphql";
+        let cached = cached_schema_path(url, &cache_dir);
+        ::std::fs::create_dir_all(&cache_dir).unwrap();
+        ::std::fs::write(&cached, "{}").unwrap();
+
+        assert_eq!(fetch_schema_inner(url, &cache_dir, true).unwrap(), cached);
+        assert_eq!(fetch_schema_inner(url, &cache_dir, false).unwrap(), cached);
+        assert_ne!(
+            cached_schema_path("https://other.example.com/graphql", &cache_dir),
+            cached
+        );
+    }
+
+    #[test]
+    fn offline_mode_requires_a_cached_schema() {
+        let cache_dir = temp_cache_dir("offline");
+        let err = fetch_schema_inner("https://api.example.com/graphql", &cache_dir, true)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.starts_with("GRAPHQL_CLIENT_OFFLINE is set but the schema for https://api.example.com/graphql is not cached"));
+    }
+}
@@ -0,0 +1,114 @@
+use failure;
+use proc_macro2::{Ident, Span, TokenStream};
+use syn;
+
+/// Generates a `From<ResponseData>` impl for the type at `target_path`, with a `todo!()` body for the user to fill in.
+pub(crate) fn response_data_into_impl(
+    module_name: &Ident,
+    target_path: &str,
+) -> Result<TokenStream, failure::Error> {
+    let target: syn::Path = syn::parse_str(target_path).map_err(|err| {
+        format_err!(
+            "Invalid type path in the into attribute: {} ({})",
+            target_path,
+            err
+        )
+    })?;
+    let message = format!(
+        "convert {}::ResponseData into {}",
+        module_name, target_path
+    );
+
+    Ok(quote! {
+        impl ::std::convert::From<#module_name::ResponseData> for #target {
+            fn from(_response_data: #module_name::ResponseData) -> Self {
+                todo!(#message)
+            }
+        }
+    })
+}
+
+/// Generates a struct for the `@key` fields of an entity type, and a trait with a `__resolve_reference` function for the response struct `name` to implement. The derive cannot write the body, so the trait is left for the user to implement, and that implementation reports failures as a GraphQL error.
+pub(crate) fn resolve_reference_stub(
+    type_name: &str,
+    name: &Ident,
+    key_fields: &[TokenStream],
+    derives: &TokenStream,
+) -> TokenStream {
+    let key_name = Ident::new(&format!("{}EntityKey", name), Span::call_site());
+    let trait_name = Ident::new(&format!("{}ResolveReference", name), Span::call_site());
+    let trait_doc = format!(
+        "Resolves the {} entity from the fields of its `@key` directive (Apollo Federation). Implement it for [{}].",
+        type_name, name
+    );
+
+    quote! {
+        #derives
+        pub struct #key_name {
+            #(#key_fields,)*
+        }
+
+        #[doc = #trait_doc]
+        pub trait #trait_name: Sized {
+            /// Resolves the entity from its key, or returns the GraphQL error to report in the response.
+            fn __resolve_reference(key: #key_name) -> Result<Self, ::graphql_client::Error>;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_data_into_impl_works() {
+        let module_name = Ident::new("my_query", Span::call_site());
+        let generated = response_data_into_impl(&module_name, "crate::domain::User")
+            .unwrap()
+            .to_string();
+
+        assert_eq!(
+            generated,
+            quote! {
+                impl ::std::convert::From<my_query::ResponseData> for crate::domain::User {
+                    fn from(_response_data: my_query::ResponseData) -> Self {
+                        todo!("convert my_query::ResponseData into crate::domain::User")
+                    }
+                }
+            }.to_string()
+        );
+    }
+
+    #[test]
+    fn response_data_into_impl_rejects_invalid_paths() {
+        let module_name = Ident::new("my_query", Span::call_site());
+        assert!(response_data_into_impl(&module_name, "not a path").is_err());
+    }
+
+    #[test]
+    fn resolve_reference_stub_works() {
+        let name = Ident::new("RustUserQueryUser", Span::call_site());
+        let generated = resolve_reference_stub(
+            "User",
+            &name,
+            &[quote!(pub id: ID)],
+            &quote!(#[derive(Deserialize)]),
+        ).to_string();
+
+        assert_eq!(
+            generated,
+            quote! {
+                #[derive(Deserialize)]
+                pub struct RustUserQueryUserEntityKey {
+                    pub id: ID,
+                }
+
+                #[doc = "Resolves the User entity from the fields of its `@key` directive (Apollo Federation). Implement it for [RustUserQueryUser]."]
+                pub trait RustUserQueryUserResolveReference: Sized {
+                    /// Resolves the entity from its key, or returns the GraphQL error to report in the response.
+                    fn __resolve_reference(key: RustUserQueryUserEntityKey) -> Result<Self, ::graphql_client::Error>;
+                }
+            }.to_string()
+        );
+    }
+}
@@ -1,22 +1,224 @@
+use directives::Directives;
 use proc_macro2;
+use query::QueryContext;
 use std::cell::Cell;
+use syn;
+
+/// Custom scalars that are mapped to a type from `graphql_client::well_known_scalars` when the `well_known_scalars` feature is enabled.
+pub(crate) const WELL_KNOWN_SCALARS: &[&str] = &["BigDecimal", "Byte", "JSON", "Long", "Short"];
+
+/// The scalar for base64-encoded binary data, mapped to `graphql_client::bytes::Base64Bytes` when the `bytes` feature is enabled.
+pub(crate) const BYTES_SCALAR: &str = "Bytes";
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq)]
 pub struct Scalar {
     pub name: String,
     pub description: Option<String>,
+    pub directives: Directives,
     pub is_required: Cell<bool>,
 }
 
 impl Scalar {
-    // TODO: do something smarter here
-    pub fn to_rust(&self) -> proc_macro2::TokenStream {
+    /// Generates a type alias for the scalar. It points to the mapping from the `scalars` option if there is one, then to the well-known scalar types and `Base64Bytes` if enabled, and to a type with the same name in the parent module by default.
+    pub(crate) fn to_rust(&self, context: &QueryContext) -> proc_macro2::TokenStream {
         use proc_macro2::{Ident, Span};
         let ident = Ident::new(&self.name, Span::call_site());
         let description = match &self.description {
             Some(d) => quote!(#[doc = #d]),
             None => quote!(),
         };
-        quote!(#description type #ident = super::#ident;)
+        if let Some(inner) = context.newtype_scalar(&self.name) {
+            return self.newtype(context, inner);
+        }
+        let target = if let Some(path) = context.scalar_mapping(&self.name) {
+            quote!(#path)
+        } else if context.well_known_scalars && WELL_KNOWN_SCALARS.contains(&self.name.as_str()) {
+            quote!(::graphql_client::well_known_scalars::#ident)
+        } else if context.bytes_scalar && self.name == BYTES_SCALAR {
+            quote!(::graphql_client::bytes::Base64Bytes)
+        } else {
+            quote!(super::#ident)
+        };
+        quote!(#description type #ident = #target;)
+    }
+
+    /// Generates a newtype around `inner` for a scalar from the `newtype_scalars` option. It (de)serializes like `inner`, and dereferences to it so its methods can be called directly.
+    fn newtype(&self, context: &QueryContext, inner: &syn::Type) -> proc_macro2::TokenStream {
+        use proc_macro2::{Ident, Span};
+        let ident = Ident::new(&self.name, Span::call_site());
+        let description = self.description.as_ref().map(|d| quote!(#[doc = #d]));
+        let derives = context.newtype_scalar_derives();
+        let core = context.core_path();
+
+        quote! {
+            #description
+            #derives
+            #[serde(transparent)]
+            pub struct #ident(pub #inner);
+
+            impl #core::ops::Deref for #ident {
+                type Target = #inner;
+
+                fn deref(&self) -> &#inner {
+                    &self.0
+                }
+            }
+
+            impl #core::ops::DerefMut for #ident {
+                fn deref_mut(&mut self) -> &mut #inner {
+                    &mut self.0
+                }
+            }
+
+            impl #core::convert::From<#inner> for #ident {
+                fn from(inner: #inner) -> Self {
+                    #ident(inner)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(name: &str) -> Scalar {
+        Scalar {
+            name: name.to_string(),
+            description: None,
+            directives: Directives::default(),
+            is_required: false.into(),
+        }
+    }
+
+    #[test]
+    fn scalars_default_to_the_parent_module() {
+        let context = QueryContext::new_empty();
+        assert_eq!(
+            scalar("Long").to_rust(&context).to_string(),
+            "type Long = super :: Long ;"
+        );
+    }
+
+    #[test]
+    fn well_known_scalars_are_mapped_when_enabled() {
+        let mut context = QueryContext::new_empty();
+        context.well_known_scalars = true;
+
+        assert_eq!(
+            scalar("Long").to_rust(&context).to_string(),
+            "type Long = :: graphql_client :: well_known_scalars :: Long ;"
+        );
+        assert_eq!(
+            scalar("Email").to_rust(&context).to_string(),
+            "type Email = super :: Email ;"
+        );
+    }
+
+    #[test]
+    fn bytes_is_mapped_when_enabled() {
+        let mut context = QueryContext::new_empty();
+        assert_eq!(
+            scalar("Bytes").to_rust(&context).to_string(),
+            "type Bytes = super :: Bytes ;"
+        );
+
+        context.bytes_scalar = true;
+        assert_eq!(
+            scalar("Bytes").to_rust(&context).to_string(),
+            "type Bytes = :: graphql_client :: bytes :: Base64Bytes ;"
+        );
+
+        context
+            .ingest_scalar_mappings("Bytes = ::std::vec::Vec<u8>")
+            .unwrap();
+        assert_eq!(
+            scalar("Bytes").to_rust(&context).to_string(),
+            "type Bytes = :: std :: vec :: Vec < u8 > ;"
+        );
+    }
+
+    #[test]
+    fn scalar_mappings_take_precedence() {
+        let mut context = QueryContext::new_empty();
+        context.well_known_scalars = true;
+        context
+            .ingest_scalar_mappings("Long = crate::MyLong, Email=::std::string::String")
+            .unwrap();
+
+        assert_eq!(
+            scalar("Long").to_rust(&context).to_string(),
+            "type Long = crate :: MyLong ;"
+        );
+        assert_eq!(
+            scalar("Email").to_rust(&context).to_string(),
+            "type Email = :: std :: string :: String ;"
+        );
+    }
+
+    #[test]
+    fn scalar_mappings_can_have_several_generic_arguments() {
+        let mut context = QueryContext::new_empty();
+        context
+            .ingest_scalar_mappings("Counts = ::std::collections::HashMap<String, u8>, Long = i64")
+            .unwrap();
+
+        assert_eq!(
+            scalar("Counts").to_rust(&context).to_string(),
+            "type Counts = :: std :: collections :: HashMap < String , u8 > ;"
+        );
+        assert_eq!(
+            scalar("Long").to_rust(&context).to_string(),
+            "type Long = i64 ;"
+        );
+    }
+
+    #[test]
+    fn invalid_scalar_mappings_are_rejected() {
+        let mut context = QueryContext::new_empty();
+        assert!(context.ingest_scalar_mappings("Long").is_err());
+        assert!(context.ingest_scalar_mappings("Long = not a path").is_err());
+    }
+
+    #[test]
+    fn newtype_scalars_are_generated_with_deref() {
+        let mut context = QueryContext::new_empty();
+        context
+            .ingest_newtype_scalars("DateTime = ::chrono::DateTime<::chrono::Utc>")
+            .unwrap();
+        let generated = scalar("DateTime").to_rust(&context).to_string();
+
+        assert!(generated.contains(
+            "# [ derive ( Serialize , Deserialize ) ] # [ serde ( transparent ) ] pub struct DateTime ( pub :: chrono :: DateTime < :: chrono :: Utc > ) ;"
+        ));
+        assert!(generated.contains(
+            "impl :: std :: ops :: Deref for DateTime { type Target = :: chrono :: DateTime < :: chrono :: Utc > ;"
+        ));
+        assert!(generated.contains("impl :: std :: ops :: DerefMut for DateTime"));
+    }
+
+    #[test]
+    fn newtype_scalars_can_have_several_generic_arguments() {
+        let mut context = QueryContext::new_empty();
+        context
+            .ingest_newtype_scalars("Counts = ::std::collections::HashMap<String, u8>, Long = i64")
+            .unwrap();
+
+        assert!(scalar("Counts").to_rust(&context).to_string().contains(
+            "pub struct Counts ( pub :: std :: collections :: HashMap < String , u8 > ) ;"
+        ));
+        assert!(scalar("Long")
+            .to_rust(&context)
+            .to_string()
+            .contains("pub struct Long ( pub i64 ) ;"));
+    }
+
+    #[test]
+    fn newtype_scalars_cannot_be_mapped_too() {
+        let mut context = QueryContext::new_empty();
+        context.ingest_scalar_mappings("Long = i64").unwrap();
+        assert!(context.ingest_newtype_scalars("Long = i64").is_err());
+        assert!(context.ingest_newtype_scalars("Short").is_err());
     }
 }
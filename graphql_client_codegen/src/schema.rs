@@ -1,11 +1,13 @@
 use deprecation::DeprecationStatus;
+use directives::{DirectiveDefinition, Directives, LinkedSpec, BUILTIN_DIRECTIVES};
 use enums::{EnumVariant, GqlEnum};
 use failure;
 use field_type::FieldType;
 use graphql_parser::{self, schema};
 use inputs::GqlInput;
 use interfaces::GqlInterface;
-use objects::{GqlObject, GqlObjectField};
+use itertools::Itertools;
+use objects::{GqlFieldArgument, GqlObject, GqlObjectField};
 use scalars::Scalar;
 use std::collections::{BTreeMap, BTreeSet};
 use unions::GqlUnion;
@@ -24,6 +26,62 @@ pub struct Schema {
     pub(crate) query_type: Option<String>,
     pub(crate) mutation_type: Option<String>,
     pub(crate) subscription_type: Option<String>,
+    pub(crate) linked_specs: Vec<LinkedSpec>,
+    pub(crate) directives: BTreeMap<String, DirectiveDefinition>,
+}
+
+/// A named type definition in a [Schema].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeDef<'a> {
+    /// An enum type.
+    Enum(&'a GqlEnum),
+    /// An input object type.
+    Input(&'a GqlInput),
+    /// An interface type.
+    Interface(&'a GqlInterface),
+    /// An object type.
+    Object(&'a GqlObject),
+    /// A custom scalar type.
+    Scalar(&'a Scalar),
+    /// A union type.
+    Union(&'a GqlUnion),
+}
+
+impl<'a> TypeDef<'a> {
+    /// The name of the type in the schema.
+    pub fn name(&self) -> &'a str {
+        match self {
+            TypeDef::Enum(enm) => &enm.name,
+            TypeDef::Input(input) => &input.name,
+            TypeDef::Interface(iface) => &iface.name,
+            TypeDef::Object(obj) => &obj.name,
+            TypeDef::Scalar(scalar) => &scalar.name,
+            TypeDef::Union(union) => &union.name,
+        }
+    }
+
+    /// The directives applied to the type definition itself.
+    pub fn directives(&self) -> &'a Directives {
+        match self {
+            TypeDef::Enum(enm) => &enm.directives,
+            TypeDef::Input(input) => &input.directives,
+            TypeDef::Interface(iface) => &iface.directives,
+            TypeDef::Object(obj) => &obj.directives,
+            TypeDef::Scalar(scalar) => &scalar.directives,
+            TypeDef::Union(union) => &union.directives,
+        }
+    }
+
+    /// Whether the type, or one of its fields, is tagged with `@tag(name: tag)`.
+    fn has_tag(&self, tag: &str) -> bool {
+        let has_tagged_field = match self {
+            TypeDef::Input(input) => input.fields.values().any(|f| f.directives.has_tag(tag)),
+            TypeDef::Interface(iface) => iface.fields.iter().any(|f| f.directives.has_tag(tag)),
+            TypeDef::Object(obj) => obj.fields.iter().any(|f| f.directives.has_tag(tag)),
+            _ => false,
+        };
+        has_tagged_field || self.directives().has_tag(tag)
+    }
 }
 
 impl Schema {
@@ -38,6 +96,200 @@ impl Schema {
             query_type: None,
             mutation_type: None,
             subscription_type: None,
+            linked_specs: Vec::new(),
+            directives: BTreeMap::new(),
+        }
+    }
+
+    /// Parses a schema in the GraphQL schema definition language.
+    pub fn from_sdl(sdl: &str) -> Result<Schema, ParseError> {
+        let document = graphql_parser::parse_schema(sdl).map_err(ParseError::Sdl)?;
+        Schema::from(document).validated()
+    }
+
+    /// Parses a schema in the GraphQL schema definition language, where input objects can inherit the fields of other input objects with `input Child implements Parent`. This is not standard GraphQL.
+    pub fn from_sdl_with_input_inheritance(sdl: &str) -> Result<Schema, ParseError> {
+        let (sdl, parents) = ::input_inheritance::strip_input_parents(sdl);
+        let document = graphql_parser::parse_schema(&sdl).map_err(ParseError::Sdl)?;
+        let mut schema = Schema::from(document);
+        for (name, parents) in parents {
+            if let Some(input) = schema.inputs.get_mut(&name) {
+                input.parents = parents;
+            }
+        }
+        schema.validated()
+    }
+
+    /// Parses the JSON response to an introspection query. Both the full response and its `data` field are accepted.
+    pub fn from_introspection_json(json: &str) -> Result<Schema, ParseError> {
+        let response: ::introspection_response::IntrospectionResponse =
+            ::serde_json::from_str(json).map_err(ParseError::Json)?;
+        Schema::from(response).validated()
+    }
+
+    fn validated(self) -> Result<Schema, ParseError> {
+        let errors = self.validate();
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(ParseError::Invalid(errors))
+        }
+    }
+
+    /// Checks the internal consistency of the schema: all the referenced types are defined, the implementors of each interface declare all its fields, and the members of unions are object types.
+    ///
+    /// [Schema::from_sdl] and [Schema::from_introspection_json] run these checks.
+    pub fn validate(&self) -> Vec<SchemaError> {
+        let mut errors = Vec::new();
+        let is_defined = |type_name: &str| {
+            DEFAULT_SCALARS.contains(&type_name) || self.type_definition(type_name).is_some()
+        };
+        let unknown = |type_name: &str, referenced_by: String| {
+            if is_defined(type_name) {
+                None
+            } else {
+                Some(SchemaError::UnknownType {
+                    type_name: type_name.to_string(),
+                    referenced_by,
+                })
+            }
+        };
+
+        let roots = [
+            ("schema.query", &self.query_type),
+            ("schema.mutation", &self.mutation_type),
+            ("schema.subscription", &self.subscription_type),
+        ];
+        for (referenced_by, root) in roots.iter() {
+            if let Some(root) = root {
+                errors.extend(unknown(root, referenced_by.to_string()));
+            }
+        }
+
+        let fields = self
+            .objects
+            .values()
+            .flat_map(|obj| obj.fields.iter().map(move |field| (&obj.name, field)))
+            .chain(
+                self.interfaces
+                    .values()
+                    .flat_map(|iface| iface.fields.iter().map(move |field| (&iface.name, field))),
+            );
+        for (parent, field) in fields {
+            let path = format!("{}.{}", parent, field.name);
+            errors.extend(unknown(&field.type_.inner_name_string(), path.clone()));
+            for argument in &field.arguments {
+                errors.extend(unknown(
+                    &argument.type_.inner_name_string(),
+                    format!("{}({}:)", path, argument.name),
+                ));
+            }
+        }
+
+        for input in self.inputs.values() {
+            let mut fields: Vec<_> = input.fields.values().collect();
+            fields.sort_by_key(|field| &field.name);
+            for field in fields {
+                errors.extend(unknown(
+                    &field.type_.inner_name_string(),
+                    format!("{}.{}", input.name, field.name),
+                ));
+            }
+            for parent in &input.parents {
+                if is_defined(parent) && !self.inputs.contains_key(parent) {
+                    errors.push(SchemaError::NonInputParent {
+                        input: input.name.clone(),
+                        parent: parent.clone(),
+                    });
+                } else {
+                    errors.extend(unknown(parent, input.name.clone()));
+                }
+            }
+            // Each cycle is reported once, from the input object with the smallest name in it.
+            if let Some(cycle) = self.input_inheritance_cycle(&input.name) {
+                if cycle.iter().all(|name| name >= &input.name) {
+                    errors.push(SchemaError::InputInheritanceCycle { cycle });
+                }
+            }
+        }
+
+        for iface in self.interfaces.values() {
+            let mut implementors: Vec<&String> = iface.implemented_by.iter().collect();
+            implementors.sort();
+            for implementor in implementors {
+                let object = match self.objects.get(implementor) {
+                    Some(object) => object,
+                    None => {
+                        errors.extend(unknown(implementor, iface.name.clone()));
+                        continue;
+                    }
+                };
+                for field in &iface.fields {
+                    if object.fields.iter().all(|f| f.name != field.name) {
+                        errors.push(SchemaError::MissingInterfaceField {
+                            interface: iface.name.clone(),
+                            implementor: implementor.clone(),
+                            field: field.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        for union in self.unions.values() {
+            for member in &union.variants {
+                if self.objects.contains_key(member) {
+                    continue;
+                }
+                if is_defined(member) {
+                    errors.push(SchemaError::NonObjectUnionMember {
+                        union_name: union.name.clone(),
+                        member: member.clone(),
+                    });
+                } else {
+                    errors.extend(unknown(member, union.name.clone()));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Follows the parents of the input object `start` and returns the input objects on a path leading back to it, starting with `start`.
+    fn input_inheritance_cycle(&self, start: &str) -> Option<Vec<String>> {
+        fn visit<'a>(
+            schema: &'a Schema,
+            start: &str,
+            current: &'a str,
+            path: &mut Vec<&'a str>,
+            visited: &mut BTreeSet<&'a str>,
+        ) -> bool {
+            let parents = match schema.inputs.get(current) {
+                Some(input) => &input.parents,
+                None => return false,
+            };
+            for parent in parents {
+                if parent == start {
+                    return true;
+                }
+                if !visited.insert(parent) {
+                    continue;
+                }
+                path.push(parent);
+                if visit(schema, start, parent, path, visited) {
+                    return true;
+                }
+                path.pop();
+            }
+            false
+        }
+
+        let mut path = vec![start];
+        let mut visited = BTreeSet::new();
+        if visit(self, start, start, &mut path, &mut visited) {
+            Some(path.into_iter().map(|name| name.to_string()).collect())
+        } else {
+            None
         }
     }
 
@@ -57,6 +309,80 @@ impl Schema {
             }).collect()
     }
 
+    /// All the type definitions in the schema, sorted by name.
+    pub fn type_definitions(&self) -> Vec<TypeDef<'_>> {
+        let mut types: Vec<TypeDef> = self
+            .enums
+            .values()
+            .map(TypeDef::Enum)
+            .chain(self.inputs.values().map(TypeDef::Input))
+            .chain(self.interfaces.values().map(TypeDef::Interface))
+            .chain(self.objects.values().map(TypeDef::Object))
+            .chain(self.scalars.values().map(TypeDef::Scalar))
+            .chain(self.unions.values().map(TypeDef::Union))
+            .collect();
+        types.sort_by_key(|ty| ty.name());
+        types
+    }
+
+    /// The type definitions that are not marked `@inaccessible`, sorted by name. These make up the public schema of a federated graph.
+    pub fn accessible_types(&self) -> impl Iterator<Item = TypeDef<'_>> {
+        self.type_definitions()
+            .into_iter()
+            .filter(|ty| !ty.directives().inaccessible)
+    }
+
+    /// The type definition with the given name, if there is one. Built-in scalars have none.
+    pub fn type_definition(&self, name: &str) -> Option<TypeDef<'_>> {
+        self.enums
+            .get(name)
+            .map(TypeDef::Enum)
+            .or_else(|| self.inputs.get(name).map(TypeDef::Input))
+            .or_else(|| self.interfaces.get(name).map(TypeDef::Interface))
+            .or_else(|| self.objects.get(name).map(TypeDef::Object))
+            .or_else(|| self.scalars.get(name).map(TypeDef::Scalar))
+            .or_else(|| self.unions.get(name).map(TypeDef::Union))
+    }
+
+    /// The types and fields added, removed or changed from this schema to `other`.
+    pub fn diff(&self, other: &Schema) -> ::diff::SchemaDiff {
+        ::diff::SchemaDiff::between(self, other)
+    }
+
+    /// The types that are tagged with `@tag(name: tag)`, or have a field tagged with it, sorted by name.
+    ///
+    /// This is meant for tooling that checks federation schema contracts.
+    pub fn types_with_tag(&self, tag: &str) -> Vec<TypeDef<'_>> {
+        self.type_definitions()
+            .into_iter()
+            .filter(|ty| ty.has_tag(tag))
+            .collect()
+    }
+
+    /// The specifications imported with `@link` on the schema definition (Apollo Federation 2). `extend schema @link(...)` is not supported by the SDL parser, so the links have to be on the `schema { ... }` definition.
+    pub fn linked_specs(&self) -> Vec<LinkedSpec> {
+        self.linked_specs.clone()
+    }
+
+    /// Whether a directive (named without `@`) is imported by one of the [linked specifications](Schema::linked_specs). Validation should not report these as unknown directives.
+    pub fn imports_directive(&self, name: &str) -> bool {
+        self.linked_specs
+            .iter()
+            .any(|spec| spec.imports_directive(name))
+    }
+
+    /// The directives defined in the schema, by name. The built-in directives (`@skip`, `@include`...) are only there if the schema defines them explicitly, as introspection results do.
+    pub fn directive_definitions(&self) -> &BTreeMap<String, DirectiveDefinition> {
+        &self.directives
+    }
+
+    /// Whether the directive called `name` (without `@`) can be used: it is built-in, defined in the schema or [imported](Schema::imports_directive).
+    pub fn defines_directive(&self, name: &str) -> bool {
+        BUILTIN_DIRECTIVES.contains(&name)
+            || self.directives.contains_key(name)
+            || self.imports_directive(name)
+    }
+
     pub(crate) fn require(&self, typename_: &str) {
         DEFAULT_SCALARS
             .iter()
@@ -79,6 +405,7 @@ impl Schema {
     }
 }
 
+/// Prefer [Schema::from_sdl] when starting from a string.
 impl ::std::convert::From<graphql_parser::schema::Document> for Schema {
     fn from(ast: graphql_parser::schema::Document) -> Schema {
         let mut schema = Schema::new();
@@ -116,6 +443,7 @@ impl ::std::convert::From<graphql_parser::schema::Document> for Schema {
                                         description: v.description.clone(),
                                         name: v.name.clone(),
                                     }).collect(),
+                                directives: Directives::from_graphql_parser(&enm.directives),
                                 is_required: false.into(),
                             },
                         );
@@ -126,6 +454,7 @@ impl ::std::convert::From<graphql_parser::schema::Document> for Schema {
                             Scalar {
                                 name: scalar.name,
                                 description: scalar.description,
+                                directives: Directives::from_graphql_parser(&scalar.directives),
                                 is_required: false.into(),
                             },
                         );
@@ -133,10 +462,12 @@ impl ::std::convert::From<graphql_parser::schema::Document> for Schema {
                     schema::TypeDefinition::Union(union) => {
                         let variants: BTreeSet<String> = union.types.into_iter().collect();
                         schema.unions.insert(
-                            union.name,
+                            union.name.clone(),
                             GqlUnion {
+                                name: union.name,
                                 variants,
                                 description: union.description,
+                                directives: Directives::from_graphql_parser(&union.directives),
                                 is_required: false.into(),
                             },
                         );
@@ -146,6 +477,7 @@ impl ::std::convert::From<graphql_parser::schema::Document> for Schema {
                             interface.name.clone().into(),
                             interface.description.as_ref().map(|d| d.as_str()),
                         );
+                        iface.directives = Directives::from_graphql_parser(&interface.directives);
                         iface
                             .fields
                             .extend(interface.fields.iter().map(|f| GqlObjectField {
@@ -153,6 +485,10 @@ impl ::std::convert::From<graphql_parser::schema::Document> for Schema {
                                 name: f.name.clone(),
                                 type_: FieldType::from(f.field_type.clone()),
                                 deprecation: DeprecationStatus::Current,
+                                directives: Directives::from_graphql_parser(&f.directives),
+                                arguments: GqlFieldArgument::from_graphql_parser_arguments(
+                                    &f.arguments,
+                                ),
                             }));
                         schema.interfaces.insert(interface.name, iface);
                     }
@@ -162,12 +498,18 @@ impl ::std::convert::From<graphql_parser::schema::Document> for Schema {
                             .insert(input.name.clone(), GqlInput::from(input));
                     }
                 },
-                schema::Definition::DirectiveDefinition(_) => (),
+                schema::Definition::DirectiveDefinition(definition) => {
+                    schema.directives.insert(
+                        definition.name.clone(),
+                        DirectiveDefinition::from_graphql_parser(definition),
+                    );
+                }
                 schema::Definition::TypeExtension(_extension) => (),
                 schema::Definition::SchemaDefinition(definition) => {
                     schema.query_type = definition.query;
                     schema.mutation_type = definition.mutation;
                     schema.subscription_type = definition.subscription;
+                    schema.linked_specs = LinkedSpec::from_graphql_parser(&definition.directives);
                 }
             }
         }
@@ -180,6 +522,7 @@ impl ::std::convert::From<graphql_parser::schema::Document> for Schema {
     }
 }
 
+/// Prefer [Schema::from_introspection_json] when starting from a string.
 impl ::std::convert::From<::introspection_response::IntrospectionResponse> for Schema {
     fn from(src: ::introspection_response::IntrospectionResponse) -> Self {
         use introspection_response::__TypeKind;
@@ -190,6 +533,13 @@ impl ::std::convert::From<::introspection_response::IntrospectionResponse> for S
         schema.query_type = root.query_type.and_then(|ty| ty.name);
         schema.mutation_type = root.mutation_type.and_then(|ty| ty.name);
         schema.subscription_type = root.subscription_type.and_then(|ty| ty.name);
+        schema.directives = root
+            .directives
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|directive| directive.and_then(DirectiveDefinition::from_introspection))
+            .map(|directive| (directive.name.clone(), directive))
+            .collect();
 
         // Holds which objects implement which interfaces so we can populate GqlInterface#implemented_by later.
         // It maps interface names to a vec of implementation names.
@@ -221,6 +571,7 @@ impl ::std::convert::From<::introspection_response::IntrospectionResponse> for S
                         name: name.clone(),
                         description: ty.description.clone(),
                         variants,
+                        directives: Directives::default(),
                         is_required: false.into(),
                     };
                     schema.enums.insert(name, enm);
@@ -236,6 +587,7 @@ impl ::std::convert::From<::introspection_response::IntrospectionResponse> for S
                             Scalar {
                                 name,
                                 description: ty.description.as_ref().cloned(),
+                                directives: Directives::default(),
                                 is_required: false.into(),
                             },
                         );
@@ -245,15 +597,17 @@ impl ::std::convert::From<::introspection_response::IntrospectionResponse> for S
                     let variants: BTreeSet<String> = ty
                         .possible_types
                         .clone()
-                        .unwrap()
+                        .unwrap_or_else(Vec::new)
                         .into_iter()
                         .filter_map(|t| t.and_then(|t| t.type_ref.name.clone()))
                         .collect();
                     schema.unions.insert(
                         name.clone(),
                         GqlUnion {
+                            name: name.clone(),
                             description: ty.description.as_ref().map(|d| d.to_owned()),
                             variants,
+                            directives: Directives::default(),
                             is_required: false.into(),
                         },
                     );
@@ -293,6 +647,8 @@ impl ::std::convert::From<::introspection_response::IntrospectionResponse> for S
                                 name: f.name.expect("field name"),
                                 type_: FieldType::from(f.type_.expect("field type")),
                                 deprecation: DeprecationStatus::Current,
+                                directives: Directives::default(),
+                                arguments: GqlFieldArgument::from_introspected_arguments(f.args),
                             }),
                     );
                     schema.interfaces.insert(name, iface);
@@ -312,11 +668,914 @@ impl ::std::convert::From<::introspection_response::IntrospectionResponse> for S
     }
 }
 
+/// Serializes the schema as the JSON response to an introspection query (the `__schema` field), which [Schema::from_introspection_json] reads back.
+///
+/// The applied directives (`@key`, `@tag`...) and the default values of input object fields are not part of the output, and the types of directive arguments are `null`, as the schema does not keep them.
+impl ::serde::Serialize for Schema {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let root_type = |name: &Option<String>| {
+            name.as_ref()
+                .map(|name| ::serde_json::json!({ "name": name }))
+        };
+
+        let mut types: Vec<::serde_json::Value> = DEFAULT_SCALARS
+            .iter()
+            .map(|name| {
+                ::serde_json::json!({
+                    "kind": "SCALAR",
+                    "name": name,
+                    "description": null,
+                    "fields": null,
+                    "inputFields": null,
+                    "interfaces": null,
+                    "enumValues": null,
+                    "possibleTypes": null,
+                })
+            }).chain(
+                self.type_definitions()
+                    .into_iter()
+                    .map(|ty| self.type_json(ty)),
+            ).collect();
+        types.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+        let directives: Vec<::serde_json::Value> = self
+            .directives
+            .values()
+            .map(|directive| {
+                let args: Vec<::serde_json::Value> = directive
+                    .arguments
+                    .iter()
+                    .map(|name| {
+                        ::serde_json::json!({
+                            "name": name,
+                            "description": null,
+                            "type": null,
+                            "defaultValue": null,
+                        })
+                    }).collect();
+                ::serde_json::json!({
+                    "name": directive.name,
+                    "description": directive.description,
+                    "locations": directive.locations,
+                    "args": args,
+                })
+            }).collect();
+
+        ::serde_json::json!({
+            "__schema": {
+                "queryType": root_type(&self.query_type),
+                "mutationType": root_type(&self.mutation_type),
+                "subscriptionType": root_type(&self.subscription_type),
+                "types": types,
+                "directives": directives,
+            }
+        }).serialize(serializer)
+    }
+}
+
+impl Schema {
+    fn type_json(&self, ty: TypeDef) -> ::serde_json::Value {
+        let named_type_refs = |mut names: Vec<&String>| {
+            names.sort();
+            names
+                .into_iter()
+                .map(|name| self.nullable_type_ref_json(&FieldType::Named(name.clone())))
+                .collect::<Vec<_>>()
+        };
+        let (kind, description) = match ty {
+            TypeDef::Enum(enm) => ("ENUM", &enm.description),
+            TypeDef::Input(input) => ("INPUT_OBJECT", &input.description),
+            TypeDef::Interface(iface) => ("INTERFACE", &iface.description),
+            TypeDef::Object(obj) => ("OBJECT", &obj.description),
+            TypeDef::Scalar(scalar) => ("SCALAR", &scalar.description),
+            TypeDef::Union(union) => ("UNION", &union.description),
+        };
+        let mut json = ::serde_json::json!({
+            "kind": kind,
+            "name": ty.name(),
+            "description": description,
+            "fields": null,
+            "inputFields": null,
+            "interfaces": null,
+            "enumValues": null,
+            "possibleTypes": null,
+        });
+
+        match ty {
+            TypeDef::Enum(enm) => {
+                json["enumValues"] = enm
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        ::serde_json::json!({
+                            "name": variant.name,
+                            "description": variant.description,
+                            "isDeprecated": false,
+                            "deprecationReason": null,
+                        })
+                    }).collect();
+            }
+            TypeDef::Input(input) => {
+                let mut fields: Vec<_> = input.fields.values().collect();
+                fields.sort_by_key(|field| &field.name);
+                json["inputFields"] = fields
+                    .into_iter()
+                    .map(|field| {
+                        ::serde_json::json!({
+                            "name": field.name,
+                            "description": field.description,
+                            "type": self.type_ref_json(&field.type_),
+                            "defaultValue": null,
+                        })
+                    }).collect();
+            }
+            TypeDef::Interface(iface) => {
+                json["fields"] = self.fields_json(&iface.fields);
+                json["possibleTypes"] =
+                    named_type_refs(iface.implemented_by.iter().collect()).into();
+            }
+            TypeDef::Object(obj) => {
+                json["fields"] = self.fields_json(&obj.fields);
+                json["interfaces"] = named_type_refs(
+                    self.interfaces
+                        .values()
+                        .filter(|iface| iface.implemented_by.contains(&obj.name))
+                        .map(|iface| &iface.name)
+                        .collect(),
+                ).into();
+            }
+            TypeDef::Scalar(_) => (),
+            TypeDef::Union(union) => {
+                json["possibleTypes"] = named_type_refs(union.variants.iter().collect()).into();
+            }
+        }
+
+        json
+    }
+
+    fn fields_json(&self, fields: &[GqlObjectField]) -> ::serde_json::Value {
+        fields
+            .iter()
+            .filter(|field| field.name != ::constants::TYPENAME_FIELD)
+            .map(|field| {
+                let args: Vec<::serde_json::Value> = field
+                    .arguments
+                    .iter()
+                    .map(|arg| {
+                        let (is_deprecated, deprecation_reason) = match &arg.deprecation {
+                            DeprecationStatus::Current => (false, None),
+                            DeprecationStatus::Deprecated(reason) => (true, reason.as_ref()),
+                        };
+                        ::serde_json::json!({
+                            "name": arg.name,
+                            "description": null,
+                            "type": self.type_ref_json(&arg.type_),
+                            "defaultValue": arg.default_value,
+                            "isDeprecated": is_deprecated,
+                            "deprecationReason": deprecation_reason,
+                        })
+                    }).collect();
+                let (is_deprecated, deprecation_reason) = match &field.deprecation {
+                    DeprecationStatus::Current => (false, None),
+                    DeprecationStatus::Deprecated(reason) => (true, reason.as_ref()),
+                };
+                ::serde_json::json!({
+                    "name": field.name,
+                    "description": field.description,
+                    "args": args,
+                    "type": self.type_ref_json(&field.type_),
+                    "isDeprecated": is_deprecated,
+                    "deprecationReason": deprecation_reason,
+                })
+            }).collect()
+    }
+
+    /// Field types are non-null unless wrapped in [FieldType::Optional].
+    fn type_ref_json(&self, field_type: &FieldType) -> ::serde_json::Value {
+        let nullable = match field_type {
+            FieldType::Optional(inner) => return self.nullable_type_ref_json(inner),
+            _ => self.nullable_type_ref_json(field_type),
+        };
+        ::serde_json::json!({ "kind": "NON_NULL", "name": null, "ofType": nullable })
+    }
+
+    fn nullable_type_ref_json(&self, field_type: &FieldType) -> ::serde_json::Value {
+        match field_type {
+            FieldType::Optional(inner) => self.nullable_type_ref_json(inner),
+            FieldType::Vector(inner) => ::serde_json::json!({
+                "kind": "LIST",
+                "name": null,
+                "ofType": self.type_ref_json(inner),
+            }),
+            FieldType::Named(name) => {
+                let kind = match self.type_definition(name) {
+                    Some(TypeDef::Enum(_)) => "ENUM",
+                    Some(TypeDef::Input(_)) => "INPUT_OBJECT",
+                    Some(TypeDef::Interface(_)) => "INTERFACE",
+                    Some(TypeDef::Object(_)) => "OBJECT",
+                    Some(TypeDef::Union(_)) => "UNION",
+                    Some(TypeDef::Scalar(_)) | None => "SCALAR",
+                };
+                ::serde_json::json!({ "kind": kind, "name": name, "ofType": null })
+            }
+        }
+    }
+}
+
+/// An error returned by [Schema::from_sdl] and [Schema::from_introspection_json].
+#[derive(Debug)]
+pub enum ParseError {
+    /// The schema definition language document is invalid.
+    Sdl(graphql_parser::schema::ParseError),
+    /// The introspection response is not valid JSON, or does not have the expected shape.
+    Json(::serde_json::Error),
+    /// The schema is not consistent, see [Schema::validate].
+    Invalid(Vec<SchemaError>),
+}
+
+impl ::std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match self {
+            ParseError::Sdl(err) => write!(f, "Invalid schema: {}", err),
+            ParseError::Json(err) => write!(f, "Invalid introspection response: {}", err),
+            ParseError::Invalid(errors) => {
+                write!(f, "Invalid schema: {}", errors.iter().format("; "))
+            }
+        }
+    }
+}
+
+impl failure::Fail for ParseError {}
+
+/// An error found while validating a schema, see [Schema::validate] and [SchemaBuilder::build].
+#[derive(Debug, PartialEq)]
+pub enum SchemaError {
+    /// A type is referenced but never defined.
+    UnknownType {
+        /// The name of the missing type.
+        type_name: String,
+        /// Where the type is referenced, e.g. `User.friends` or `schema.query`.
+        referenced_by: String,
+    },
+    /// An object implementing an interface does not declare one of the interface fields.
+    MissingInterfaceField {
+        /// The name of the interface.
+        interface: String,
+        /// The name of the object implementing it.
+        implementor: String,
+        /// The name of the missing field.
+        field: String,
+    },
+    /// A member of a union is not an object type.
+    NonObjectUnionMember {
+        /// The name of the union.
+        union_name: String,
+        /// The name of the member type.
+        member: String,
+    },
+    /// An input object inherits from a type that is not an input object, see [Schema::from_sdl_with_input_inheritance].
+    NonInputParent {
+        /// The name of the input object.
+        input: String,
+        /// The name of the type it inherits from.
+        parent: String,
+    },
+    /// Input objects inherit from each other in a cycle, see [Schema::from_sdl_with_input_inheritance].
+    InputInheritanceCycle {
+        /// The input objects in the cycle, each one inheriting from the next and the last one from the first.
+        cycle: Vec<String>,
+    },
+}
+
+impl ::std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match self {
+            SchemaError::UnknownType {
+                type_name,
+                referenced_by,
+            } => write!(
+                f,
+                "Unknown type `{}` referenced by `{}`",
+                type_name, referenced_by
+            ),
+            SchemaError::MissingInterfaceField {
+                interface,
+                implementor,
+                field,
+            } => write!(
+                f,
+                "`{}` implements `{}` but does not declare its field `{}`",
+                implementor, interface, field
+            ),
+            SchemaError::NonObjectUnionMember { union_name, member } => write!(
+                f,
+                "The member `{}` of the union `{}` is not an object type",
+                member, union_name
+            ),
+            SchemaError::NonInputParent { input, parent } => write!(
+                f,
+                "The input object `{}` implements `{}`, which is not an input object",
+                input, parent
+            ),
+            SchemaError::InputInheritanceCycle { cycle } => write!(
+                f,
+                "The input objects `{}` inherit from each other in a cycle",
+                cycle.join("` -> `")
+            ),
+        }
+    }
+}
+
+impl failure::Fail for SchemaError {}
+
+/// Builds a [Schema] one type definition at a time, for tests and tooling that do not start from a schema file.
+///
+/// ```
+/// # extern crate graphql_client_codegen;
+/// # extern crate graphql_parser;
+/// use graphql_client_codegen::schema::SchemaBuilder;
+/// use graphql_parser::schema::Type;
+///
+/// let schema = SchemaBuilder::new()
+///     .query_type("Query")
+///     .scalar("DateTime")
+///     .enum_type("Color", &["RED", "GREEN"])
+///     .object(
+///         "Query",
+///         &[
+///             ("now", Type::NamedType("DateTime".to_string())),
+///             ("favoriteColor", Type::NamedType("Color".to_string())),
+///         ],
+///     ).build()
+///     .unwrap();
+/// # let _ = schema;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaBuilder {
+    definitions: Vec<schema::Definition>,
+    schema_definition: Option<schema::SchemaDefinition>,
+}
+
+impl SchemaBuilder {
+    /// An empty builder.
+    pub fn new() -> SchemaBuilder {
+        SchemaBuilder::default()
+    }
+
+    /// Adds any type definition parsed by (or built for) `graphql_parser`.
+    pub fn type_definition(mut self, definition: schema::TypeDefinition) -> SchemaBuilder {
+        self.definitions
+            .push(schema::Definition::TypeDefinition(definition));
+        self
+    }
+
+    /// Adds a custom scalar.
+    pub fn scalar(self, name: &str) -> SchemaBuilder {
+        self.type_definition(schema::TypeDefinition::Scalar(schema::ScalarType {
+            position: Default::default(),
+            description: None,
+            name: name.to_string(),
+            directives: Vec::new(),
+        }))
+    }
+
+    /// Adds an enum with the given values.
+    pub fn enum_type(self, name: &str, values: &[&str]) -> SchemaBuilder {
+        self.type_definition(schema::TypeDefinition::Enum(schema::EnumType {
+            position: Default::default(),
+            description: None,
+            name: name.to_string(),
+            directives: Vec::new(),
+            values: values
+                .iter()
+                .map(|value| schema::EnumValue {
+                    position: Default::default(),
+                    description: None,
+                    name: value.to_string(),
+                    directives: Vec::new(),
+                }).collect(),
+        }))
+    }
+
+    /// Adds an object type with the given fields, without arguments.
+    pub fn object(self, name: &str, fields: &[(&str, schema::Type)]) -> SchemaBuilder {
+        self.type_definition(schema::TypeDefinition::Object(schema::ObjectType {
+            position: Default::default(),
+            description: None,
+            name: name.to_string(),
+            implements_interfaces: Vec::new(),
+            directives: Vec::new(),
+            fields: fields
+                .iter()
+                .map(|(field_name, field_type)| schema::Field {
+                    position: Default::default(),
+                    description: None,
+                    name: field_name.to_string(),
+                    arguments: Vec::new(),
+                    field_type: field_type.clone(),
+                    directives: Vec::new(),
+                }).collect(),
+        }))
+    }
+
+    /// Sets the root query type.
+    pub fn query_type(self, name: &str) -> SchemaBuilder {
+        self.root_type(|definition| definition.query = Some(name.to_string()))
+    }
+
+    /// Sets the root mutation type.
+    pub fn mutation_type(self, name: &str) -> SchemaBuilder {
+        self.root_type(|definition| definition.mutation = Some(name.to_string()))
+    }
+
+    /// Sets the root subscription type.
+    pub fn subscription_type(self, name: &str) -> SchemaBuilder {
+        self.root_type(|definition| definition.subscription = Some(name.to_string()))
+    }
+
+    fn root_type<F: FnOnce(&mut schema::SchemaDefinition)>(mut self, set: F) -> SchemaBuilder {
+        set(self
+            .schema_definition
+            .get_or_insert_with(|| schema::SchemaDefinition {
+                position: Default::default(),
+                directives: Vec::new(),
+                query: None,
+                mutation: None,
+                subscription: None,
+            }));
+        self
+    }
+
+    /// Checks that every referenced type is defined, then builds the [Schema].
+    pub fn build(self) -> Result<Schema, SchemaError> {
+        let mut definitions = self.definitions;
+        definitions.extend(
+            self.schema_definition
+                .map(schema::Definition::SchemaDefinition),
+        );
+        let document = schema::Document { definitions };
+
+        validate_references(&document)?;
+
+        Ok(Schema::from(document))
+    }
+}
+
+/// Checks that all the types referenced in the document are defined in it, or are built-in scalars.
+fn validate_references(document: &schema::Document) -> Result<(), SchemaError> {
+    let defined: BTreeSet<&str> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            schema::Definition::TypeDefinition(ty) => Some(type_definition_name(ty)),
+            _ => None,
+        }).chain(DEFAULT_SCALARS.iter().cloned())
+        .collect();
+
+    let check = |type_name: &str, referenced_by: &str| {
+        if defined.contains(type_name) {
+            Ok(())
+        } else {
+            Err(SchemaError::UnknownType {
+                type_name: type_name.to_string(),
+                referenced_by: referenced_by.to_string(),
+            })
+        }
+    };
+    let check_fields = |parent: &str, fields: &[schema::Field]| {
+        for field in fields {
+            let path = format!("{}.{}", parent, field.name);
+            check(&innermost_name(&field.field_type), &path)?;
+            for argument in &field.arguments {
+                check(
+                    &innermost_name(&argument.value_type),
+                    &format!("{}({}:)", path, argument.name),
+                )?;
+            }
+        }
+        Ok(())
+    };
+
+    for definition in &document.definitions {
+        match definition {
+            schema::Definition::TypeDefinition(schema::TypeDefinition::Object(obj)) => {
+                for iface in &obj.implements_interfaces {
+                    check(iface, &obj.name)?;
+                }
+                check_fields(&obj.name, &obj.fields)?;
+            }
+            schema::Definition::TypeDefinition(schema::TypeDefinition::Interface(iface)) => {
+                check_fields(&iface.name, &iface.fields)?;
+            }
+            schema::Definition::TypeDefinition(schema::TypeDefinition::InputObject(input)) => {
+                for field in &input.fields {
+                    check(
+                        &innermost_name(&field.value_type),
+                        &format!("{}.{}", input.name, field.name),
+                    )?;
+                }
+            }
+            schema::Definition::TypeDefinition(schema::TypeDefinition::Union(union)) => {
+                for member in &union.types {
+                    check(member, &union.name)?;
+                }
+            }
+            schema::Definition::SchemaDefinition(definition) => {
+                let roots = [
+                    ("schema.query", &definition.query),
+                    ("schema.mutation", &definition.mutation),
+                    ("schema.subscription", &definition.subscription),
+                ];
+                for (referenced_by, root) in roots.iter() {
+                    if let Some(root) = root {
+                        check(root, referenced_by)?;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn type_definition_name(definition: &schema::TypeDefinition) -> &str {
+    match definition {
+        schema::TypeDefinition::Scalar(scalar) => &scalar.name,
+        schema::TypeDefinition::Object(obj) => &obj.name,
+        schema::TypeDefinition::Interface(iface) => &iface.name,
+        schema::TypeDefinition::Union(union) => &union.name,
+        schema::TypeDefinition::Enum(enm) => &enm.name,
+        schema::TypeDefinition::InputObject(input) => &input.name,
+    }
+}
+
+fn innermost_name(ty: &schema::Type) -> String {
+    FieldType::from(ty.clone()).inner_name_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use constants::*;
 
+    #[test]
+    fn from_sdl_and_from_introspection_json_parse_schemas() {
+        let sdl = Schema::from_sdl("schema { query: Query } type Query { name: String }").unwrap();
+        assert_eq!(sdl.query_type, Some("Query".to_string()));
+
+        let json =
+            Schema::from_introspection_json(include_str!("tests/github_schema.json")).unwrap();
+        assert_eq!(json.query_type, Some("Query".to_string()));
+    }
+
+    #[test]
+    fn from_sdl_and_from_introspection_json_report_invalid_input() {
+        match Schema::from_sdl("type Query {") {
+            Err(ParseError::Sdl(_)) => (),
+            other => panic!("expected an SDL error, got {:?}", other.map(|_| ())),
+        }
+
+        let err = Schema::from_introspection_json("{ nope").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid introspection response: "));
+    }
+
+    #[test]
+    fn input_objects_can_inherit_with_input_inheritance() {
+        let sdl = "
+            schema { query: Query }
+            type Query { version: String }
+            input Named { name: String }
+            input NewUser implements Named { email: String }
+        ";
+        assert!(Schema::from_sdl(sdl).is_err());
+
+        let schema = Schema::from_sdl_with_input_inheritance(sdl).unwrap();
+        assert_eq!(schema.inputs["NewUser"].parents, vec!["Named".to_string()]);
+        assert!(schema.inputs["Named"].parents.is_empty());
+
+        match Schema::from_sdl_with_input_inheritance(
+            &sdl.replace("NewUser implements Named", "NewUser implements Query"),
+        ) {
+            Err(ParseError::Invalid(errors)) => assert_eq!(
+                errors,
+                vec![SchemaError::NonInputParent {
+                    input: "NewUser".to_string(),
+                    parent: "Query".to_string(),
+                }]
+            ),
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn input_inheritance_cycles_are_rejected() {
+        let sdl = "
+            schema { query: Query }
+            type Query { version: String }
+            input Named implements Timestamped { name: String }
+            input Timestamped implements NewUser { createdAt: String }
+            input NewUser implements Named { email: String }
+            input Selfish implements Selfish { me: String }
+            input Child implements Named { age: Int }
+        ";
+        match Schema::from_sdl_with_input_inheritance(sdl) {
+            Err(ParseError::Invalid(errors)) => {
+                assert_eq!(
+                    errors,
+                    vec![
+                        SchemaError::InputInheritanceCycle {
+                            cycle: vec![
+                                "Named".to_string(),
+                                "Timestamped".to_string(),
+                                "NewUser".to_string(),
+                            ],
+                        },
+                        SchemaError::InputInheritanceCycle {
+                            cycle: vec!["Selfish".to_string()],
+                        },
+                    ]
+                );
+                assert_eq!(
+                    errors[0].to_string(),
+                    "The input objects `Named` -> `Timestamped` -> `NewUser` inherit from each other in a cycle"
+                );
+            }
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn inconsistent_schemas_are_rejected() {
+        let sdl = r#"
+            schema { query: Query }
+            type Query { node(id: ID!, filter: Filter): Node, search: SearchResult }
+            interface Node { id: ID! name: String }
+            type User implements Node { id: ID! }
+            union SearchResult = User | Node | Ghost
+        "#;
+        let schema = Schema::from(graphql_parser::parse_schema(sdl).unwrap());
+
+        assert_eq!(
+            schema.validate(),
+            vec![
+                SchemaError::UnknownType {
+                    type_name: "Filter".to_string(),
+                    referenced_by: "Query.node(filter:)".to_string(),
+                },
+                SchemaError::MissingInterfaceField {
+                    interface: "Node".to_string(),
+                    implementor: "User".to_string(),
+                    field: "name".to_string(),
+                },
+                SchemaError::UnknownType {
+                    type_name: "Ghost".to_string(),
+                    referenced_by: "SearchResult".to_string(),
+                },
+                SchemaError::NonObjectUnionMember {
+                    union_name: "SearchResult".to_string(),
+                    member: "Node".to_string(),
+                },
+            ]
+        );
+
+        let err = Schema::from_sdl(sdl).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "Invalid schema: Unknown type `Filter` referenced by `Query.node(filter:)`; `User` implements `Node` but does not declare its field `name`; "
+        ));
+    }
+
+    #[test]
+    fn schemas_round_trip_through_introspection_json() {
+        let sdl = Schema::from_sdl(
+            r#"
+            schema { query: Query }
+            directive @cached(ttl: Int) on FIELD
+            """A thing with a name"""
+            interface Named { name: String! }
+            type User implements Named {
+                name: String!
+                friends(first: Int = 10, after: ID): [User!]
+                age: Int @deprecated(reason: "private")
+            }
+            type Group implements Named { name: String! }
+            union Member = User | Group
+            enum Role { ADMIN, USER }
+            input Filter { role: Role!, names: [String] }
+            scalar DateTime
+            type Query { members(filter: Filter): [Member]!, now: DateTime }
+            "#,
+        ).unwrap();
+        let json = ::serde_json::to_string(&sdl).unwrap();
+        assert_eq!(Schema::from_introspection_json(&json).unwrap(), sdl);
+
+        let github =
+            Schema::from_introspection_json(include_str!("tests/github_schema.json")).unwrap();
+        let json = ::serde_json::to_string(&github).unwrap();
+        assert_eq!(Schema::from_introspection_json(&json).unwrap(), github);
+    }
+
+    #[test]
+    fn directive_definitions_are_collected() {
+        let sdl = Schema::from_sdl(
+            r#"
+            directive @cached(ttl: Int, scope: String) on FIELD | FRAGMENT_SPREAD
+            type Query { name: String }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            sdl.directive_definitions()["cached"],
+            DirectiveDefinition {
+                name: "cached".to_string(),
+                description: None,
+                arguments: vec!["ttl".to_string(), "scope".to_string()],
+                locations: vec!["FIELD".to_string(), "FRAGMENT_SPREAD".to_string()],
+            }
+        );
+        assert!(sdl.defines_directive("cached"));
+        assert!(sdl.defines_directive("skip"));
+        assert!(!sdl.defines_directive("live"));
+
+        let json =
+            Schema::from_introspection_json(include_str!("tests/github_schema.json")).unwrap();
+        let include = &json.directive_definitions()["include"];
+        assert_eq!(include.arguments, vec!["if".to_string()]);
+        assert_eq!(
+            include.locations,
+            vec![
+                "FIELD".to_string(),
+                "FRAGMENT_SPREAD".to_string(),
+                "INLINE_FRAGMENT".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn linked_specs_are_read_from_the_schema_definition() {
+        let schema = Schema::from_sdl(
+            r#"
+            schema @link(url: "https://specs.apollo.dev/federation/v2.0", import: ["@key"]) {
+                query: Query
+            }
+            type Query { name: String }
+            "#,
+        ).unwrap();
+
+        assert_eq!(schema.linked_specs().len(), 1);
+        assert_eq!(
+            schema.linked_specs()[0].url,
+            "https://specs.apollo.dev/federation/v2.0"
+        );
+        assert!(schema.imports_directive("key"));
+        assert!(!schema.imports_directive("external"));
+    }
+
+    #[test]
+    fn schema_builder_builds_valid_schemas() {
+        let schema = SchemaBuilder::new()
+            .query_type("Query")
+            .scalar("DateTime")
+            .enum_type("Color", &["RED", "GREEN"])
+            .object(
+                "Query",
+                &[
+                    ("now", schema::Type::NamedType("DateTime".to_string())),
+                    (
+                        "colors",
+                        schema::Type::ListType(Box::new(schema::Type::NonNullType(Box::new(
+                            schema::Type::NamedType("Color".to_string()),
+                        )))),
+                    ),
+                    ("name", schema::Type::NamedType("String".to_string())),
+                ],
+            ).build()
+            .unwrap();
+
+        assert_eq!(schema.query_type, Some("Query".to_string()));
+        assert_eq!(
+            schema
+                .type_definitions()
+                .iter()
+                .map(|ty| ty.name())
+                .collect::<Vec<_>>(),
+            vec!["Color", "DateTime", "Query"]
+        );
+        assert_eq!(schema.enums["Color"].variants.len(), 2);
+        assert_eq!(
+            schema.objects["Query"]
+                .fields
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![TYPENAME_FIELD, "now", "colors", "name"]
+        );
+    }
+
+    #[test]
+    fn schema_builder_rejects_unknown_types() {
+        let err = SchemaBuilder::new()
+            .object(
+                "User",
+                &[(
+                    "friends",
+                    schema::Type::ListType(Box::new(schema::Type::NamedType(
+                        "Person".to_string(),
+                    ))),
+                )],
+            ).build()
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            SchemaError::UnknownType {
+                type_name: "Person".to_string(),
+                referenced_by: "User.friends".to_string(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Unknown type `Person` referenced by `User.friends`"
+        );
+
+        let err = SchemaBuilder::new().query_type("Query").build().unwrap_err();
+
+        assert_eq!(
+            err,
+            SchemaError::UnknownType {
+                type_name: "Query".to_string(),
+                referenced_by: "schema.query".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn types_with_tag_works() {
+        let sdl = r#"
+            type Query {
+                users: [User!]!
+                orders: [Order!]! @tag(name: "internal")
+            }
+
+            type User @tag(name: "public") @tag(name: "partner") {
+                id: ID!
+            }
+
+            type Order {
+                id: ID!
+                total: MoneyAmount @tag(name: "partner")
+            }
+
+            scalar MoneyAmount @tag(name: "partner")
+
+            enum Currency @tag(name: "internal") {
+                EUR
+            }
+
+            input OrderFilter {
+                currency: Currency @tag(name: "internal")
+            }
+
+            union SearchResult @tag(name: "public") = User | Order
+        "#;
+        let schema = Schema::from(graphql_parser::parse_schema(sdl).unwrap());
+
+        let names = |tag| -> Vec<&str> {
+            schema
+                .types_with_tag(tag)
+                .iter()
+                .map(|ty| ty.name())
+                .collect()
+        };
+
+        assert_eq!(names("public"), vec!["SearchResult", "User"]);
+        assert_eq!(names("partner"), vec!["MoneyAmount", "Order", "User"]);
+        assert_eq!(names("internal"), vec!["Currency", "OrderFilter", "Query"]);
+        assert!(names("unknown").is_empty());
+
+        assert_eq!(
+            schema.objects["User"].directives.tags,
+            vec!["public".to_string(), "partner".to_string()]
+        );
+    }
+
+    #[test]
+    fn accessible_types_leave_out_inaccessible_types() {
+        let schema = Schema::from_sdl(
+            "
+            type Query { user: User }
+            type User { id: ID! secret: String @inaccessible }
+            type InternalAudit @inaccessible { id: ID! }
+            ",
+        )
+        .unwrap();
+
+        let names: Vec<&str> = schema.accessible_types().map(|ty| ty.name()).collect();
+        assert_eq!(names, vec!["Query", "User"]);
+    }
+
     #[test]
     fn build_schema_works() {
         let gql_schema = include_str!("tests/star_wars_schema.graphql");
@@ -333,18 +1592,24 @@ mod tests {
                         name: TYPENAME_FIELD.to_string(),
                         type_: FieldType::Named(string_type()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "id".to_string(),
                         type_: FieldType::Named("ID".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "name".to_string(),
                         type_: FieldType::Named("String".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
@@ -355,12 +1620,33 @@ mod tests {
                             ))),
                         )))),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "friendsConnection".to_string(),
                         type_: FieldType::Named("FriendsConnection".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: vec![
+                            GqlFieldArgument {
+                                name: "after".to_string(),
+                                type_: FieldType::Optional(Box::new(FieldType::Named(
+                                    "ID".to_string()
+                                ))),
+                                default_value: None,
+                                deprecation: DeprecationStatus::Current,
+                            },
+                            GqlFieldArgument {
+                                name: "first".to_string(),
+                                type_: FieldType::Optional(Box::new(FieldType::Named(
+                                    "Int".to_string()
+                                ))),
+                                default_value: None,
+                                deprecation: DeprecationStatus::Current,
+                            },
+                        ],
                     },
                     GqlObjectField {
                         description: None,
@@ -369,6 +1655,8 @@ mod tests {
                             FieldType::Named("Episode".to_string()),
                         )))),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
@@ -377,8 +1665,11 @@ mod tests {
                             "String".to_string()
                         ))),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                 ],
+                directives: Directives::default(),
                 is_required: false.into(),
             })
         )
@@ -1,13 +1,99 @@
 use constants::*;
+use failure;
 use graphql_parser::query::SelectionSet;
+use graphql_parser::Pos;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct SelectionField {
     pub alias: Option<String>,
     pub name: String,
     pub fields: Selection,
+    /// Whether the field has a `@stream` directive: its items can arrive in later incremental payloads.
+    pub stream: bool,
+    /// Where the field is in the query document.
+    pub position: Pos,
 }
 
+/// Where an item is in a query document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceLocation {
+    /// The query file, when the document was read from one.
+    pub path: Option<PathBuf>,
+    /// Starting from 1.
+    pub line: usize,
+    /// Starting from 1.
+    pub column: usize,
+}
+
+/// An error about an item selected in the query document, with its location when it is known.
+#[derive(PartialEq)]
+pub struct SelectionError {
+    pub message: String,
+    pub location: Option<SourceLocation>,
+}
+
+impl SelectionError {
+    pub(crate) fn at(position: Pos, message: String) -> SelectionError {
+        SelectionError {
+            message,
+            location: Some(SourceLocation {
+                path: None,
+                line: position.line,
+                column: position.column,
+            }),
+        }
+    }
+
+    /// Records the query file in the location of `error`, if it is a `SelectionError`.
+    pub(crate) fn in_file(error: failure::Error, path: &Path) -> failure::Error {
+        match error.downcast::<SelectionError>() {
+            Ok(mut error) => {
+                if let Some(location) = &mut error.location {
+                    location.path = Some(path.to_path_buf());
+                }
+                error.into()
+            }
+            Err(error) => error,
+        }
+    }
+}
+
+// The derive panics with the `Debug` representation of errors.
+impl fmt::Debug for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            Some(SourceLocation {
+                path: Some(path),
+                line,
+                ..
+            }) => write!(
+                f,
+                "error in {} at line {}: {}",
+                path.display(),
+                line,
+                self.message
+            ),
+            Some(SourceLocation { line, column, .. }) => write!(
+                f,
+                "error at line {}, column {}: {}",
+                line, column, self.message
+            ),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl failure::Fail for SelectionError {}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct SelectionFragmentSpread {
     pub fragment_name: String,
@@ -44,6 +130,39 @@ impl Selection {
     pub fn extract_typename(&self) -> Option<&SelectionField> {
         self.0.iter().filter_map(|f| f.as_typename()).next()
     }
+
+    /// The names of the fragments spread anywhere in the selection, including in nested fields and inline fragments.
+    pub(crate) fn fragment_spreads(&self) -> BTreeSet<&str> {
+        let mut spreads = BTreeSet::new();
+        for item in &self.0 {
+            match item {
+                SelectionItem::Field(field) => spreads.extend(field.fields.fragment_spreads()),
+                SelectionItem::FragmentSpread(spread) => {
+                    spreads.insert(spread.fragment_name.as_str());
+                }
+                SelectionItem::InlineFragment(fragment) => {
+                    spreads.extend(fragment.fields.fragment_spreads())
+                }
+            }
+        }
+        spreads
+    }
+
+    /// Replaces the inline fragments on `type_name` itself (like `... on Query` at the root of a query) by their fields. They always apply, so there is nothing to choose between.
+    pub(crate) fn merge_inline_fragments_on(&self, type_name: &str) -> Selection {
+        let mut items = Vec::with_capacity(self.0.len());
+
+        for item in &self.0 {
+            match item {
+                SelectionItem::InlineFragment(fragment) if fragment.on == type_name => {
+                    items.extend(fragment.fields.merge_inline_fragments_on(type_name).0)
+                }
+                other => items.push(other.clone()),
+            }
+        }
+
+        Selection(items)
+    }
 }
 
 impl<'a> ::std::convert::From<&'a SelectionSet> for Selection {
@@ -58,6 +177,11 @@ impl<'a> ::std::convert::From<&'a SelectionSet> for Selection {
                     alias: f.alias.as_ref().map(|alias| alias.to_string()),
                     name: f.name.to_string(),
                     fields: (&f.selection_set).into(),
+                    stream: f
+                        .directives
+                        .iter()
+                        .any(|directive| directive.name == STREAM_DIRECTIVE),
+                    position: f.position,
                 }),
                 Selection::FragmentSpread(spread) => {
                     SelectionItem::FragmentSpread(SelectionFragmentSpread {
@@ -88,6 +212,61 @@ mod tests {
     use super::*;
     use graphql_parser;
 
+    #[test]
+    fn selection_errors_display_their_location() {
+        let error = SelectionError::at(Pos { line: 4, column: 7 }, "Unknown field".to_string());
+        assert_eq!(
+            error.to_string(),
+            "error at line 4, column 7: Unknown field"
+        );
+
+        let error = SelectionError::in_file(error.into(), Path::new("src/query.graphql"));
+        assert_eq!(
+            error.to_string(),
+            "error in src/query.graphql at line 4: Unknown field"
+        );
+
+        let other = SelectionError::in_file(format_err!("Unrelated"), Path::new("query.graphql"));
+        assert_eq!(other.to_string(), "Unrelated");
+    }
+
+    #[test]
+    fn inline_fragments_on_the_type_itself_are_merged() {
+        let field = |name: &str| {
+            SelectionItem::Field(SelectionField {
+                alias: None,
+                name: name.to_string(),
+                fields: Selection(Vec::new()),
+                stream: false,
+                position: Pos::default(),
+            })
+        };
+        let inline_fragment = |on: &str, fields| {
+            SelectionItem::InlineFragment(SelectionInlineFragment {
+                on: on.to_string(),
+                fields: Selection(fields),
+            })
+        };
+        let selection = Selection(vec![
+            field("a"),
+            inline_fragment(
+                "Query",
+                vec![field("b"), inline_fragment("Query", vec![field("c")])],
+            ),
+            inline_fragment("Dog", vec![field("d")]),
+        ]);
+
+        assert_eq!(
+            selection.merge_inline_fragments_on("Query"),
+            Selection(vec![
+                field("a"),
+                field("b"),
+                field("c"),
+                inline_fragment("Dog", vec![field("d")]),
+            ])
+        );
+    }
+
     #[test]
     fn selection_from_graphql_parser_selection_set() {
         let query = r##"
@@ -133,11 +312,21 @@ mod tests {
                         alias: None,
                         name: "isCat".to_string(),
                         fields: Selection(Vec::new()),
+                        stream: false,
+                        position: Pos {
+                            line: 4,
+                            column: 13
+                        },
                     }),
                     SelectionItem::Field(SelectionField {
                         alias: None,
                         name: "isHorse".to_string(),
                         fields: Selection(Vec::new()),
+                        stream: false,
+                        position: Pos {
+                            line: 5,
+                            column: 13
+                        },
                     }),
                     SelectionItem::FragmentSpread(SelectionFragmentSpread {
                         fragment_name: "Timestamps".to_string(),
@@ -146,6 +335,11 @@ mod tests {
                         alias: None,
                         name: "barks".to_string(),
                         fields: Selection(Vec::new()),
+                        stream: false,
+                        position: Pos {
+                            line: 7,
+                            column: 13
+                        },
                     }),
                     SelectionItem::InlineFragment(SelectionInlineFragment {
                         on: "Dog".to_string(),
@@ -153,19 +347,39 @@ mod tests {
                             alias: None,
                             name: "rating".to_string(),
                             fields: Selection(Vec::new()),
+                            stream: false,
+                            position: Pos {
+                                line: 9,
+                                column: 17
+                            },
                         })]),
                     }),
                     SelectionItem::Field(SelectionField {
                         alias: None,
                         name: "pawsCount".to_string(),
                         fields: Selection(Vec::new()),
+                        stream: false,
+                        position: Pos {
+                            line: 11,
+                            column: 13
+                        },
                     }),
                     SelectionItem::Field(SelectionField {
                         alias: Some("aliased".to_string()),
                         name: "sillyName".to_string(),
                         fields: Selection(Vec::new()),
+                        stream: false,
+                        position: Pos {
+                            line: 12,
+                            column: 13
+                        },
                     }),
                 ]),
+                stream: false,
+                position: Pos {
+                    line: 3,
+                    column: 11
+                },
             })])
         );
     }
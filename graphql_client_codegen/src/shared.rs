@@ -76,12 +76,16 @@ pub(crate) fn field_impls_for_selection(
                 let name = &selected.name;
                 let alias = selected.alias.as_ref().unwrap_or(name);
 
-                let ty = fields
-                    .iter()
-                    .find(|f| &f.name == name)
-                    .ok_or_else(|| format_err!("could not find field `{}`", name))?
-                    .type_
-                    .inner_name_string();
+                let field = fields.iter().find(|f| &f.name == name).ok_or_else(|| {
+                    SelectionError::at(
+                        selected.position,
+                        format!("could not find field `{}`", name),
+                    )
+                })?;
+                if context.skips_field(field) {
+                    return Ok(quote!());
+                }
+                let ty = field.type_.inner_name_string();
                 let prefix = format!("{}{}", prefix.to_camel_case(), alias.to_camel_case());
                 context.maybe_expand_field(&ty, &selected.fields, &prefix)
             } else {
@@ -109,34 +113,75 @@ pub(crate) fn response_fields_for_selection(
                     .iter()
                     .find(|field| &field.name == name)
                     .ok_or_else(|| {
-                        format_err!(
-                            "Could not find field `{}` on `{}`. Available fields: `{}`.",
-                            name.as_str(),
-                            type_name,
-                            schema_fields
-                                .iter()
-                                .map(|ref field| &field.name)
-                                .format("`, `"),
+                        SelectionError::at(
+                            f.position,
+                            format!(
+                                "Could not find field `{}` on `{}`. Available fields: `{}`.",
+                                name.as_str(),
+                                type_name,
+                                schema_fields
+                                    .iter()
+                                    .map(|ref field| &field.name)
+                                    .format("`, `"),
+                            ),
                         )
                     })?;
-                let ty = schema_field.type_.to_rust(
-                    context,
-                    &format!("{}{}", prefix.to_camel_case(), alias.to_camel_case()),
-                );
+                if context.skips_field(schema_field) {
+                    return Ok(quote!());
+                }
+                let field_prefix = format!("{}{}", prefix.to_camel_case(), alias.to_camel_case());
+                let semantic_non_null = &schema_field.directives.semantic_non_null;
+                let ty = if f.stream {
+                    if !schema_field.type_.is_list() {
+                        Err(SelectionError::at(
+                            f.position,
+                            format!(
+                                "@stream can only be used on list fields, not on {}.{}",
+                                type_name, name
+                            ),
+                        ))?
+                    }
+                    schema_field
+                        .type_
+                        .to_rust_streamed(context, &field_prefix, semantic_non_null)
+                } else {
+                    schema_field
+                        .type_
+                        .to_rust_semantic(context, &field_prefix, semantic_non_null)
+                };
 
-                Ok(render_object_field(
+                let field = render_object_field(
                     alias,
                     &ty,
                     schema_field.description.as_ref().map(|s| s.as_str()),
                     &schema_field.deprecation,
-                    &context.deprecation_strategy,
-                ))
+                    context.deprecation_strategy_for(type_name, name),
+                );
+
+                // The `serde_as` type follows the GraphQL type, with `Option` and `Vec`. serde_with
+                // has no adapters for `SemanticResult` and `StreamedField`, so these fields are left
+                // to the `Deserialize` impl of the scalar.
+                let serde_as =
+                    if f.stream || schema_field.type_.has_semantic_result(semantic_non_null) {
+                        None
+                    } else {
+                        context.serde_as_annotation(&schema_field.type_)
+                    };
+                match serde_as {
+                    Some(serde_as) if !field.is_empty() => Ok(quote!(#serde_as #field)),
+                    _ => Ok(field),
+                }
             }
             SelectionItem::FragmentSpread(fragment) => {
+                // The fragment fields are read from the same JSON object, each fragment from its
+                // own copy (see `fragment_spreads_deserialization`).
                 let field_name =
                     Ident::new(&fragment.fragment_name.to_snake_case(), Span::call_site());
                 context.require(&fragment.fragment_name);
-                let type_name = Ident::new(&fragment.fragment_name, Span::call_site());
+                let type_name = Ident::new(
+                    &context.prefixed_type_name(&fragment.fragment_name),
+                    Span::call_site(),
+                );
                 Ok(quote!{
                     #[serde(flatten)]
                     pub #field_name: #type_name
@@ -153,6 +198,112 @@ pub(crate) fn response_fields_for_selection(
         }).collect()
 }
 
+/// For the structs of selections with fragment spreads: `#[serde(try_from = ...)]` and the impl it uses, which reads the flattened fields (the fragment structs) from their own copy of the JSON object, and the other fields from another one. serde gives each key to only one of the flattened structs, so a field selected in two fragments, or both next to a spread and in the fragment, would be missing from the others. Without `Deserialize` in the response derives, both are empty.
+pub(crate) fn fragment_spreads_deserialization(
+    context: &QueryContext,
+    struct_name: &Ident,
+    fields: &[TokenStream],
+    selection: &Selection,
+) -> Result<(TokenStream, TokenStream), failure::Error> {
+    let has_fragment_spreads = selection
+        .0
+        .iter()
+        .any(|item| matches!(item, SelectionItem::FragmentSpread(_)));
+    if !has_fragment_spreads || !context.response_derives_deserialize() {
+        return Ok((quote!(), quote!()));
+    }
+
+    let fields: ::syn::FieldsNamed = ::syn::parse2(quote!({ #(#fields,)* }))?;
+    let is_flatten = |attr: &::syn::Attribute| match attr.interpret_meta() {
+        Some(::syn::Meta::List(list)) => {
+            list.ident == "serde"
+                && list.nested.iter().any(|nested| match nested {
+                    ::syn::NestedMeta::Meta(::syn::Meta::Word(word)) => word == "flatten",
+                    _ => false,
+                })
+        }
+        _ => false,
+    };
+    let (flattened, own): (Vec<_>, Vec<_>) = fields
+        .named
+        .into_iter()
+        .partition(|field| field.attrs.iter().any(is_flatten));
+    let flattened_names = flattened.iter().map(|field| &field.ident);
+    let own_names: Vec<_> = own.iter().map(|field| field.ident.clone()).collect();
+    let own_names_values = own_names.clone();
+
+    let core = context.core_path();
+    let serde_as = context.serde_as_attribute();
+    let attribute = quote!(#[serde(try_from = "::graphql_client::serde_json::Value")]);
+    let deserialization = quote! {
+        #[allow(deprecated)]
+        impl #core::convert::TryFrom<::graphql_client::serde_json::Value> for #struct_name {
+            type Error = ::graphql_client::serde_json::Error;
+
+            fn try_from(value: ::graphql_client::serde_json::Value) -> Result<Self, Self::Error> {
+                #serde_as
+                #[derive(Deserialize)]
+                struct OwnFields {
+                    #(#own,)*
+                }
+
+                let own: OwnFields = ::graphql_client::serde_json::from_value(value.clone())?;
+                Ok(#struct_name {
+                    #(#own_names: own.#own_names_values,)*
+                    #(#flattened_names: ::graphql_client::serde_json::from_value(value.clone())?,)*
+                })
+            }
+        }
+    };
+    Ok((attribute, deserialization))
+}
+
+/// Generates a `field_names()` function on the struct for `selection`, listing the response keys of its fields in selection order. Fields from fragment spreads are listed by the fragment struct.
+pub(crate) fn field_names_impl(
+    struct_name: &Ident,
+    type_name: &str,
+    schema_fields: &[GqlObjectField],
+    context: &QueryContext,
+    selection: &Selection,
+) -> TokenStream {
+    let field_names = selection.0.iter().filter_map(|item| match item {
+        SelectionItem::Field(f) => {
+            let schema_field = schema_fields.iter().find(|field| field.name == f.name)?;
+            if is_rendered(context, type_name, schema_field) {
+                Some(f.alias.as_ref().unwrap_or(&f.name))
+            } else {
+                None
+            }
+        }
+        SelectionItem::FragmentSpread(_) | SelectionItem::InlineFragment(_) => None,
+    });
+
+    quote! {
+        impl #struct_name {
+            /// The GraphQL names of the fields in this struct, in selection order.
+            pub fn field_names() -> &'static [&'static str] {
+                &[#(#field_names),*]
+            }
+        }
+    }
+}
+
+/// Whether the field of `type_name` is part of the generated struct, i.e. it is not skipped (see `QueryContext::skips_field`) or denied as deprecated.
+pub(crate) fn is_rendered(
+    context: &QueryContext,
+    type_name: &str,
+    schema_field: &GqlObjectField,
+) -> bool {
+    let denied = matches!(
+        (
+            &schema_field.deprecation,
+            context.deprecation_strategy_for(type_name, &schema_field.name),
+        ),
+        (DeprecationStatus::Deprecated(_), DeprecationStrategy::Deny)
+    );
+    !context.skips_field(schema_field) && !denied
+}
+
 /// Given the GraphQL schema name for an object/interface/input object field and
 /// the equivalent rust name, produces a serde annotation to map them during
 /// (de)serialization if it is necessary, otherwise an empty TokenStream.
@@ -0,0 +1,60 @@
+use proc_macro2::TokenStream;
+use std::path::Path;
+
+/// Generates a test comparing `generated_code` to the `insta` snapshot called `snapshot_name` in `snapshot_dir`.
+///
+/// The code is captured when the macro runs, so the test fails when codegen output changes.
+pub(crate) fn snapshot_test(
+    snapshot_name: &str,
+    snapshot_dir: &Path,
+    generated_code: &TokenStream,
+) -> TokenStream {
+    let snapshot_dir = snapshot_dir.to_string_lossy().into_owned();
+    let generated_code = generated_code.to_string();
+
+    quote! {
+        #[cfg(test)]
+        #[test]
+        fn generated_code_snapshot() {
+            let mut settings = ::insta::Settings::clone_current();
+            settings.set_snapshot_path(#snapshot_dir);
+            settings.set_prepend_module_to_snapshot(false);
+            settings.bind(|| {
+                ::insta::assert_snapshot!(#snapshot_name, #generated_code, "generated code");
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_test_works() {
+        let generated = snapshot_test(
+            "my_query",
+            Path::new("/queries/snapshots"),
+            &quote!(
+                pub struct ResponseData;
+            ),
+        )
+        .to_string();
+
+        assert_eq!(
+            generated,
+            quote! {
+                #[cfg(test)]
+                #[test]
+                fn generated_code_snapshot() {
+                    let mut settings = ::insta::Settings::clone_current();
+                    settings.set_snapshot_path("/queries/snapshots");
+                    settings.set_prepend_module_to_snapshot(false);
+                    settings.bind(|| {
+                        ::insta::assert_snapshot!("my_query", "pub struct ResponseData ;", "generated code");
+                    });
+                }
+            }.to_string()
+        );
+    }
+}
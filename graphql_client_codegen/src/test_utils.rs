@@ -0,0 +1,170 @@
+use failure;
+use graphql_parser;
+use graphql_parser::query::Document;
+use lint::{check_directives, check_fields, LintWarning};
+use schema::Schema;
+use std::fmt;
+
+/// A part of a query document that does not match the schema, see [check_query_matches_schema].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// The operation the problem was found in, if it is named.
+    pub operation: Option<String>,
+    /// The line of the offending item in the query document (starting from 1).
+    pub line: usize,
+    /// The column of the offending item in the query document (starting from 1).
+    pub column: usize,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl From<LintWarning> for ValidationError {
+    fn from(warning: LintWarning) -> Self {
+        ValidationError {
+            operation: warning.operation,
+            line: warning.line,
+            column: warning.column,
+            message: warning.message,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(operation) = &self.operation {
+            write!(f, "{}: ", operation)?;
+        }
+        write!(
+            f,
+            "{} (line {}, column {})",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl failure::Fail for ValidationError {}
+
+/// Checks the fields, fragments and directives of the query document against the schema, with the same checks as [lint::check_fields] and [lint::check_directives].
+pub fn check_query_matches_schema(
+    document: &Document,
+    schema: &Schema,
+) -> Result<(), Vec<ValidationError>> {
+    let mut warnings = check_fields(document, schema);
+    warnings.extend(check_directives(document, schema));
+    warnings.sort_by_key(|warning| (warning.line, warning.column));
+
+    if warnings.is_empty() {
+        Ok(())
+    } else {
+        Err(warnings.into_iter().map(ValidationError::from).collect())
+    }
+}
+
+/// Panics if the query is not valid GraphQL or does not match the schema, with a message listing the problems. Use [check_query_matches_schema] when you do not want to panic.
+///
+/// ```rust,ignore
+/// #[test]
+/// fn the_query_matches_the_schema() {
+///     let schema = graphql_client_codegen::read_schema("schema.graphql".as_ref()).unwrap();
+///     graphql_client_codegen::test_utils::assert_query_matches_schema(include_str!("query.graphql"), &schema);
+/// }
+/// ```
+pub fn assert_query_matches_schema(query: &str, schema: &Schema) {
+    let document = match graphql_parser::parse_query(query) {
+        Ok(document) => document,
+        Err(err) => panic!("The query is not valid GraphQL: {}", err),
+    };
+
+    if let Err(errors) = check_query_matches_schema(&document, schema) {
+        let errors: Vec<String> = errors.iter().map(|error| format!("- {}", error)).collect();
+        panic!(
+            "The query does not match the schema:\n{}",
+            errors.join("\n")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = "
+        schema { query: Query }
+        type Query { dogs: [Dog!]! pet: Pet }
+        type Dog implements Pet { name: String! owner: Person }
+        type Person { firstName: String! }
+        interface Pet { name: String! }
+        union SearchResult = Dog | Person
+    ";
+
+    #[test]
+    fn matching_queries_pass() {
+        let schema = Schema::from_sdl(SCHEMA).unwrap();
+
+        assert_query_matches_schema(
+            "
+            query Dogs { dogs { __typename ...DogName owner { firstName } } pet { ... on Dog { name } } }
+            fragment DogName on Dog { name }
+            ",
+            &schema,
+        );
+    }
+
+    #[test]
+    fn all_problems_are_reported() {
+        let schema = Schema::from_sdl(SCHEMA).unwrap();
+        let document = graphql_parser::parse_query(
+            "query Dogs {
+  dogs { name { length } owner ...Missing }
+  pet { age ... on Cat { name } }
+  cats
+}
+fragment Owner on Person { firstName @uppercase }
+",
+        )
+        .unwrap();
+        let messages: Vec<String> = check_query_matches_schema(&document, &schema)
+            .unwrap_err()
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "Dogs: field `name` of type `String` cannot have a selection of subfields (line 2, column 10)",
+                "Dogs: field `owner` of type `Person` must have a selection of subfields (line 2, column 26)",
+                "Dogs: fragment `Missing` is not defined (line 2, column 35)",
+                "Dogs: field `age` is not defined on type `Pet` (line 3, column 9)",
+                "Dogs: type condition `Cat` is not an object, interface or union type (line 3, column 17)",
+                "Dogs: field `cats` is not defined on type `Query` (line 4, column 3)",
+                "directive `@uppercase` is not defined in the schema (line 6, column 38)",
+            ]
+        );
+    }
+
+    #[test]
+    fn validation_errors_have_the_position_of_the_problem() {
+        let schema = Schema::from_sdl(SCHEMA).unwrap();
+        let document = graphql_parser::parse_query("query Q { cats }").unwrap();
+
+        assert_eq!(
+            check_query_matches_schema(&document, &schema),
+            Err(vec![ValidationError {
+                operation: Some("Q".to_string()),
+                line: 1,
+                column: 11,
+                message: "field `cats` is not defined on type `Query`".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The query does not match the schema:\n- Q: field `cats` is not defined on type `Query` (line 1, column 11)"
+    )]
+    fn assertions_panic_with_the_problems() {
+        let schema = Schema::from_sdl(SCHEMA).unwrap();
+        assert_query_matches_schema("query Q { cats }", &schema);
+    }
+}
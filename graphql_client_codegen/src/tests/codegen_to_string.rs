@@ -0,0 +1,173 @@
+use deprecation::DeprecationStrategy;
+use schema::Schema;
+use {codegen_to_string, GraphQLClientDeriveOptions};
+
+const SCHEMA: &str = include_str!("star_wars_schema.graphql");
+const QUERY: &str = include_str!("star_wars_query.graphql");
+
+fn options() -> GraphQLClientDeriveOptions {
+    GraphQLClientDeriveOptions {
+        struct_name: "StarWarsQuery".to_string(),
+        response_derives: Some("Debug".to_string()),
+        deprecation_strategy: Some(DeprecationStrategy::Warn),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn codegen_to_string_generates_the_module() {
+    let schema = Schema::from_sdl(SCHEMA).unwrap();
+    let generated = codegen_to_string(&schema, QUERY, &options()).unwrap();
+
+    assert!(generated.starts_with("mod star_wars_query {"));
+    assert!(generated.contains("pub const OPERATION_NAME : & 'static str = \"StarWarsQuery\" ;"));
+    assert!(generated.contains("pub struct Variables { # [ serde ( rename = \"episodeForHero\" ) ] pub episode_for_hero : Episode , }"));
+    assert!(generated.contains("# [ derive ( Deserialize , Debug ) ] pub struct ResponseData { pub hero : Option < RustStarWarsQueryHero > , }"));
+    assert!(generated.contains("impl :: graphql_client :: GraphQLQuery for StarWarsQuery"));
+}
+
+#[test]
+fn codegen_to_string_reports_invalid_queries() {
+    let schema = Schema::from_sdl(SCHEMA).unwrap();
+
+    assert!(codegen_to_string(&schema, "query StarWarsQuery {", &options()).is_err());
+    assert!(codegen_to_string(&schema, "query StarWarsQuery { villain }", &options()).is_err());
+}
+
+#[test]
+fn federation_mode_documents_shareable_types() {
+    let schema = Schema::from_sdl(
+        "
+        schema { query: Query }
+        type Query { product: Product }
+        type Product @shareable { upc: String! price: Int @external }
+        ",
+    )
+    .unwrap();
+    let query = "query ProductQuery { product { upc price } }";
+    let mut options = options();
+    options.struct_name = "ProductQuery".to_string();
+
+    let generated = codegen_to_string(&schema, query, &options).unwrap();
+    assert!(generated.contains("pub price : Option < Int >"));
+    assert!(!generated.contains("@shareable"));
+
+    options.federation_mode = true;
+    let generated = codegen_to_string(&schema, query, &options).unwrap();
+    assert!(generated.contains(
+        "# [ doc = \"`@shareable`: other subgraphs can resolve this type too.\" ] pub struct RustProductQueryProduct { pub upc : String , }"
+    ));
+}
+
+#[test]
+fn only_required_fragments_are_generated() {
+    let schema = Schema::from_sdl(SCHEMA).unwrap();
+    let query = "
+        fragment HumanName on Human { name }
+        fragment UnusedHumanPlanet on Human { homePlanet }
+        fragment ZHumanDetails on Human { id ...HumanName }
+        query HumanQuery { human(id: \"1000\") { ...ZHumanDetails } }
+    ";
+    let mut options = options();
+    options.struct_name = "HumanQuery".to_string();
+
+    let generated = codegen_to_string(&schema, query, &options).unwrap();
+    assert!(generated.contains("pub struct ZHumanDetails {"));
+    assert!(generated.contains("pub struct HumanName {"));
+    assert!(!generated.contains("pub struct UnusedHumanPlanet"));
+}
+
+#[test]
+fn inaccessible_fields_are_not_generated() {
+    let schema = Schema::from_sdl(
+        "
+        schema { query: Query }
+        type Query { user: User }
+        type User { id: ID! secret: String @inaccessible audit: Audit }
+        type Audit @inaccessible { id: ID! }
+        ",
+    )
+    .unwrap();
+    let query = "query UserQuery { user { id secret audit { id } } }";
+    let mut options = options();
+    options.struct_name = "UserQuery".to_string();
+
+    let generated = codegen_to_string(&schema, query, &options).unwrap();
+    assert!(generated.contains("pub struct RustUserQueryUser { pub id : ID , }"));
+    assert!(!generated.contains("RustUserQueryUserAudit"));
+}
+
+#[test]
+fn inline_fragments_on_the_root_type_are_merged() {
+    let schema = Schema::from_sdl(SCHEMA).unwrap();
+    let query = "query RootFragmentQuery { ... on Query { human(id: \"1000\") { name } } }";
+    let mut options = options();
+    options.struct_name = "RootFragmentQuery".to_string();
+
+    let generated = codegen_to_string(&schema, query, &options).unwrap();
+    assert!(generated.contains(
+        "pub struct ResponseData { pub human : Option < RustRootFragmentQueryHuman > , }"
+    ));
+}
+
+#[test]
+fn query_validation_can_be_skipped() {
+    let schema = Schema::from_sdl(SCHEMA).unwrap();
+    let query = "
+        query StarWarsQuery { human(id: \"1000\") { id } }
+        query StarWarsQuery { human(id: \"1000\") { name } }
+    ";
+
+    assert!(codegen_to_string(&schema, query, &options()).is_err());
+
+    let mut options = options();
+    options.skip_query_validation = true;
+    let generated = codegen_to_string(&schema, query, &options).unwrap();
+    assert!(generated.contains("# [ deprecated ( note = \"skip_query_validation is set on StarWarsQuery: the query is not validated\" ) ]"));
+    assert!(generated.contains("pub struct RustStarWarsQueryHuman { pub id : ID , }"));
+}
+
+#[test]
+fn deserializer_impl_requires_serialize() {
+    let schema = Schema::from_sdl(SCHEMA).unwrap();
+    let mut options = options();
+    options.deserializer_impl = true;
+
+    let err = codegen_to_string(&schema, QUERY, &options).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "deserializer_impl requires Serialize in the response derives"
+    );
+
+    options.response_derives = Some("Serialize".to_string());
+    let generated = codegen_to_string(&schema, QUERY, &options).unwrap();
+    assert!(generated.contains("impl < 'de > :: serde :: Deserializer < 'de > for ResponseData"));
+}
+
+#[test]
+fn fragment_cycles_are_rejected() {
+    let schema = Schema::from_sdl(SCHEMA).unwrap();
+    let query = "
+        fragment HumanName on Human { name ...HumanId }
+        fragment HumanId on Human { id friends { ...HumanName } }
+        query StarWarsQuery { human(id: \"1000\") { ...HumanName } }
+    ";
+
+    let err = codegen_to_string(&schema, query, &options()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Fragment cycle: HumanId -> HumanName -> HumanId"
+    );
+}
+
+#[test]
+fn stream_is_rejected_on_fields_that_are_not_lists() {
+    let schema = Schema::from_sdl(SCHEMA).unwrap();
+    let query = "query StarWarsQuery { human(id: \"1000\") { name @stream } }";
+
+    let err = codegen_to_string(&schema, query, &options()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error at line 1, column 43: @stream can only be used on list fields, not on Human.name"
+    );
+}
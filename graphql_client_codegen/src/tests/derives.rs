@@ -0,0 +1,147 @@
+use deprecation::DeprecationStrategy;
+use std::path::PathBuf;
+use {generate_module_token_stream, GraphQLClientDeriveOptions};
+
+fn generate(variables_derives: Option<&str>, response_derives: Option<&str>) -> String {
+    generate_with(|options| {
+        options.variables_derives = variables_derives.map(String::from);
+        options.response_derives = response_derives.map(String::from);
+    })
+}
+
+fn generate_with<F: FnOnce(&mut GraphQLClientDeriveOptions)>(configure: F) -> String {
+    try_generate_with(configure).unwrap()
+}
+
+fn try_generate_with<F: FnOnce(&mut GraphQLClientDeriveOptions)>(
+    configure: F,
+) -> Result<String, failure::Error> {
+    let mut options = GraphQLClientDeriveOptions {
+        struct_name: "StarWarsQuery".to_string(),
+        deprecation_strategy: Some(DeprecationStrategy::Warn),
+        ..Default::default()
+    };
+    configure(&mut options);
+
+    generate_module_token_stream(
+        PathBuf::from("src/tests/star_wars_query.graphql"),
+        PathBuf::from("src/tests/star_wars_schema.graphql"),
+        Some(options),
+    )
+    .map(|generated| generated.to_string())
+}
+
+#[test]
+fn variables_and_response_derives_are_applied_separately() {
+    let generated = generate(Some("Debug, Hash"), Some("PartialEq"));
+
+    assert!(generated.contains("# [ derive ( Serialize , Debug , Hash ) ] pub struct Variables"));
+    assert!(generated.contains("# [ derive ( Deserialize , PartialEq ) ] pub struct ResponseData"));
+}
+
+#[test]
+fn no_extra_derives_by_default() {
+    let generated = generate(None, None);
+
+    assert!(generated.contains("# [ derive ( Serialize ) ] pub struct Variables"));
+    assert!(generated.contains("# [ derive ( Deserialize ) ] pub struct ResponseData"));
+}
+
+#[test]
+fn ord_derives_require_eq() {
+    let generated = generate_with(|options| {
+        options.response_derives = Some("PartialEq, Eq".to_string());
+        options.ord_derives = true;
+    });
+
+    assert!(generated.contains(
+        "# [ derive ( Deserialize , PartialEq , Eq , PartialOrd , Ord ) ] pub struct ResponseData"
+    ));
+    assert!(generated.contains("# [ derive ( Serialize ) ] pub struct Variables"));
+
+    let generated = generate_with(|options| {
+        options.response_derives = Some("PartialEq".to_string());
+        options.ord_derives = true;
+    });
+
+    assert!(generated.contains("# [ derive ( Deserialize , PartialEq ) ] pub struct ResponseData"));
+}
+
+#[test]
+fn max_query_depth_generates_deprecation_warnings() {
+    let generated = generate_with(|options| options.max_query_depth = Some(1));
+
+    assert!(generated.contains(
+        "# [ deprecated ( note = \"StarWarsQuery: field `name` is nested 2 levels deep, the maximum is 1 (line 3, column 5)\" ) ] const QUERY_DEPTH_WARNING_0 : ( ) = ( ) ; const _ : ( ) = QUERY_DEPTH_WARNING_0 ;"
+    ));
+    assert!(generated.contains("const QUERY_DEPTH_WARNING_1"));
+    assert!(!generated.contains("const QUERY_DEPTH_WARNING_2"));
+
+    let generated = generate_with(|options| options.max_query_depth = Some(2));
+
+    assert!(!generated.contains("QUERY_DEPTH_WARNING"));
+}
+
+#[test]
+fn warn_missing_fields_generates_deprecation_warnings() {
+    let generated = generate_with(|options| options.warn_missing_fields = true);
+
+    assert!(generated.contains(
+        "# [ deprecated ( note = \"StarWarsQuery: field `reviews` of type `Query` is not selected (line 1, column 1)\" ) ] const MISSING_FIELD_WARNING_0 : ( ) = ( ) ; const _ : ( ) = MISSING_FIELD_WARNING_0 ;"
+    ));
+    assert!(generated.contains(
+        "\"StarWarsQuery: field `id` of type `Character` is not selected (line 2, column 3)\""
+    ));
+    assert!(!generated.contains("field `name` of type `Character` is not selected"));
+
+    let generated = generate(None, None);
+
+    assert!(!generated.contains("MISSING_FIELD_WARNING"));
+}
+
+#[test]
+fn scalars_with_generates_serde_as_annotations() {
+    let generated = generate_with(|options| {
+        options.scalars_with = Some("String = ::serde_with::DisplayFromStr".to_string())
+    });
+
+    assert!(generated.contains(
+        "# [ :: serde_with :: serde_as ] # [ derive ( Deserialize ) ] pub struct ResponseData"
+    ));
+    assert!(generated.contains(
+        "# [ serde_as ( as = \"::serde_with::DisplayFromStr\" ) ] pub name : String"
+    ));
+    assert!(!generate(None, None).contains("serde_as"));
+}
+
+#[test]
+fn generate_structs_false_leaves_out_the_response_types() {
+    let generated = generate_with(|options| options.generate_structs = false);
+
+    assert!(generated.contains("pub struct Variables"));
+    assert!(generated.contains("pub enum Episode"));
+    assert!(generated.contains("type ResponseData = :: graphql_client :: serde_json :: Value ;"));
+    assert!(!generated.contains("pub struct ResponseData"));
+    assert!(!generated.contains("RustStarWarsQueryHero"));
+    assert!(!generated.contains("CACHE_MAX_AGE"));
+}
+
+#[test]
+fn generate_structs_false_rejects_options_on_the_response_types() {
+    let error = |configure: fn(&mut GraphQLClientDeriveOptions)| {
+        try_generate_with(|options| {
+            options.generate_structs = false;
+            configure(options);
+        })
+        .unwrap_err()
+        .to_string()
+    };
+
+    assert_eq!(
+        error(|options| options.into = Some("crate::Hero".to_string())),
+        "The into option needs the response types, so it cannot be used with generate_structs = \"false\""
+    );
+    assert!(error(|options| options.generate_mock = true).contains("generate_mock"));
+    assert!(error(|options| options.deserializer_impl = true).contains("deserializer_impl"));
+    assert!(error(|options| options.strict_response = true).contains("strict_response"));
+}
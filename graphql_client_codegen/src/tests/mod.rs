@@ -1 +1,3 @@
+mod codegen_to_string;
+mod derives;
 mod github;
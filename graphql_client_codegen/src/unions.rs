@@ -1,4 +1,5 @@
 use constants::*;
+use directives::Directives;
 use failure;
 use proc_macro2::{Ident, Span, TokenStream};
 use query::QueryContext;
@@ -8,8 +9,10 @@ use std::collections::BTreeSet;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GqlUnion {
+    pub name: String,
     pub description: Option<String>,
     pub variants: BTreeSet<String>,
+    pub directives: Directives,
     pub is_required: Cell<bool>,
 }
 
@@ -87,6 +90,52 @@ pub(crate) fn union_variants(
     Ok((variants, children_definitions, used_variants))
 }
 
+/// Lets the structs of the selected variants be converted into the union enum with `into()`.
+fn union_from_impls(struct_name: &Ident, prefix: &str, used_variants: &[String]) -> Vec<TokenStream> {
+    used_variants
+        .iter()
+        .map(|v| {
+            let variant_type = Ident::new(&format!("{}On{}", prefix, v), Span::call_site());
+            let v = Ident::new(v, Span::call_site());
+            quote! {
+                impl From<#variant_type> for #struct_name {
+                    fn from(variant: #variant_type) -> Self {
+                        #struct_name::#v(variant)
+                    }
+                }
+            }
+        }).collect()
+}
+
+/// Selected variants display their contents, the others their type name.
+fn union_display_impl(
+    core: &TokenStream,
+    struct_name: &Ident,
+    used_variants: &[String],
+    unused_variants: &[&String],
+) -> TokenStream {
+    let used_arms = used_variants.iter().map(|v| {
+        let v = Ident::new(v, Span::call_site());
+        quote!(#struct_name::#v(ref inner) => #core::fmt::Display::fmt(inner, f))
+    });
+    let unused_arms = unused_variants.iter().map(|v| {
+        let name = v.as_str();
+        let v = Ident::new(v, Span::call_site());
+        quote!(#struct_name::#v => f.write_str(#name))
+    });
+
+    quote! {
+        impl #core::fmt::Display for #struct_name {
+            fn fmt(&self, f: &mut #core::fmt::Formatter) -> #core::fmt::Result {
+                match *self {
+                    #(#used_arms,)*
+                    #(#unused_arms,)*
+                }
+            }
+        }
+    }
+}
+
 impl GqlUnion {
     pub(crate) fn response_for_selection(
         &self,
@@ -108,15 +157,33 @@ impl GqlUnion {
         let (mut variants, children_definitions, used_variants) =
             union_variants(selection, query_context, prefix)?;
 
-        variants.extend(
-            self.variants
-                .iter()
-                .filter(|v| used_variants.iter().find(|a| a == v).is_none())
-                .map(|v| {
-                    let v = Ident::new(v, Span::call_site());
-                    quote!(#v)
-                }),
-        );
+        let unused_variants: Vec<&String> = self
+            .variants
+            .iter()
+            .filter(|v| used_variants.iter().find(|a| a == v).is_none())
+            .collect();
+
+        variants.extend(unused_variants.iter().map(|v| {
+            let v = Ident::new(v, Span::call_site());
+            quote!(#v)
+        }));
+
+        if variants.is_empty() {
+            return Ok(self.placeholder_response(query_context, &struct_name));
+        }
+
+        let from_impls = union_from_impls(&struct_name, prefix, &used_variants);
+
+        let display_impl = if query_context.response_derives_display() {
+            Some(union_display_impl(
+                &query_context.core_path(),
+                &struct_name,
+                &used_variants,
+                &unused_variants,
+            ))
+        } else {
+            None
+        };
 
         Ok(quote!{
             #(#children_definitions)*
@@ -126,8 +193,85 @@ impl GqlUnion {
             pub enum #struct_name {
                 #(#variants),*
             }
+
+            #(#from_impls)*
+
+            #display_impl
         })
     }
+
+    /// Unions without members are placeholders in some generated schemas. An enum without variants cannot even be deserialized, so the type name of the value is kept in an `Other` variant.
+    fn placeholder_response(
+        &self,
+        query_context: &QueryContext,
+        struct_name: &Ident,
+    ) -> TokenStream {
+        let derives = query_context.response_enum_derives();
+        let core = query_context.core_path();
+        let doc = format!(
+            "`{}` is a placeholder union without members in the schema. `Other` holds the `__typename` of the value.",
+            self.name
+        );
+
+        let serialize_impl = if query_context.response_derives_serialize() {
+            Some(quote! {
+                impl ::serde::Serialize for #struct_name {
+                    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> #core::result::Result<S::Ok, S::Error> {
+                        use ::serde::ser::SerializeMap;
+
+                        match *self {
+                            #struct_name::Other(ref typename) => {
+                                let mut map = serializer.serialize_map(Some(1))?;
+                                map.serialize_entry("__typename", typename)?;
+                                map.end()
+                            }
+                        }
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
+        let display_impl = if query_context.response_derives_display() {
+            Some(quote! {
+                impl #core::fmt::Display for #struct_name {
+                    fn fmt(&self, f: &mut #core::fmt::Formatter) -> #core::fmt::Result {
+                        match *self {
+                            #struct_name::Other(ref typename) => f.write_str(typename),
+                        }
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
+        quote! {
+            #[doc = #doc]
+            #derives
+            pub enum #struct_name {
+                Other(String),
+            }
+
+            impl<'de> ::serde::Deserialize<'de> for #struct_name {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> #core::result::Result<Self, D::Error> {
+                    #[derive(Deserialize)]
+                    struct Typename {
+                        #[serde(rename = "__typename")]
+                        typename: String,
+                    }
+
+                    let Typename { typename } = Typename::deserialize(deserializer)?;
+                    Ok(#struct_name::Other(typename))
+                }
+            }
+
+            #serialize_impl
+
+            #display_impl
+        }
+    }
 }
 
 #[cfg(test)]
@@ -135,6 +279,7 @@ mod tests {
     use super::*;
     use deprecation::DeprecationStatus;
     use field_type::FieldType;
+    use graphql_parser::Pos;
     use objects::{GqlObject, GqlObjectField};
     use selection::*;
 
@@ -147,6 +292,8 @@ mod tests {
                     alias: None,
                     name: "firstName".to_string(),
                     fields: Selection(vec![]),
+                    stream: false,
+                    position: Pos::default(),
                 })]),
             }),
             SelectionItem::InlineFragment(SelectionInlineFragment {
@@ -155,6 +302,8 @@ mod tests {
                     alias: None,
                     name: "title".to_string(),
                     fields: Selection(vec![]),
+                    stream: false,
+                    position: Pos::default(),
                 })]),
             }),
         ];
@@ -162,8 +311,10 @@ mod tests {
         let selection = Selection(fields);
         let prefix = "Meow";
         let union = GqlUnion {
+            name: "Meow".to_string(),
             description: None,
             variants: BTreeSet::new(),
+            directives: Directives::default(),
             is_required: false.into(),
         };
 
@@ -178,6 +329,8 @@ mod tests {
                         name: "firstName".to_string(),
                         type_: FieldType::Named("String".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
@@ -185,14 +338,19 @@ mod tests {
                         type_: FieldType::Named("String".to_string()),
 
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "createdAt".to_string(),
                         type_: FieldType::Named("Date".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                 ],
+                directives: Directives::default(),
                 is_required: false.into(),
             },
         );
@@ -208,14 +366,19 @@ mod tests {
                         name: "title".to_string(),
                         type_: FieldType::Named("String".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "created_at".to_string(),
                         type_: FieldType::Named("Date".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                 ],
+                directives: Directives::default(),
                 is_required: false.into(),
             },
         );
@@ -237,6 +400,8 @@ mod tests {
                 alias: None,
                 name: "__typename".to_string(),
                 fields: Selection(vec![]),
+                stream: false,
+                position: Pos::default(),
             }),
             SelectionItem::InlineFragment(SelectionInlineFragment {
                 on: "User".to_string(),
@@ -244,6 +409,8 @@ mod tests {
                     alias: None,
                     name: "firstName".to_string(),
                     fields: Selection(vec![]),
+                    stream: false,
+                    position: Pos::default(),
                 })]),
             }),
             SelectionItem::InlineFragment(SelectionInlineFragment {
@@ -252,6 +419,8 @@ mod tests {
                     alias: None,
                     name: "title".to_string(),
                     fields: Selection(vec![]),
+                    stream: false,
+                    position: Pos::default(),
                 })]),
             }),
         ];
@@ -259,8 +428,10 @@ mod tests {
         let selection = Selection(fields);
         let prefix = "Meow";
         let union = GqlUnion {
+            name: "Meow".to_string(),
             description: None,
             variants: BTreeSet::new(),
+            directives: Directives::default(),
             is_required: false.into(),
         };
 
@@ -279,26 +450,35 @@ mod tests {
                         name: "__typename".to_string(),
                         type_: FieldType::Named(string_type()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "firstName".to_string(),
                         type_: FieldType::Named(string_type()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "lastName".to_string(),
                         type_: FieldType::Named(string_type()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "createdAt".to_string(),
                         type_: FieldType::Named("Date".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                 ],
+                directives: Directives::default(),
                 is_required: false.into(),
             },
         );
@@ -314,20 +494,27 @@ mod tests {
                         name: "__typename".to_string(),
                         type_: FieldType::Named(string_type()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "title".to_string(),
                         type_: FieldType::Named("String".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                     GqlObjectField {
                         description: None,
                         name: "createdAt".to_string(),
                         type_: FieldType::Named("Date".to_string()),
                         deprecation: DeprecationStatus::Current,
+                        directives: Directives::default(),
+                        arguments: Vec::new(),
                     },
                 ],
+                directives: Directives::default(),
                 is_required: false.into(),
             },
         );
@@ -343,13 +530,46 @@ mod tests {
             vec![
                 "# [ derive ( Deserialize ) ] ",
                 "pub struct MeowOnUser { # [ serde ( rename = \"firstName\" ) ] pub first_name : String , } ",
+                "impl MeowOnUser { # [ doc = r\" The GraphQL names of the fields in this struct, in selection order.\" ] ",
+                "pub fn field_names ( ) -> & 'static [ & 'static str ] { & [ \"firstName\" ] } } ",
                 "# [ derive ( Deserialize ) ] ",
                 "pub struct MeowOnOrganization { pub title : String , } ",
+                "impl MeowOnOrganization { # [ doc = r\" The GraphQL names of the fields in this struct, in selection order.\" ] ",
+                "pub fn field_names ( ) -> & 'static [ & 'static str ] { & [ \"title\" ] } } ",
                 "# [ derive ( Deserialize ) ] ",
                 "# [ serde ( tag = \"__typename\" ) ] ",
-                "pub enum Meow { User ( MeowOnUser ) , Organization ( MeowOnOrganization ) }",
+                "pub enum Meow { User ( MeowOnUser ) , Organization ( MeowOnOrganization ) } ",
+                "impl From < MeowOnUser > for Meow { fn from ( variant : MeowOnUser ) -> Self { Meow :: User ( variant ) } } ",
+                "impl From < MeowOnOrganization > for Meow { fn from ( variant : MeowOnOrganization ) -> Self { Meow :: Organization ( variant ) } }",
             ].into_iter()
                 .collect::<String>(),
         );
     }
+
+    #[test]
+    fn union_display_impl_works() {
+        let struct_name = Ident::new("Meow", Span::call_site());
+        let organization = "Organization".to_string();
+
+        let result = union_display_impl(
+            &quote!(::std),
+            &struct_name,
+            &["User".to_string()],
+            &[&organization],
+        );
+
+        assert_eq!(
+            result.to_string(),
+            quote! {
+                impl ::std::fmt::Display for Meow {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                        match *self {
+                            Meow::User(ref inner) => ::std::fmt::Display::fmt(inner, f),
+                            Meow::Organization => f.write_str("Organization"),
+                        }
+                    }
+                }
+            }.to_string()
+        );
+    }
 }
@@ -1,8 +1,14 @@
+use deprecation::DeprecationStatus;
+use enums::ENUMS_PREFIX;
+use failure;
 use field_type::FieldType;
 use graphql_parser;
-use proc_macro2::{Ident, Span, TokenStream};
+use graphql_parser::query;
+use heck::{CamelCase, MixedCase, ShoutySnakeCase, SnakeCase};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use query::QueryContext;
-use std::collections::BTreeMap;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Debug, Clone)]
 pub struct Variable {
@@ -17,13 +23,29 @@ impl Variable {
         match &self.default {
             Some(default) => {
                 let fn_name = Ident::new(&format!("default_{}", self.name), Span::call_site());
-                let ty = self.ty.to_rust(context, "");
-                let value = graphql_parser_value_to_literal(
-                    default,
-                    context,
-                    &self.ty,
-                    self.ty.is_optional(),
-                );
+                let ty = self.ty.to_rust_variable(context);
+                let value = if let Some(item_type) = self.ty.optional_list_item() {
+                    let item_literal = |item| {
+                        graphql_parser_value_to_literal(
+                            item,
+                            context,
+                            item_type,
+                            item_type.is_optional(),
+                        )
+                    };
+                    match default {
+                        graphql_parser::query::Value::List(items) => {
+                            let items = items.iter().map(item_literal);
+                            quote!(Some(::graphql_client::CoercedList::List(vec![#(#items,)*])))
+                        }
+                        item => {
+                            let item = item_literal(item);
+                            quote!(Some(::graphql_client::CoercedList::Single(#item)))
+                        }
+                    }
+                } else {
+                    graphql_parser_value_to_literal(default, context, &self.ty, self.ty.is_optional())
+                };
                 quote! {
                     pub fn #fn_name() -> #ty {
                         #value
@@ -36,6 +58,39 @@ impl Variable {
     }
 }
 
+/// The case of the serialized variable names, from the `rename_variables` option. Without it, variables are serialized with their name in the query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum VariablesCase {
+    Camel,
+    Snake,
+    Pascal,
+    ScreamingSnake,
+}
+
+impl VariablesCase {
+    pub(crate) fn parse(case: &str) -> Result<VariablesCase, failure::Error> {
+        match case.trim() {
+            "camelCase" => Ok(VariablesCase::Camel),
+            "snake_case" => Ok(VariablesCase::Snake),
+            "PascalCase" => Ok(VariablesCase::Pascal),
+            "SCREAMING_SNAKE_CASE" => Ok(VariablesCase::ScreamingSnake),
+            other => Err(format_err!(
+                "Unknown rename_variables case: {} (expected camelCase, snake_case, PascalCase or SCREAMING_SNAKE_CASE)",
+                other
+            )),
+        }
+    }
+
+    pub(crate) fn apply(self, name: &str) -> String {
+        match self {
+            VariablesCase::Camel => name.to_mixed_case(),
+            VariablesCase::Snake => name.to_snake_case(),
+            VariablesCase::Pascal => name.to_camel_case(),
+            VariablesCase::ScreamingSnake => name.to_shouty_snake_case(),
+        }
+    }
+}
+
 impl ::std::convert::From<graphql_parser::query::VariableDefinition> for Variable {
     fn from(def: graphql_parser::query::VariableDefinition) -> Variable {
         Variable {
@@ -46,6 +101,289 @@ impl ::std::convert::From<graphql_parser::query::VariableDefinition> for Variabl
     }
 }
 
+/// A variable passed to a field argument that the schema marks `@deprecated`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DeprecatedArgumentUse {
+    pub type_name: String,
+    pub field_name: String,
+    pub argument_name: String,
+    pub reason: Option<String>,
+}
+
+/// The variables of the operation called `operation_name`, and of the fragments it spreads, that are passed directly to deprecated arguments.
+pub(crate) fn deprecated_argument_uses(
+    context: &QueryContext,
+    document: &query::Document,
+    operation_name: &str,
+) -> BTreeMap<String, DeprecatedArgumentUse> {
+    let mut walker = DeprecatedArgumentWalker {
+        context,
+        document,
+        visited_fragments: Vec::new(),
+        uses: BTreeMap::new(),
+    };
+
+    for definition in &document.definitions {
+        let (name, root, selection_set) = match definition {
+            query::Definition::Operation(query::OperationDefinition::Query(q)) => {
+                (&q.name, &context.schema.query_type, &q.selection_set)
+            }
+            query::Definition::Operation(query::OperationDefinition::Mutation(m)) => {
+                (&m.name, &context.schema.mutation_type, &m.selection_set)
+            }
+            query::Definition::Operation(query::OperationDefinition::Subscription(s)) => {
+                (&s.name, &context.schema.subscription_type, &s.selection_set)
+            }
+            _ => continue,
+        };
+        if let (Some(name), Some(root)) = (name, root) {
+            if name == operation_name {
+                walker.walk(root, selection_set);
+            }
+        }
+    }
+
+    walker.uses
+}
+
+struct DeprecatedArgumentWalker<'a> {
+    context: &'a QueryContext,
+    document: &'a query::Document,
+    /// To avoid looping on fragment cycles.
+    visited_fragments: Vec<&'a str>,
+    uses: BTreeMap<String, DeprecatedArgumentUse>,
+}
+
+impl<'a> DeprecatedArgumentWalker<'a> {
+    fn walk(&mut self, on: &str, selection_set: &'a query::SelectionSet) {
+        for item in &selection_set.items {
+            match item {
+                query::Selection::Field(field) => self.walk_field(on, field),
+                query::Selection::FragmentSpread(spread) => {
+                    self.walk_fragment(&spread.fragment_name)
+                }
+                query::Selection::InlineFragment(fragment) => match &fragment.type_condition {
+                    Some(query::TypeCondition::On(on)) => self.walk(on, &fragment.selection_set),
+                    None => self.walk(on, &fragment.selection_set),
+                },
+            }
+        }
+    }
+
+    fn walk_field(&mut self, on: &str, field: &'a query::Field) {
+        let schema_field = match self.context.schema_field(on, &field.name) {
+            Some(schema_field) => schema_field,
+            None => return,
+        };
+
+        for (name, value) in &field.arguments {
+            let argument = schema_field.arguments.iter().find(|arg| &arg.name == name);
+            if let (Some(argument), query::Value::Variable(variable)) = (argument, value) {
+                if let DeprecationStatus::Deprecated(reason) = &argument.deprecation {
+                    self.uses.insert(
+                        variable.clone(),
+                        DeprecatedArgumentUse {
+                            type_name: on.to_string(),
+                            field_name: field.name.clone(),
+                            argument_name: name.clone(),
+                            reason: reason.clone(),
+                        },
+                    );
+                }
+            }
+        }
+
+        let type_name = schema_field.type_.inner_name_string();
+        self.walk(&type_name, &field.selection_set);
+    }
+
+    fn walk_fragment(&mut self, name: &'a str) {
+        if self.visited_fragments.contains(&name) {
+            return;
+        }
+        self.visited_fragments.push(name);
+
+        let document = self.document;
+        for definition in &document.definitions {
+            if let query::Definition::Fragment(fragment) = definition {
+                if fragment.name == name {
+                    let query::TypeCondition::On(on) = &fragment.type_condition;
+                    self.walk(on, &fragment.selection_set);
+                }
+            }
+        }
+    }
+}
+
+/// The `variables_schema` function, returning a JSON Schema for the variables of the operation. Input objects are described once in `definitions` and referenced from there, since they can be recursive.
+pub(crate) fn variables_schema_fn(variables: &[Variable], context: &QueryContext) -> TokenStream {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for variable in variables {
+        let name = context.variable_name(&variable.name);
+        properties.insert(name.clone(), json_schema_for_type(&variable.ty, context));
+        if !variable.ty.is_optional() && variable.default.is_none() {
+            required.push(Value::String(name));
+        }
+    }
+
+    let mut visited_inputs = BTreeSet::new();
+    let mut pending: Vec<String> = variables
+        .iter()
+        .map(|variable| variable.ty.inner_name_string())
+        .collect();
+    let mut definitions = Map::new();
+    while let Some(name) = pending.pop() {
+        let input = match context.schema.inputs.get(&name) {
+            Some(input) => input,
+            None => continue,
+        };
+        if !visited_inputs.insert(name) {
+            continue;
+        }
+        pending.extend(
+            input
+                .fields
+                .values()
+                .map(|field| field.type_.inner_name_string()),
+        );
+        pending.extend(input.parents.iter().cloned());
+        definitions.insert(input.name.clone(), input_json_schema(input, context));
+    }
+
+    let mut schema = ::serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    });
+    if !definitions.is_empty() {
+        schema["definitions"] = Value::Object(definitions);
+    }
+    let schema = json_value_expression(&schema);
+
+    quote! {
+        /// A JSON Schema describing the variables of the operation, with their serialized names and whether they are required.
+        pub fn variables_schema() -> ::graphql_client::serde_json::Value {
+            #schema
+        }
+    }
+}
+
+/// The `definitions` entry for an input object.
+fn input_json_schema(input: &::inputs::GqlInput, context: &QueryContext) -> Value {
+    let mut fields: Vec<_> = input.fields.values().collect();
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let properties: Map<String, Value> = fields
+        .iter()
+        .map(|field| {
+            (
+                field.name.clone(),
+                json_schema_for_type(&field.type_, context),
+            )
+        })
+        .collect();
+    let required: Vec<&String> = fields
+        .iter()
+        .filter(|field| !field.type_.is_optional())
+        .map(|field| &field.name)
+        .collect();
+
+    let mut schema = Map::new();
+    if let Some(description) = &input.description {
+        schema.insert("description".to_string(), ::serde_json::json!(description));
+    }
+    if !input.parents.is_empty() {
+        let references: Vec<Value> = input
+            .parents
+            .iter()
+            .map(|parent| ::serde_json::json!({ "$ref": format!("#/definitions/{}", parent) }))
+            .collect();
+        schema.insert("allOf".to_string(), Value::Array(references));
+    }
+    schema.insert("type".to_string(), ::serde_json::json!("object"));
+    schema.insert("properties".to_string(), Value::Object(properties));
+    schema.insert("required".to_string(), ::serde_json::json!(required));
+    Value::Object(schema)
+}
+
+/// The JSON Schema of a variable or input field type. Nullable types accept `null`, and whether a field can be left out is expressed in `required`.
+fn json_schema_for_type(ty: &FieldType, context: &QueryContext) -> Value {
+    match ty {
+        FieldType::Optional(inner) => nullable_json_schema(json_schema_for_type(inner, context)),
+        FieldType::Vector(inner) => {
+            ::serde_json::json!({ "type": "array", "items": json_schema_for_type(inner, context) })
+        }
+        FieldType::Named(name) => match name.as_str() {
+            "Boolean" => ::serde_json::json!({ "type": "boolean" }),
+            "Float" => ::serde_json::json!({ "type": "number" }),
+            "Int" => ::serde_json::json!({ "type": "integer" }),
+            "String" => ::serde_json::json!({ "type": "string" }),
+            "ID" if context.scalar_mapping("ID").is_none() => {
+                ::serde_json::json!({ "type": "string" })
+            }
+            name => {
+                if let Some(enm) = context.schema.enums.get(name) {
+                    let variants: Vec<&String> =
+                        enm.variants.iter().map(|variant| &variant.name).collect();
+                    ::serde_json::json!({ "type": "string", "enum": variants })
+                } else if context.schema.inputs.contains_key(name) {
+                    ::serde_json::json!({ "$ref": format!("#/definitions/{}", name) })
+                } else {
+                    // Custom scalars can be serialized to any JSON value.
+                    ::serde_json::json!({})
+                }
+            }
+        },
+    }
+}
+
+/// Extends `schema` to accept `null`: it is added to the `type` (and to the `enum` values), and references are combined with the `null` type. The empty schema already accepts anything.
+fn nullable_json_schema(mut schema: Value) -> Value {
+    if schema.get("$ref").is_some() {
+        return ::serde_json::json!({ "anyOf": [schema, { "type": "null" }] });
+    }
+    if let Some(ty) = schema.get("type").cloned() {
+        schema["type"] = ::serde_json::json!([ty, "null"]);
+    }
+    if let Some(Value::Array(values)) = schema.get_mut("enum") {
+        values.push(Value::Null);
+    }
+    schema
+}
+
+/// An expression building `value` in the generated code. Objects are filled with `Map::insert` calls: the `json!` macro reaches the recursion limit with the schemas of large inputs.
+fn json_value_expression(value: &Value) -> TokenStream {
+    match value {
+        Value::Null => quote!(::graphql_client::serde_json::Value::Null),
+        Value::Bool(value) => quote!(::graphql_client::serde_json::Value::Bool(#value)),
+        Value::Number(number) => {
+            let number = number.to_string();
+            quote!(::graphql_client::serde_json::from_str(#number).expect("JSON number"))
+        }
+        Value::String(value) => quote!(::graphql_client::serde_json::Value::String(#value.into())),
+        Value::Array(items) => {
+            let items = items.iter().map(json_value_expression);
+            quote!(::graphql_client::serde_json::Value::Array(
+                ::graphql_client::alloc::vec![#(#items),*]
+            ))
+        }
+        Value::Object(map) if map.is_empty() => quote!(
+            ::graphql_client::serde_json::Value::Object(::graphql_client::serde_json::Map::new())
+        ),
+        Value::Object(map) => {
+            let keys = map.keys();
+            let values = map.values().map(json_value_expression);
+            quote!({
+                let mut map = ::graphql_client::serde_json::Map::new();
+                #(map.insert(#keys.into(), #values);)*
+                ::graphql_client::serde_json::Value::Object(map)
+            })
+        }
+    }
+}
+
 fn graphql_parser_value_to_literal(
     value: &graphql_parser::query::Value,
     context: &QueryContext,
@@ -63,12 +401,34 @@ fn graphql_parser_value_to_literal(
         Value::String(s) => quote!(#s.to_string()),
         Value::Variable(_) => panic!("variable in variable"),
         Value::Null => panic!("null as default value"),
-        Value::Float(f) => quote!(#f),
+        Value::Float(f) => {
+            // Unsuffixed, so it is also a valid `f32` when `Float` is mapped to it.
+            let f = Literal::f64_unsuffixed(*f);
+            quote!(#f)
+        }
         Value::Int(i) => {
             let i = i.as_i64();
             quote!(#i)
         }
-        Value::Enum(en) => quote!(#en),
+        Value::Enum(en) => {
+            let type_name = ty.inner_name_string();
+            let enum_name = Ident::new(
+                &context.prefixed_type_name(&format!("{}{}", ENUMS_PREFIX, type_name)),
+                Span::call_site(),
+            );
+            let is_known_variant = context
+                .schema
+                .enums
+                .get(&type_name)
+                .map(|enm| enm.variants.iter().any(|variant| &variant.name == en))
+                .unwrap_or(false);
+            if is_known_variant {
+                let variant = Ident::new(en, Span::call_site());
+                quote!(#enum_name::#variant)
+            } else {
+                quote!(#enum_name::Other(#en.to_string()))
+            }
+        }
         Value::List(inner) => {
             let elements = inner
                 .iter()
@@ -105,7 +465,7 @@ fn render_object_literal(
         .fields
         .iter()
         .map(|(name, field)| {
-            let field_name = Ident::new(&name, Span::call_site());
+            let field_name = Ident::new(&name.to_snake_case(), Span::call_site());
             let provided_value = object.get(name);
             match provided_value {
                 Some(default_value) => {
@@ -120,8 +480,31 @@ fn render_object_literal(
                 None => quote!(#field_name: None),
             }
         }).collect();
+    // The fields of the parent input objects are provided in the same object.
+    let parents = schema_type.parents.iter().map(|parent| {
+        let field_name = Ident::new(&parent.to_snake_case(), Span::call_site());
+        let value = render_object_literal(object, &FieldType::Named(parent.clone()), context);
+        quote!(#field_name: #value)
+    });
 
     quote!(#constructor {
+        #(#parents,)*
         #(#fields,)*
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variables_cases_are_applied() {
+        let rename = |case: &str| VariablesCase::parse(case).unwrap().apply("episodeForHero");
+
+        assert_eq!(rename("camelCase"), "episodeForHero");
+        assert_eq!(rename("snake_case"), "episode_for_hero");
+        assert_eq!(rename("PascalCase"), "EpisodeForHero");
+        assert_eq!(rename("SCREAMING_SNAKE_CASE"), "EPISODE_FOR_HERO");
+        assert!(VariablesCase::parse("kebab-case").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+#[macro_use]
 extern crate failure;
 extern crate graphql_client_codegen;
 extern crate proc_macro;
@@ -14,11 +15,33 @@ pub fn graphql_query_derive(input: proc_macro::TokenStream) -> proc_macro::Token
     let input = TokenStream::from(input);
     let ast = syn::parse2(input).expect("Derive input is well formed");
     let (query_path, schema_path) = build_query_and_schema_path(&ast);
-    let options = build_graphql_client_derive_options(&ast);
+    let options = build_graphql_client_derive_options(&ast).unwrap();
     let gen = generate_module_token_stream(query_path, schema_path, Some(options)).unwrap();
+    let gen = match attributes::extract_attr(&ast, "output_directory") {
+        Ok(output_directory) => include_generated_module(&ast, &gen, &output_directory),
+        Err(_) => gen,
+    };
     gen.into()
 }
 
+/// Writes the generated code to the `output_directory` and includes the file instead.
+fn include_generated_module(
+    input: &syn::DeriveInput,
+    gen: &TokenStream,
+    output_directory: &str,
+) -> TokenStream {
+    let cargo_manifest_dir =
+        ::std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR env variable is defined");
+    let output_directory = ::std::path::Path::new(&cargo_manifest_dir).join(output_directory);
+    let path = write_generated_module(gen, &input.ident.to_string(), &output_directory)
+        .context("Writing the generated module")
+        .unwrap();
+
+    format!("include!({:?});", path.display().to_string())
+        .parse()
+        .expect("include! is well formed")
+}
+
 fn build_query_and_schema_path(
     input: &syn::DeriveInput,
 ) -> (std::path::PathBuf, std::path::PathBuf) {
@@ -33,19 +56,104 @@ fn build_query_and_schema_path(
     let schema_path = attributes::extract_attr(input, "schema_path")
         .context("Extracting schema path")
         .unwrap();
-    let schema_path = ::std::path::Path::new(&cargo_manifest_dir).join(schema_path);
+    let schema_path = if remote_schema::is_url(&schema_path) {
+        let cache_dir = ::std::path::Path::new(&cargo_manifest_dir).join(remote_schema::CACHE_DIR);
+        remote_schema::fetch_schema(&schema_path, &cache_dir)
+            .context("Fetching the schema")
+            .unwrap()
+    } else {
+        ::std::path::Path::new(&cargo_manifest_dir).join(schema_path)
+    };
     (query_path, schema_path)
 }
 
-fn build_graphql_client_derive_options(input: &syn::DeriveInput) -> GraphQLClientDeriveOptions {
+fn build_graphql_client_derive_options(
+    input: &syn::DeriveInput,
+) -> Result<GraphQLClientDeriveOptions, failure::Error> {
     let response_derives = attributes::extract_attr(input, "response_derives").ok();
+    let response_derives_exclusive =
+        attributes::extract_attr(input, "response_derives_exclusive").ok();
     // The user can determine what to do about deprecations.
     let deprecation_strategy = deprecation::extract_deprecation_strategy(input).unwrap_or_default();
+    let scalars = attributes::extract_attr(input, "scalars").ok();
+    let scalars_with = attributes::extract_attr(input, "scalars_with").ok();
+    let newtype_scalars = attributes::extract_attr(input, "newtype_scalars").ok();
+    let input_inheritance = attributes::extract_attr(input, "input_inheritance")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let into = attributes::extract_attr(input, "into").ok();
+    let ord_derives = attributes::extract_attr(input, "ord_derives")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let snapshot_test = attributes::extract_attr(input, "snapshot_test")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let federation_mode = attributes::extract_attr(input, "federation_mode")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let strict_response = attributes::extract_attr(input, "strict_response")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let skip_query_validation = attributes::extract_attr(input, "skip_query_validation")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let deserializer_impl = attributes::extract_attr(input, "deserializer_impl")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let no_std = attributes::extract_attr(input, "no_std")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let generate_mock = attributes::extract_attr(input, "generate_mock")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    let generate_structs = attributes::extract_attr(input, "generate_structs")
+        .map(|value| value == "true")
+        .unwrap_or(true);
+    let response_type_prefix = attributes::extract_attr(input, "response_type_prefix").ok();
+    let response_wrapper = attributes::extract_attr(input, "response_wrapper").ok();
+    let rename_variables = attributes::extract_attr(input, "rename_variables").ok();
+    let allow_deprecated_fields = attributes::extract_attr(input, "allow_deprecated_fields").ok();
+    let max_query_depth = match attributes::extract_attr(input, "max_query_depth") {
+        Ok(depth) => Some(depth.parse().map_err(|_| {
+            format_err!(
+                "Invalid max_query_depth: expected a positive integer, found {:?}",
+                depth
+            )
+        })?),
+        Err(_) => None,
+    };
+    let warn_missing_fields = attributes::extract_attr(input, "warn_missing_fields")
+        .map(|value| value == "true")
+        .unwrap_or(false);
 
-    GraphQLClientDeriveOptions {
+    Ok(GraphQLClientDeriveOptions {
         struct_name: input.clone().ident.to_string(),
         additional_derives: response_derives,
+        variables_derives: None,
+        response_derives: None,
+        response_derives_exclusive,
         deprecation_strategy: Some(deprecation_strategy),
         module_visibility: input.clone().vis,
-    }
+        scalars,
+        scalars_with,
+        newtype_scalars,
+        input_inheritance,
+        into,
+        ord_derives,
+        max_query_depth,
+        warning_span: input.ident.span(),
+        warn_missing_fields,
+        snapshot_test,
+        response_type_prefix,
+        response_wrapper,
+        allow_deprecated_fields,
+        federation_mode,
+        generate_mock,
+        generate_structs,
+        rename_variables,
+        strict_response,
+        skip_query_validation,
+        deserializer_impl,
+        no_std,
+    })
 }